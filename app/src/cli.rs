@@ -0,0 +1,325 @@
+//! Headless scans from the command line
+//!
+//! `net_monkey --scan 192.168.1.0/24 --ports 22,80,443 --format json` scans
+//! without opening a window, prints the results to stdout and exits. Probing
+//! uses the scan settings saved from the GUI.
+//!
+//! Release builds on Windows have no console of their own, so anything
+//! printed goes to the console the program was started from.
+//!
+//! `--theme <name>` starts the GUI in a theme for that run only. Without it,
+//! a non-empty `NO_COLOR` starts it in the black and white
+//! [`HIGH_CONTRAST_THEME`].
+
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use net_monkey_core::{
+    MIN_SCAN_PREFIX, NoopProcessor, ScanMessage, ScanRange, ScannedIp, scan_stream,
+};
+use net_monkey_theme::{HIGH_CONTRAST_THEME, ThemeDefinition};
+
+use crate::export;
+use crate::views::settings::{AppConfig, ParsedPorts};
+
+pub const USAGE: &str = "\
+Usage: net_monkey [--theme <name>]
+       net_monkey --scan <ip>[/<prefix>] [--ports <list>] [--format <format>]
+
+Without --scan the GUI is started.
+
+Options:
+  --theme <name>          Start the GUI in this theme, without saving it
+  --scan <ip>[/<prefix>]  Scan the subnet around <ip>, or only <ip> without a prefix.
+                          IPv4 prefixes must be /16 or longer
+  --ports <list>          Ports to probe, like 22,80,8000-8080 (default 80,443)
+  --format <format>       json, csv or prometheus (default json)
+  -h, --help              Show this message
+
+Set NO_COLOR to start in the High Contrast theme unless --theme is given.";
+
+/// How results are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Csv,
+    Prometheus,
+}
+
+impl OutputFormat {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "prometheus" => Ok(OutputFormat::Prometheus),
+            _ => Err(format!("Unknown format \"{name}\"")),
+        }
+    }
+}
+
+/// What the command line asked for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Gui {
+        theme: Option<String>,
+    },
+    Help,
+    Scan {
+        base: IpAddr,
+        prefix: u8,
+        ports: Vec<u16>,
+        format: OutputFormat,
+    },
+}
+
+/// Read the arguments after the program name
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let (mut target, mut ports, mut format) = (None, None, OutputFormat::default());
+    let mut theme = None;
+    while let Some(arg) = args.next() {
+        // Both `--ports 22` and `--ports=22`
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        if matches!(flag.as_str(), "-h" | "--help") {
+            return Ok(Command::Help);
+        }
+        let value = inline
+            .or_else(|| args.next())
+            .ok_or_else(|| format!("{flag} needs a value"))?;
+        match flag.as_str() {
+            "--scan" => target = Some(parse_target(&value)?),
+            "--ports" => {
+                let parsed = ParsedPorts::parse(&value);
+                if let Some(error) = parsed.error() {
+                    return Err(error);
+                }
+                ports = Some(parsed.ports);
+            }
+            "--format" => format = OutputFormat::parse(&value)?,
+            "--theme" => theme = Some(value),
+            _ => return Err(format!("Unknown option {flag}")),
+        }
+    }
+
+    match target {
+        Some(_) if theme.is_some() => Err(String::from("--theme only applies to the GUI")),
+        Some((base, prefix)) => Ok(Command::Scan {
+            base,
+            prefix,
+            ports: ports.unwrap_or_else(|| AppConfig::default().ports),
+            format,
+        }),
+        None if ports.is_some() || format != OutputFormat::default() => {
+            Err(String::from("--ports and --format need --scan"))
+        }
+        None => Ok(Command::Gui { theme }),
+    }
+}
+
+/// Theme the GUI starts in for this run, from `--theme` or `NO_COLOR`
+fn startup_theme(name: Option<String>) -> Result<Option<ThemeDefinition>, String> {
+    let manager = AppConfig::theme_manager();
+    match name {
+        Some(name) => manager.load_theme(&name).map(Some).ok_or_else(|| {
+            format!(
+                "Unknown theme \"{name}\", choose from: {}",
+                manager.available_themes().join(", ")
+            )
+        }),
+        None if no_color() => Ok(manager.load_theme(HIGH_CONTRAST_THEME)),
+        None => Ok(None),
+    }
+}
+
+/// Whether `NO_COLOR` is set to anything but an empty string, see no-color.org
+fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// `ip/prefix`, or a lone address as a single host
+fn parse_target(text: &str) -> Result<(IpAddr, u8), String> {
+    let (ip, prefix) = match text.split_once('/') {
+        Some((ip, prefix)) => (ip, Some(prefix)),
+        None => (text, None),
+    };
+    let ip: IpAddr = ip
+        .trim()
+        .parse()
+        .map_err(|_| format!("\"{ip}\" isn't an IP address"))?;
+    // Wider IPv4 subnets would be narrowed by the scanner without asking
+    let (min_prefix, max_prefix) = match ip {
+        IpAddr::V4(_) => (MIN_SCAN_PREFIX, 32),
+        IpAddr::V6(_) => (0, 128),
+    };
+    let prefix = match prefix {
+        Some(prefix) => prefix
+            .trim()
+            .parse::<u8>()
+            .ok()
+            .filter(|prefix| (min_prefix..=max_prefix).contains(prefix))
+            .ok_or_else(|| format!("Prefix must be {min_prefix}-{max_prefix}"))?,
+        None => max_prefix,
+    };
+    Ok((ip, prefix))
+}
+
+/// Run a headless scan if the arguments ask for one, exiting once it's
+/// done. Returns the theme to start in if the GUI should start instead.
+pub fn run_from_args() -> Option<ThemeDefinition> {
+    let command = match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Gui { theme }) => match startup_theme(theme) {
+            Ok(theme) => return theme,
+            Err(e) => Err(e),
+        },
+        Ok(command) => Ok(command),
+        Err(e) => Err(format!("{e}\n\n{USAGE}")),
+    };
+
+    // Everything from here on prints
+    attach_console();
+    let code = match command {
+        Ok(Command::Gui { .. }) => unreachable!("the GUI has already been started"),
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            0
+        }
+        Ok(Command::Scan {
+            base,
+            prefix,
+            ports,
+            format,
+        }) => match scan(base, prefix, ports, format) {
+            Ok(output) => {
+                print!("{output}");
+                0
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                1
+            }
+        },
+        Err(e) => {
+            eprintln!("{e}");
+            2
+        }
+    };
+    std::process::exit(code);
+}
+
+/// Attach to the console of the shell that started us, since release builds
+/// use the Windows GUI subsystem and otherwise print nowhere
+#[cfg(target_os = "windows")]
+fn attach_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // Fails harmlessly when there's no parent console or one is already attached
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_console() {}
+
+/// Scan to completion, returning the results formatted for printing
+fn scan(base: IpAddr, prefix: u8, ports: Vec<u16>, format: OutputFormat) -> Result<String, String> {
+    let config = AppConfig::load().unwrap_or_default().scan;
+    let runtime =
+        tokio::runtime::Runtime::new().map_err(|e| format!("Failed to start the scanner: {e}"))?;
+
+    let scan = scan_stream(
+        ScanRange::new(base, prefix),
+        ports,
+        config,
+        Arc::new(NoopProcessor),
+    );
+    let found = scan.fold(Ok(Vec::new()), |found, message| {
+        futures::future::ready(match (found, message) {
+            (Ok(mut found), ScanMessage::Result(result)) => {
+                found.push(result);
+                Ok(found)
+            }
+            // Only a scan that couldn't probe at all fails without an address
+            (Ok(_), ScanMessage::Error { ip: None, error }) => Err(format!("Scan failed: {error}")),
+            (found, _) => found,
+        })
+    });
+    let mut results: Vec<ScannedIp> = runtime.block_on(found)?;
+    results.sort_by_key(|result| result.ip);
+    format_results(&results, format)
+}
+
+fn format_results(results: &[ScannedIp], format: OutputFormat) -> Result<String, String> {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(results)
+            .map(|json| json + "\n")
+            .map_err(|e| format!("Failed to format results: {e}")),
+        OutputFormat::Csv => Ok(export::results_to_csv(results)),
+        OutputFormat::Prometheus => Ok(net_monkey_core::to_prometheus(
+            results,
+            std::time::SystemTime::now(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(args("")), Ok(Command::Gui { theme: None }));
+        assert_eq!(
+            parse_args(vec![String::from("--theme=High Contrast")]),
+            Ok(Command::Gui {
+                theme: Some(String::from("High Contrast"))
+            })
+        );
+        assert!(parse_args(args("--scan 10.0.0.1 --theme Dark")).is_err());
+        assert_eq!(
+            parse_args(args("--scan 10.0.0.0/24 --help")),
+            Ok(Command::Help)
+        );
+        assert_eq!(
+            parse_args(args("--scan 192.168.1.0/24 --ports 22,80-81 --format=csv")),
+            Ok(Command::Scan {
+                base: "192.168.1.0".parse().unwrap(),
+                prefix: 24,
+                ports: vec![22, 80, 81],
+                format: OutputFormat::Csv,
+            })
+        );
+        // A lone address is a single host
+        assert_eq!(
+            parse_args(args("--scan fd00::1")),
+            Ok(Command::Scan {
+                base: "fd00::1".parse().unwrap(),
+                prefix: 128,
+                ports: vec![80, 443],
+                format: OutputFormat::Json,
+            })
+        );
+
+        assert!(parse_args(args("--scan 10.0.0.0/33")).is_err());
+        // Too wide to sweep, rather than quietly scanning a /16
+        assert!(parse_args(args("--scan 10.0.0.0/8")).is_err());
+        assert!(parse_args(args("--scan fd00::/64")).is_ok());
+        assert!(parse_args(args("--scan")).is_err());
+        assert!(parse_args(args("--scan 10.0.0.1 --format xml")).is_err());
+        assert!(parse_args(args("--scan 10.0.0.1 --ports 0")).is_err());
+        assert!(parse_args(args("--ports 22")).is_err());
+        assert!(parse_args(args("--verbose 1")).is_err());
+    }
+}
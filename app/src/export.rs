@@ -0,0 +1,118 @@
+//! Export of scan results to image files and text
+//!
+//! Results are drawn as a subnet heatmap: a 16x16 grid with one cell per
+//! final address octet, colored by ping the same way as the results view.
+//! They can also be written as CSV with the same columns "Copy as CSV row"
+//! uses.
+
+use std::io::Cursor;
+
+use iced::Color;
+use image::{ImageOutputFormat, Rgba, RgbaImage};
+use net_monkey_core::ScannedIp;
+use net_monkey_theme::SimpleColors;
+
+use crate::views::ip_scan::csv_row;
+use crate::views::settings::PingThresholds;
+
+const GRID_SIZE: u32 = 16;
+const CELL_SIZE: u32 = 24;
+const CELL_GAP: u32 = 2;
+
+/// Render scan results as a subnet heatmap and encode it as PNG
+pub fn results_to_png(
+    ips: &[ScannedIp],
+    colors: SimpleColors,
+    thresholds: PingThresholds,
+) -> Result<Vec<u8>, image::ImageError> {
+    let side = GRID_SIZE * (CELL_SIZE + CELL_GAP) + CELL_GAP;
+    let mut image = RgbaImage::from_pixel(side, side, to_rgba(colors.background_color()));
+
+    let mut cells = [None; (GRID_SIZE * GRID_SIZE) as usize];
+    for scanned in ips {
+        cells[host_octet(scanned) as usize] = Some(thresholds.color(scanned.ping, colors));
+    }
+
+    for (index, cell) in cells.iter().enumerate() {
+        let color = cell.unwrap_or_else(|| colors.container_color());
+        let x = (index as u32 % GRID_SIZE) * (CELL_SIZE + CELL_GAP) + CELL_GAP;
+        let y = (index as u32 / GRID_SIZE) * (CELL_SIZE + CELL_GAP) + CELL_GAP;
+        fill_rect(&mut image, x, y, CELL_SIZE, to_rgba(color));
+    }
+
+    let mut png = Cursor::new(Vec::new());
+    image.write_to(&mut png, ImageOutputFormat::Png)?;
+    Ok(png.into_inner())
+}
+
+/// Scan results as CSV with a header row, one host per line
+pub fn results_to_csv(ips: &[ScannedIp]) -> String {
+    let mut csv = String::from("ip,ping_ms,state,ports,mac,vendor,os\n");
+    for scanned in ips {
+        csv += &csv_row(scanned);
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Lowest address byte, which picks the heatmap cell
+fn host_octet(scanned: &ScannedIp) -> u8 {
+    match scanned.ip {
+        std::net::IpAddr::V4(ipv4) => ipv4.octets()[3],
+        std::net::IpAddr::V6(ipv6) => ipv6.octets()[15],
+    }
+}
+
+fn fill_rect(image: &mut RgbaImage, x: u32, y: u32, size: u32, color: Rgba<u8>) {
+    for py in y..y + size {
+        for px in x..x + size {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn to_rgba(color: Color) -> Rgba<u8> {
+    Rgba(color.into_rgba8())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn test_results_to_png_is_valid() {
+        let ips = vec![
+            ScannedIp::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)), true, 5, vec![80]),
+            ScannedIp::new(
+                IpAddr::V4(Ipv4Addr::new(192, 168, 1, 200)),
+                true,
+                300,
+                vec![],
+            ),
+        ];
+
+        let png = results_to_png(&ips, SimpleColors::DARK, PingThresholds::default()).unwrap();
+
+        assert!(png.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]));
+        let decoded = image::load_from_memory(&png).unwrap();
+        assert_eq!(
+            decoded.width(),
+            GRID_SIZE * (CELL_SIZE + CELL_GAP) + CELL_GAP
+        );
+    }
+
+    #[test]
+    fn test_results_to_csv() {
+        let ips = [ScannedIp::new(
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 7)),
+            true,
+            2,
+            vec![22],
+        )];
+        assert_eq!(
+            results_to_csv(&ips),
+            "ip,ping_ms,state,ports,mac,vendor,os\n10.0.0.7,2,up,22,,,\n"
+        );
+    }
+}
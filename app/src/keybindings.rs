@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Key, Modifiers, Named};
+
+/// Actions that can be triggered from the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    Fullscreen,
+    Windowed,
+    Scan,
+    CancelScan,
+    NextTab,
+    ToggleTheme,
+}
+
+impl Action {
+    /// All rebindable actions, in the order shown in settings
+    pub const ALL: &[Action] = &[
+        Action::Fullscreen,
+        Action::Windowed,
+        Action::Scan,
+        Action::CancelScan,
+        Action::NextTab,
+        Action::ToggleTheme,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Fullscreen => "Fullscreen",
+            Action::Windowed => "Windowed",
+            Action::Scan => "Start Scan",
+            Action::CancelScan => "Cancel Scan",
+            Action::NextTab => "Next Tab",
+            Action::ToggleTheme => "Toggle Theme",
+        }
+    }
+}
+
+/// A key together with the modifiers held while pressing it
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeyCombo {
+    /// Named keys use their iced name (e.g. "ArrowUp"), characters are lowercase
+    pub key: String,
+    #[serde(default)]
+    pub shift: bool,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub alt: bool,
+    #[serde(default)]
+    pub logo: bool,
+}
+
+impl KeyCombo {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            shift: false,
+            ctrl: false,
+            alt: false,
+            logo: false,
+        }
+    }
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+    pub fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// Build a combo from a key event, ignoring presses of lone modifier keys
+    pub fn from_event(key: &Key, mods: Modifiers) -> Option<Self> {
+        let key = match key {
+            Key::Named(Named::Shift | Named::Control | Named::Alt | Named::Super | Named::Meta) => {
+                return None;
+            }
+            Key::Named(named) => format!("{named:?}"),
+            Key::Character(c) => c.to_lowercase(),
+            Key::Unidentified => return None,
+        };
+        Some(Self {
+            key,
+            shift: mods.shift(),
+            ctrl: mods.control(),
+            alt: mods.alt(),
+            logo: mods.logo(),
+        })
+    }
+
+    /// Zero-based tab that Ctrl+1 through Ctrl+9 jump to
+    pub fn tab_index(&self) -> Option<usize> {
+        if !self.ctrl || self.shift || self.alt || self.logo {
+            return None;
+        }
+        match self.key.parse::<usize>() {
+            Ok(n @ 1..=9) => Some(n - 1),
+            _ => None,
+        }
+    }
+
+    /// Tab and Shift+Tab always move focus and can't be rebound
+    pub fn is_reserved(&self) -> bool {
+        self.key == "Tab" && !self.ctrl && !self.alt && !self.logo
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (held, name) in [
+            (self.ctrl, "Ctrl+"),
+            (self.alt, "Alt+"),
+            (self.logo, "Super+"),
+            (self.shift, "Shift+"),
+        ] {
+            if held {
+                f.write_str(name)?;
+            }
+        }
+        f.write_str(&self.key)
+    }
+}
+
+/// Reasons a key combo can't be bound to an action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindError {
+    Reserved(KeyCombo),
+    Conflict(Action),
+}
+
+impl fmt::Display for BindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindError::Reserved(combo) => write!(f, "{combo} is reserved"),
+            BindError::Conflict(action) => write!(f, "already bound to {}", action.label()),
+        }
+    }
+}
+
+pub fn default_keybindings() -> HashMap<Action, KeyCombo> {
+    HashMap::from([
+        (Action::Fullscreen, KeyCombo::new("ArrowUp").shift()),
+        (Action::Windowed, KeyCombo::new("ArrowDown").shift()),
+        (Action::Scan, KeyCombo::new("Enter").ctrl()),
+        (Action::CancelScan, KeyCombo::new("Escape")),
+        (Action::NextTab, KeyCombo::new("Tab").ctrl()),
+        (Action::ToggleTheme, KeyCombo::new("t").ctrl()),
+    ])
+}
+
+/// Find the action bound to `combo`, if any
+pub fn action_for(bindings: &HashMap<Action, KeyCombo>, combo: &KeyCombo) -> Option<Action> {
+    bindings
+        .iter()
+        .find_map(|(action, bound)| (bound == combo).then_some(*action))
+}
+
+/// Bind `combo` to `action`, refusing reserved combos and ones used by another action
+pub fn bind(
+    bindings: &mut HashMap<Action, KeyCombo>,
+    action: Action,
+    combo: KeyCombo,
+) -> Result<(), BindError> {
+    if combo.is_reserved() {
+        return Err(BindError::Reserved(combo));
+    }
+    match action_for(bindings, &combo) {
+        Some(other) if other != action => Err(BindError::Conflict(other)),
+        _ => {
+            bindings.insert(action, combo);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_have_no_conflicts() {
+        let bindings = default_keybindings();
+        for action in Action::ALL {
+            let combo = &bindings[action];
+            assert!(!combo.is_reserved());
+            assert_eq!(action_for(&bindings, combo), Some(*action));
+        }
+    }
+
+    #[test]
+    fn test_bind_rejects_reserved_and_conflicts() {
+        let mut bindings = default_keybindings();
+        let tab = KeyCombo::from_event(&Key::Named(Named::Tab), Modifiers::SHIFT).unwrap();
+        assert_eq!(
+            bind(&mut bindings, Action::Scan, tab.clone()),
+            Err(BindError::Reserved(tab))
+        );
+        assert_eq!(
+            bind(&mut bindings, Action::Scan, KeyCombo::new("Escape")),
+            Err(BindError::Conflict(Action::CancelScan))
+        );
+
+        let f5 = KeyCombo::from_event(&Key::Named(Named::F5), Modifiers::empty()).unwrap();
+        assert_eq!(bind(&mut bindings, Action::Scan, f5.clone()), Ok(()));
+        assert_eq!(action_for(&bindings, &f5), Some(Action::Scan));
+    }
+
+    #[test]
+    fn test_tab_index() {
+        let ctrl_2 = KeyCombo::from_event(&Key::Character("2".into()), Modifiers::CTRL).unwrap();
+        assert_eq!(ctrl_2.tab_index(), Some(1));
+        assert_eq!(KeyCombo::new("2").tab_index(), None);
+        assert_eq!(KeyCombo::new("0").ctrl().tab_index(), None);
+        assert_eq!(KeyCombo::new("2").ctrl().shift().tab_index(), None);
+    }
+
+    #[test]
+    fn test_modifier_only_presses_are_ignored() {
+        assert_eq!(
+            KeyCombo::from_event(&Key::Named(Named::Shift), Modifiers::SHIFT),
+            None
+        );
+    }
+}
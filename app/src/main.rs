@@ -1,55 +1,74 @@
 // #![allow(unused_imports, unused)]
-#![feature(addr_parse_ascii, result_option_map_or_default)]
+#![feature(addr_parse_ascii)]
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 #[cfg(feature = "cosmic")]
 use cosmic::app::{Core, Settings, Task};
 #[cfg(feature = "cosmic")]
+use cosmic::iced::{Event, event};
+#[cfg(feature = "cosmic")]
 use cosmic::iced_core::Size;
 #[cfg(feature = "cosmic")]
 use cosmic::keyboard::{Key, Modifiers, key::Named};
 #[cfg(feature = "cosmic")]
+use cosmic::widget::image as iced_image;
+#[cfg(feature = "cosmic")]
 use cosmic::widget::image::Handle;
 #[cfg(feature = "cosmic")]
 use cosmic::widget::{Image, Row, button, center, column, container, text};
 #[cfg(feature = "cosmic")]
-use cosmic::widget::{button::Status, image as iced_image};
-#[cfg(feature = "cosmic")]
 use cosmic::window::{Mode, icon::from_file_data};
 #[cfg(feature = "cosmic")]
-use cosmic::{ApplicationExt, Center, Color, Element, Fill, Subscription, keyboard, window};
+use cosmic::{ApplicationExt, Center, Element, Fill, Subscription, keyboard, window};
 #[cfg(feature = "cosmic")]
 use image::ImageFormat;
 
 #[cfg(not(feature = "cosmic"))]
 use iced::keyboard::{Key, Modifiers, key::Named};
 #[cfg(not(feature = "cosmic"))]
+use iced::widget::image as iced_image;
+#[cfg(not(feature = "cosmic"))]
 use iced::widget::image::Handle;
 #[cfg(not(feature = "cosmic"))]
 use iced::widget::{Image, Row, button, center, column, container, text};
 #[cfg(not(feature = "cosmic"))]
-use iced::widget::{button::Status, image as iced_image};
-#[cfg(not(feature = "cosmic"))]
-use iced::window::{Mode, Settings, icon::from_file_data};
+use iced::window::{Mode, Position, Settings, icon::from_file_data};
 #[cfg(not(feature = "cosmic"))]
-use iced::{Center, Color, Element, Fill, Subscription, Task, Theme, keyboard};
+use iced::{Center, Element, Event, Fill, Subscription, Task, Theme, event, keyboard, window};
 #[cfg(not(feature = "cosmic"))]
 use image::ImageFormat;
 
-use crate::views::settings::{AppConfig, ChangeConfig, IpScannerApp, ModeTab};
-use net_monkey_core::{NetworkAdapter, ScannedIp, get_network_adapters};
-use net_monkey_theme::helpers;
-
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use crate::keybindings::{Action, KeyCombo};
+use crate::views::ip_scan::ScanStats;
+use crate::views::settings::{AppConfig, ChangeConfig, IpScannerApp, ModeTab, SortKey};
+use crate::views::theme_edit::ColorType;
+use futures::{FutureExt, StreamExt};
+use net_monkey_core::{
+    ADAPTER_POLL_INTERVAL, CancelToken, ConnectionEvent, NetworkAdapter, PacketEncoding,
+    ProbeError, ScanMessage, ScannedIp, TaskState, adapter_changes, create_tcp_client,
+    create_udp_client, get_network_adapters, scan_single_host,
+};
+use net_monkey_theme::helpers::{self, ButtonVariant};
+use net_monkey_theme::{Hsl, ThemeChanged, ThemeDefinition};
+
+mod cli;
+mod export;
+mod keybindings;
 mod views;
 
 #[cfg(feature = "cosmic")]
 pub fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let settings = Settings::default().size(Size::new(500.0, 800.0));
+    let theme_override = cli::run_from_args();
+    let config = AppConfig::load().unwrap_or_default();
+    let settings = Settings::default()
+        .size(Size::new(config.window.width, config.window.height))
+        .scale_factor(config.ui_scale())
+        .default_font(config.font.font());
 
-    let input = (
-        AppConfig::load().unwrap_or_default(),
-        get_network_adapters(),
-    );
+    let input = (config, get_network_adapters(), theme_override);
 
     cosmic::app::run::<IpScannerApp>(settings, input)?;
     Ok(())
@@ -60,16 +79,31 @@ pub fn main() -> iced::Result {
     // #[cfg(not(target_arch = "wasm32"))]
     // tracing_subscriber::fmt::init();
 
+    let theme_override = cli::run_from_args();
+
+    // Loaded again once running, this copy only sets up the window and font
+    let config = AppConfig::load().unwrap_or_default();
+    let geometry = config.window;
     let window = Settings {
         icon: from_file_data(APP_ICON, Some(ImageFormat::Ico)).ok(),
+        size: iced::Size::new(geometry.width, geometry.height),
+        position: match geometry.position {
+            Some((x, y)) => Position::Specific(iced::Point::new(x, y)),
+            None => Position::default(),
+        },
         ..Default::default()
     };
-    IpScannerApp::run_with(window)
+    IpScannerApp::run_with(window, config.font.font(), theme_override)
 }
 
 const ICON_FONT: &[u8] = include_bytes!("../assets/icons.ttf");
 const APP_BACKGROUND: &[u8] = include_bytes!("../assets/background.png");
 const APP_ICON: &[u8] = include_bytes!("../assets/net_monkey.ico");
+/// Quiet period after a config change before it's written, so dragging a
+/// slider saves once rather than on every step
+const CONFIG_SAVE_DELAY: Duration = Duration::from_millis(500);
+/// How often the OS light/dark preference is checked while the theme follows it
+const SYSTEM_THEME_POLL: Duration = Duration::from_secs(5);
 const TABS: &[ModeTab] = &[
     ModeTab::IpScan,
     ModeTab::TCPclient,
@@ -83,17 +117,103 @@ pub fn hero_image() -> Image<Handle> {
 
 #[derive(Debug, Clone)]
 pub enum Msg {
-    Loaded((AppConfig, Vec<NetworkAdapter>)),
+    Loaded(Box<(AppConfig, Vec<NetworkAdapter>)>),
+    /// An adapter was added, removed or changed since the list was last fetched
+    AdaptersChanged(Vec<NetworkAdapter>),
     TabChanged(ModeTab),
-    FocusMove { shift: bool },
+    FocusMove {
+        shift: bool,
+    },
     WinSize(Mode),
     BeginScan,
+    /// The scanner is running and can be stopped with this token
+    ScanStarted(CancelToken),
+    CancelScan,
     ScanComplete,
-    PingResult(ScannedIp),
+    PingResults(Vec<ScannedIp>),
+    /// Why hosts in the running scan gave no answer
+    ProbesFailed(Vec<ProbeError>),
+    ScanProgress {
+        scanned: usize,
+        total: usize,
+    },
+    ExportImage,
+    ExportMetrics,
+    /// Path written to by an export, or why it failed
+    Exported(Result<String, String>),
     Testing,
     Config(ChangeConfig),
     Adaptor(NetworkAdapter),
     RefreshTheme,
+    /// A theme file was edited outside the app
+    ThemeFileChanged(ThemeChanged),
+    KeyPressed(KeyCombo),
+    Rebind(Action),
+    SelectHost(IpAddr),
+    /// Put text such as a host address on the clipboard
+    CopyToClipboard(String),
+    /// Probe one host again, replacing its result
+    RescanHost(IpAddr),
+    HostRescanned(Result<ScannedIp, String>),
+    /// Switch to the TCP client with a scanned host's open port filled in
+    OpenTcpTo {
+        ip: IpAddr,
+        port: u16,
+    },
+    /// Text typed into the results filter
+    FilterResults(String),
+    /// UI scale slider moved, see [`IpScannerApp::ui_scale_drag`]
+    UiScaleDrag(f32),
+    UiScaleReleased,
+    /// A column heading was clicked, sorting by it or flipping its direction
+    SortResults(SortKey),
+    ToggleAdvancedScan,
+    /// Text typed into the favorites field
+    FavoriteName(String),
+    /// Low-frequency tick that refreshes result ages in monitor mode
+    Tick(Instant),
+    /// Start the next monitor scan once the interval has passed
+    MonitorRescan,
+    /// Save the config if nothing has changed since this generation
+    SaveConfig(u64),
+    WindowResized {
+        width: f32,
+        height: f32,
+    },
+    WindowMoved {
+        x: f32,
+        y: f32,
+    },
+    // Theme editor
+    EditTheme(String),
+    ResumeThemeDraft,
+    ColorEdit {
+        color_type: ColorType,
+        hex_value: String,
+    },
+    HslEdit {
+        color_type: ColorType,
+        hsl: Hsl,
+    },
+    /// Step back through theme editor color changes
+    UndoThemeEdit,
+    RedoThemeEdit,
+    /// Put one color back to its value in the theme being edited
+    ResetColor(ColorType),
+    /// Opacity slider of a color, from 0.0 for clear to 1.0 for opaque
+    AlphaEdit {
+        color_type: ColorType,
+        alpha: f32,
+    },
+    /// A color slider was let go, so the draft can be saved
+    HslReleased,
+    GenerateFromAccent,
+    ThemeName(String),
+    ThemeFilePath(String),
+    ImportTheme,
+    ExportTheme(ThemeDefinition),
+    SaveTheme,
+    DiscardTheme,
     // Tcp Stuff
     // SendPacket,
     // ConnectionToggle,
@@ -103,19 +223,29 @@ pub enum Msg {
     // Udp Stuff
     SendPacket,
     ChangePacket(String),
+    PacketEncoding(PacketEncoding),
     ChangeIpPort(String),
     ChangeIpAddress(String),
     ConnectionToggle,
+    /// Something happened on the socket opened from a connection tab
+    Connection(ModeTab, ConnectionEvent),
 }
 impl Msg {
-    fn key_press(any_key: Key, mods: Modifiers) -> Option<Msg> {
-        let Key::Named(key) = any_key else {
-            return None;
-        };
-        match (key, mods) {
-            (Named::ArrowUp, Modifiers::SHIFT) => Some(Msg::WinSize(Mode::Fullscreen)),
-            (Named::ArrowDown, Modifiers::SHIFT) => Some(Msg::WinSize(Mode::Windowed)),
-            (Named::Tab, _) => Some(Msg::tab(mods.shift())),
+    /// Bindings live in the config, so presses are resolved in `update`
+    fn key_press(key: Key, mods: Modifiers) -> Option<Msg> {
+        KeyCombo::from_event(&key, mods).map(Msg::KeyPressed)
+    }
+    /// Window geometry changes worth remembering for the next launch
+    fn window_event(event: Event, _status: event::Status, _id: window::Id) -> Option<Msg> {
+        match event {
+            Event::Window(window::Event::Resized(size)) => Some(Msg::WindowResized {
+                width: size.width,
+                height: size.height,
+            }),
+            Event::Window(window::Event::Moved(point)) => Some(Msg::WindowMoved {
+                x: point.x,
+                y: point.y,
+            }),
             _ => None,
         }
     }
@@ -130,7 +260,7 @@ impl Msg {
 #[cfg(feature = "cosmic")]
 impl cosmic::Application for IpScannerApp {
     type Executor = cosmic::executor::Default;
-    type Flags = (AppConfig, Vec<NetworkAdapter>);
+    type Flags = (AppConfig, Vec<NetworkAdapter>, Option<ThemeDefinition>);
     type Message = Msg;
     const APP_ID: &'static str = "com.system76.NetMonkey";
 
@@ -142,9 +272,13 @@ impl cosmic::Application for IpScannerApp {
         &mut self.core
     }
 
-    fn init(core: Core, (config, adapters): Self::Flags) -> (Self, Task<Self::Message>) {
+    fn init(
+        core: Core,
+        (config, adapters, theme_override): Self::Flags,
+    ) -> (Self, Task<Self::Message>) {
         let mut app = Self {
             core,
+            theme_override,
             config,
             adapters,
             ..Default::default()
@@ -166,28 +300,56 @@ impl cosmic::Application for IpScannerApp {
     }
 }
 
+/// Switching between the system light and dark modes, or editing the
+/// active COSMIC theme, sends `RefreshTheme` so the app recolors straight away
+#[cfg(feature = "cosmic")]
+fn cosmic_theme_subscription(is_dark: bool) -> Subscription<Msg> {
+    use cosmic::cosmic_config::{CosmicConfigEntry, config_subscription};
+    use cosmic::cosmic_theme::{THEME_MODE_ID, ThemeMode};
+
+    let mode = config_subscription::<_, ThemeMode>(
+        std::any::TypeId::of::<ThemeMode>(),
+        THEME_MODE_ID.into(),
+        ThemeMode::VERSION,
+    )
+    .map(|_| Msg::RefreshTheme);
+    let theme = cosmic::theme::subscription(is_dark).map(|_| Msg::RefreshTheme);
+    Subscription::batch([mode, theme])
+}
+
 #[cfg(not(feature = "cosmic"))]
 impl IpScannerApp {
-    fn run_with(window: Settings) -> Result<(), iced::Error> {
+    fn run_with(
+        window: Settings,
+        default_font: iced::Font,
+        theme_override: Option<ThemeDefinition>,
+    ) -> Result<(), iced::Error> {
         iced::application("Net Monkey", Self::update, Self::view)
-            .window_size((500.0, 800.0))
             .font(ICON_FONT)
+            .default_font(default_font)
             .window(window)
             .subscription(Self::subscription)
             .theme(Self::theme)
-            .run_with(Self::initialize)
+            .scale_factor(Self::scale_factor)
+            .run_with(move || Self::initialize(theme_override))
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.config.ui_scale().into()
     }
 
     #[cfg(not(feature = "cosmic"))]
-    fn initialize() -> (Self, Task<Msg>) {
+    fn initialize(theme_override: Option<ThemeDefinition>) -> (Self, Task<Msg>) {
+        let mut app = Self::default();
+        app.theme_override = theme_override;
         (
-            Self::default(),
+            app,
             Task::perform(
                 async {
-                    (
+                    Box::new((
                         AppConfig::load().unwrap_or_default(),
                         get_network_adapters(),
-                    )
+                    ))
                 },
                 Msg::Loaded,
             ),
@@ -197,23 +359,88 @@ impl IpScannerApp {
 
 impl IpScannerApp {
     fn update_common(&mut self, msg: Msg) -> Task<Msg> {
+        #[cfg(feature = "cosmic")]
+        use cosmic::iced::clipboard;
         #[cfg(feature = "cosmic")]
         use cosmic::widget::{focus_next, focus_previous};
         #[cfg(feature = "cosmic")]
         use cosmic::window::{change_mode, get_latest};
         #[cfg(not(feature = "cosmic"))]
+        use iced::clipboard;
+        #[cfg(not(feature = "cosmic"))]
         use iced::widget::{focus_next, focus_previous};
         #[cfg(not(feature = "cosmic"))]
         use iced::window::{change_mode, get_latest};
 
         // All Msgs that return a Task
         let cmd = match &msg {
+            Msg::KeyPressed(combo) => return self.key_pressed(combo.clone()),
             Msg::WinSize(mode) => {
                 let mode = *mode; // Copy the mode value
                 get_latest().and_then(move |id| change_mode(id, mode))
             }
             Msg::FocusMove { shift: true } => focus_previous(),
             Msg::FocusMove { shift: false } => focus_next(),
+            Msg::TabChanged(tab) if *tab != self.tab => {
+                let focus = match tab {
+                    ModeTab::Settings => {
+                        net_monkey_components::dropdown::focus(views::settings::starting_ip_id())
+                    }
+                    _ => Task::none(),
+                };
+                // The tab is remembered for the next launch
+                Task::batch([focus, self.config_changed()])
+            }
+            // Switched like any other tab change, so it's remembered too
+            Msg::OpenTcpTo { .. } => self.update_common(Msg::TabChanged(ModeTab::TCPclient)),
+            Msg::Config(_) | Msg::Adaptor(_) | Msg::SaveTheme => self.config_changed(),
+            Msg::UiScaleReleased => match self.ui_scale_drag.take() {
+                Some(scale) => Task::done(Msg::Config(ChangeConfig::UiScale(scale))),
+                None => Task::none(),
+            },
+            Msg::WindowResized { width, height } => {
+                let scale = self.config.ui_scale();
+                match self.config.window.resized(*width, *height, scale) {
+                    true => self.config_changed(),
+                    false => Task::none(),
+                }
+            }
+            Msg::WindowMoved { x, y } => {
+                let scale = self.config.ui_scale();
+                match self.config.window.moved(*x, *y, scale) {
+                    true => self.config_changed(),
+                    false => Task::none(),
+                }
+            }
+            Msg::ThemeFileChanged(_) => match self.reload_theme() {
+                true => self.config_changed(),
+                false => Task::none(),
+            },
+            Msg::ConnectionToggle => self.toggle_connection(),
+            Msg::ExportImage => self.export_image(),
+            Msg::ExportMetrics => self.export_metrics(),
+            Msg::CopyToClipboard(contents) => clipboard::write(contents.clone()),
+            Msg::RescanHost(ip) => {
+                let (ip, ports, config) =
+                    (*ip, self.config.ports.clone(), self.config.scan.clone());
+                Task::perform(
+                    async move { scan_single_host(ip, &ports, config).await },
+                    Msg::HostRescanned,
+                )
+            }
+            // Let the scanner wind down, it reports back once stopped
+            Msg::CancelScan if self.scanning() => Task::done(Msg::ScanComplete),
+            // A cancelled scan also stops monitoring until the next manual scan
+            Msg::ScanComplete
+                if self.config.monitor
+                    && !self
+                        .scan_cancel
+                        .as_ref()
+                        .is_some_and(CancelToken::is_cancelled) =>
+            {
+                let interval = self.config.monitor_interval();
+                Task::perform(tokio::time::sleep(interval), |_| Msg::MonitorRescan)
+            }
             _ => Task::none(),
         };
 
@@ -225,6 +452,7 @@ impl IpScannerApp {
         // All Msgs that should print
         match &msg {
             Msg::BeginScan => println!("Starting scan..."),
+            Msg::CancelScan => println!("Scan cancelled"),
             Msg::ScanComplete => println!("Scan completed!"),
             Msg::Testing => println!("Test clicked"),
             Msg::Config(change) => println!("Updating config {change:?}"),
@@ -232,35 +460,326 @@ impl IpScannerApp {
         }
         // All Msgs that should update the state
         match msg {
-            Msg::Loaded((c, a)) => self.loaded(c, a),
-            Msg::PingResult(res) => {
-                self.scan_progress += 1;
-                self.ips.push(res);
+            Msg::Loaded(loaded) => {
+                let (c, a) = *loaded;
+                self.loaded(c, a)
             }
-            Msg::ConnectionToggle
-            | Msg::SendPacket
+            Msg::AdaptersChanged(adaptors) => self.adaptors = adaptors,
+            Msg::PingResults(results) => {
+                let now = Instant::now();
+                self.scan_stats.responded += results.iter().filter(|result| result.alive).count();
+                for result in results {
+                    self.last_updated.insert(result.ip, now);
+                    self.record_result(result);
+                }
+            }
+            Msg::Connection(tab, event) => {
+                if let Some(conn) = self.connection_mut(&tab) {
+                    conn.on_event(event);
+                }
+            }
+            Msg::SendPacket
             | Msg::ChangePacket(_)
+            | Msg::PacketEncoding(_)
             | Msg::ChangeIpAddress(_)
             | Msg::ChangeIpPort(_) => self.update_client_server(msg, self.tab.clone()),
-            Msg::TabChanged(tab) => self.tab = tab,
-            Msg::BeginScan => self.scan_progress = 0,
-            Msg::ScanComplete => self.scan_progress = 255,
-            Msg::Config(change) => self.config.update(change),
+            Msg::EditTheme(_)
+            | Msg::ResumeThemeDraft
+            | Msg::ColorEdit { .. }
+            | Msg::HslEdit { .. }
+            | Msg::AlphaEdit { .. }
+            | Msg::ResetColor(_)
+            | Msg::UndoThemeEdit
+            | Msg::RedoThemeEdit
+            | Msg::HslReleased
+            | Msg::GenerateFromAccent
+            | Msg::ThemeName(_)
+            | Msg::ThemeFilePath(_)
+            | Msg::ImportTheme
+            | Msg::ExportTheme(_)
+            | Msg::SaveTheme
+            | Msg::DiscardTheme => {
+                let saved = self.theme_editor.update(msg, &AppConfig::theme_manager());
+                // Editing ends a --theme or NO_COLOR override, so the editor
+                // shows the theme being edited
+                if saved.is_some() || self.theme_editor.draft.is_some() {
+                    self.theme_override = None;
+                }
+                if let Some(theme) = saved {
+                    self.config.custom_theme = Some(theme);
+                }
+            }
+            Msg::TabChanged(tab) => {
+                // Connections only live while their tab is open
+                if tab != self.tab
+                    && let Some(conn) = self.connection_mut(&self.tab.clone())
+                {
+                    conn.teardown();
+                }
+                self.config.last_tab = tab.clone();
+                self.tab = tab;
+            }
+            Msg::HostRescanned(Ok(mut result)) => {
+                // Keep the interface a multi-adapter scan found the host from
+                if let Some(previous) = self.ips.iter().find(|ip| ip.ip == result.ip) {
+                    result.interface = result.interface.or_else(|| previous.interface.clone());
+                }
+                self.last_updated.insert(result.ip, Instant::now());
+                self.record_result(result);
+                self.save_results();
+            }
+            Msg::HostRescanned(Err(e)) => eprintln!("Failed to rescan host: {e}"),
+            Msg::OpenTcpTo { ip, port } => {
+                self.tcp_client.ip_address = ip.to_string();
+                self.tcp_client.ip_port = port.to_string();
+            }
+            Msg::BeginScan if self.scan_range().is_none() => eprintln!(
+                "Can't scan: starting IP {} isn't a valid address for the IP family",
+                self.config.starting_ip
+            ),
+            Msg::BeginScan => {
+                self.scan = TaskState::default();
+                self.scan_stats = ScanStats::start(Instant::now());
+            }
+            Msg::ProbesFailed(errors) => self.scan_stats.fail(errors),
+            Msg::ScanProgress { scanned, total } => {
+                self.scan = TaskState::running(ScanMessage::progress_ratio(scanned, total));
+                self.scan_stats.progress(scanned, total);
+            }
+            Msg::ScanStarted(cancel) => {
+                // Scans resumed at launch start without a BeginScan
+                if self.scan_stats.started.is_none() {
+                    self.scan_stats = ScanStats::start(Instant::now());
+                }
+                self.scan_cancel = Some(cancel);
+            }
+            Msg::CancelScan => {
+                if let Some(cancel) = &self.scan_cancel {
+                    cancel.cancel();
+                }
+            }
+            Msg::ScanComplete => {
+                self.scan = TaskState::done();
+                let cancelled = self
+                    .scan_cancel
+                    .as_ref()
+                    .is_some_and(CancelToken::is_cancelled);
+                self.scan_stats.finish(Instant::now(), cancelled);
+                self.scan_cancel = None;
+                self.save_results();
+            }
+            // Monitoring may have been switched off, or a scan started by hand, while waiting
+            Msg::MonitorRescan if self.config.monitor && !self.scanning() => {
+                self.scan = TaskState::default();
+                self.scan_stats = ScanStats::start(Instant::now());
+            }
+            Msg::Tick(now) => self.now = Some(now),
+            Msg::FavoriteName(name) => self.favorite_name = name,
+            Msg::Config(ChangeConfig::RecallFavorite(name)) => {
+                self.favorite_name = name.clone();
+                self.config.update(ChangeConfig::RecallFavorite(name));
+            }
+            Msg::SaveConfig(generation) if generation == self.config_generation => {
+                self.save_config()
+            }
+            Msg::Config(change) => {
+                // Picking a theme in settings ends a --theme or NO_COLOR override
+                if matches!(
+                    change,
+                    ChangeConfig::ToggleTheme | ChangeConfig::FollowSystemTheme(_)
+                ) {
+                    self.theme_override = None;
+                }
+                self.config.update(change)
+            }
+            Msg::Rebind(action) => {
+                self.rebinding = Some(action);
+                self.rebind_error = None;
+            }
+            // Selecting the open host again collapses it
+            Msg::SelectHost(ip) if self.selected_host == Some(ip) => self.selected_host = None,
+            Msg::SelectHost(ip) => self.selected_host = Some(ip),
+            Msg::FilterResults(filter) => self.results_filter = filter,
+            Msg::UiScaleDrag(scale) => self.ui_scale_drag = Some(scale),
+            Msg::SortResults(key) => {
+                self.results_sort.toggle(key);
+                self.sort_results();
+            }
+            Msg::ToggleAdvancedScan => self.show_advanced_scan = !self.show_advanced_scan,
             Msg::Adaptor(a) => self.config.update(ChangeConfig::StartingIp(a.ip_address)),
-            Msg::RefreshTheme => {
-                println!("Theme refreshed");
+            Msg::RefreshTheme => self.refresh_theme(),
+            Msg::ThemeFileChanged(_) => {
+                self.theme_editor.themes = AppConfig::theme_manager().available_themes();
             }
+            Msg::Exported(Ok(path)) => println!("Exported scan results to {path}"),
+            Msg::Exported(Err(e)) => eprintln!("Failed to export scan results: {e}"),
             _ => {}
         }
     }
 
+    /// Resolve a key press against the configured bindings, or capture it
+    /// when an action is waiting to be rebound
+    fn key_pressed(&mut self, combo: KeyCombo) -> Task<Msg> {
+        if let Some(action) = self.rebinding.take() {
+            if combo == KeyCombo::new("Escape") {
+                return Task::none();
+            }
+            return match self.config.bind_key(action, combo) {
+                Ok(()) => self.config_changed(),
+                Err(e) => {
+                    self.rebind_error = Some((action, e));
+                    Task::none()
+                }
+            };
+        }
+        if combo.is_reserved() {
+            return self.update_common(Msg::tab(combo.shift));
+        }
+        match self.config.action_for(&combo) {
+            Some(action) => {
+                let msg = self.action_msg(action);
+                self.update_common(msg)
+            }
+            // Ctrl+1..Ctrl+4 jump straight to a tab unless bound to something else
+            None => match combo.tab_index().and_then(|n| TABS.get(n)) {
+                Some(tab) => self.update_common(Msg::TabChanged(tab.clone())),
+                None if self.tab == ModeTab::Settings && self.theme_editor.draft.is_some() => {
+                    match views::theme_edit::shortcut(&combo) {
+                        Some(msg) => self.update_common(msg),
+                        None => Task::none(),
+                    }
+                }
+                None => Task::none(),
+            },
+        }
+    }
+
+    /// Pick up on-disk edits to the active custom theme, returning whether it changed
+    fn reload_theme(&mut self) -> bool {
+        let Some(active) = &self.config.custom_theme else {
+            return false;
+        };
+        match AppConfig::theme_manager().load_theme(&active.name) {
+            Some(theme) if &theme != active => {
+                self.config.custom_theme = Some(theme);
+                true
+            }
+            // Removed or mid-write, keep showing the last good version
+            _ => false,
+        }
+    }
+
+    /// Schedule a save once the config has been left alone for [`CONFIG_SAVE_DELAY`]
+    ///
+    /// Each change supersedes the previously scheduled save, and nothing is
+    /// saved before the stored config has been loaded.
+    fn config_changed(&mut self) -> Task<Msg> {
+        if !self.loaded {
+            return Task::none();
+        }
+        self.config_generation += 1;
+        let generation = self.config_generation;
+        Task::perform(tokio::time::sleep(CONFIG_SAVE_DELAY), move |_| {
+            Msg::SaveConfig(generation)
+        })
+    }
+
+    fn action_msg(&self, action: Action) -> Msg {
+        match action {
+            Action::Fullscreen => Msg::WinSize(Mode::Fullscreen),
+            Action::Windowed => Msg::WinSize(Mode::Windowed),
+            Action::Scan => Msg::BeginScan,
+            Action::CancelScan => Msg::CancelScan,
+            Action::NextTab => {
+                let current = TABS.iter().position(|t| t == &self.tab).unwrap_or(0);
+                Msg::TabChanged(TABS[(current + 1) % TABS.len()].clone())
+            }
+            Action::ToggleTheme => Msg::Config(ChangeConfig::ToggleTheme),
+        }
+    }
+
+    /// Render the current results to a PNG in the data directory
+    fn export_image(&self) -> Task<Msg> {
+        let png = export::results_to_png(
+            &self.ips,
+            self.theme_provider().colors(),
+            self.config.ping_thresholds(),
+        )
+        .map_err(|e| e.to_string());
+        Self::write_export("scan_results.png", png)
+    }
+
+    /// Snapshot for node_exporter's textfile collector
+    fn export_metrics(&self) -> Task<Msg> {
+        let metrics = net_monkey_core::to_prometheus(&self.ips, std::time::SystemTime::now());
+        Self::write_export("net_monkey.prom", Ok(metrics.into_bytes()))
+    }
+
+    /// Write an export to the data directory off the UI thread
+    ///
+    /// The file is written beside the target and renamed over it, so readers
+    /// such as node_exporter never see it half written.
+    fn write_export(file_name: &str, contents: Result<Vec<u8>, String>) -> Task<Msg> {
+        let path = AppConfig::data_file_path(file_name);
+        Task::perform(
+            async move {
+                let contents = contents?;
+                if let Some(dir) = std::path::Path::new(&path).parent() {
+                    tokio::fs::create_dir_all(dir)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                }
+                let partial = format!("{path}.tmp");
+                tokio::fs::write(&partial, contents)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                tokio::fs::rename(&partial, &path)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(path)
+            },
+            Msg::Exported,
+        )
+    }
+
+    /// Open a socket for the current connection tab, or close the open one
+    fn toggle_connection(&mut self) -> Task<Msg> {
+        let tab = self.tab.clone();
+        let Some(conn) = self.connection_mut(&tab) else {
+            return Task::none();
+        };
+        if !conn.connections.is_empty() {
+            conn.teardown();
+            return Task::none();
+        }
+        let addr = match conn.socket_addr() {
+            Ok(addr) => addr,
+            Err(e) => {
+                conn.history.push(e);
+                return Task::none();
+            }
+        };
+        conn.connections.push(addr.ip());
+        let open = match tab {
+            ModeTab::TCPclient => create_tcp_client(addr).boxed(),
+            ModeTab::UDPclient => create_udp_client(addr).boxed(),
+            _ => return Task::none(),
+        };
+
+        let events = futures::stream::once(open).flat_map(|rx| {
+            futures::stream::unfold(rx, |mut rx| async move {
+                rx.recv().await.map(|event| (event, rx))
+            })
+        });
+        let (task, handle) =
+            Task::run(events, move |event| Msg::Connection(tab.clone(), event)).abortable();
+        conn.track(handle);
+        task
+    }
+
     fn update_client_server(&mut self, msg: Msg, tab: ModeTab) {
-        match tab {
-            ModeTab::TCPclient => self.tcp_client.update(msg),
-            ModeTab::UDPclient => self.udp_client.update(msg),
-            ModeTab::TCPserver => self.tcp_server.update(msg),
-            ModeTab::UDPserver => self.udp_server.update(msg),
-            _ => {}
+        if let Some(conn) = self.connection_mut(&tab) {
+            conn.update(msg);
         }
     }
 
@@ -271,7 +790,7 @@ impl IpScannerApp {
 
     #[cfg(not(feature = "cosmic"))]
     fn theme(&self) -> Theme {
-        self.config.theme_provider().to_iced_theme()
+        self.theme_provider().to_iced_theme()
     }
 
     #[cfg(not(feature = "cosmic"))]
@@ -280,12 +799,64 @@ impl IpScannerApp {
     }
 
     fn subscription_common(&self) -> Subscription<Msg> {
-        let scan_sub = match self.loaded && self.scan_progress < 255 {
-            true => views::ip_scan::subscription(),
+        let scan_sub = match (self.scan_range(), self.scanning()) {
+            (Some(range), true) if self.loaded => views::ip_scan::subscription(
+                self.config.results_refresh(),
+                self.scan_targets(),
+                self.config.scan.clone(),
+                range,
+                self.config.ports.clone(),
+            ),
+            _ => Subscription::none(),
+        };
+        #[cfg(feature = "cosmic")]
+        use cosmic::iced::time::every;
+        #[cfg(not(feature = "cosmic"))]
+        use iced::time::every;
+
+        // Ages and the scan clock only need second resolution, so redraw once a second
+        let age_sub = match (self.config.monitor && !self.ips.is_empty()) || self.scanning() {
+            true => every(Duration::from_secs(1)).map(Msg::Tick),
             false => Subscription::none(),
         };
         let kb_sub = keyboard::on_key_press(Msg::key_press);
-        Subscription::batch([scan_sub, kb_sub])
+        let window_sub = event::listen_with(Msg::window_event);
+        let theme_sub = match self.loaded {
+            true => AppConfig::theme_manager()
+                .watch()
+                .map(Msg::ThemeFileChanged),
+            false => Subscription::none(),
+        };
+        let adaptor_sub = match self.loaded {
+            true => Subscription::run_with_id("adaptors", adapter_changes(ADAPTER_POLL_INTERVAL))
+                .map(Msg::AdaptersChanged),
+            false => Subscription::none(),
+        };
+        // COSMIC reports its own theme changes, see `cosmic_theme_subscription`
+        let appearance_sub = match self.config.follow_system_theme
+            && self.config.custom_theme.is_none()
+            && !self.system_theme.is_cosmic_active()
+        {
+            true => net_monkey_theme::system::changes(SYSTEM_THEME_POLL).map(|_| Msg::RefreshTheme),
+            false => Subscription::none(),
+        };
+        #[cfg(feature = "cosmic")]
+        let appearance_sub = match self.system_theme.is_cosmic_active() {
+            true => Subscription::batch([
+                appearance_sub,
+                cosmic_theme_subscription(self.system_theme.is_dark()),
+            ]),
+            false => appearance_sub,
+        };
+        Subscription::batch([
+            scan_sub,
+            age_sub,
+            kb_sub,
+            window_sub,
+            theme_sub,
+            adaptor_sub,
+            appearance_sub,
+        ])
     }
 
     #[cfg(not(feature = "cosmic"))]
@@ -294,7 +865,7 @@ impl IpScannerApp {
     }
 
     fn view_common(&self) -> Element<'_, Msg> {
-        let colors = self.config.theme_provider().colors();
+        let colors = self.theme_provider().colors();
         let tabs = self.render_tabs();
         let col = match self.tab {
             ModeTab::IpScan => views::ip_scan::view(self).into(),
@@ -306,7 +877,7 @@ impl IpScannerApp {
         // Create themed content container
         let content = helpers::themed_container(
             column![tabs, col].height(Fill).spacing(20),
-            &self.config.theme_provider(),
+            &self.theme_provider(),
         );
 
         // Main background container with theme colors
@@ -322,61 +893,22 @@ impl IpScannerApp {
     }
 
     fn render_tabs(&self) -> Row<'_, Msg> {
-        let colors = self.config.theme_provider().colors();
+        let theme_provider = self.theme_provider();
+        let compact = self.config.compact_tabs();
         let buttons = TABS.iter().map(|tab| {
-            let active = &self.tab == tab;
-            let primary_color = colors.primary_color();
-            let background_color = colors.background_color();
-            let text_color = colors.text_color();
-
-            let button_style = move |_theme: &_, status: Status| match (active, status) {
-                (true, _) => button::Style {
-                    background: Some(iced::Background::Color(primary_color)),
-                    text_color: background_color,
-                    border: iced::Border {
-                        color: primary_color,
-                        width: 2.0,
-                        radius: 4.0.into(),
-                    },
-                    ..Default::default()
-                },
-                (false, Status::Hovered) => button::Style {
-                    background: Some(iced::Background::Color(Color::from_rgba(
-                        primary_color.r,
-                        primary_color.g,
-                        primary_color.b,
-                        0.2,
-                    ))),
-                    text_color,
-                    border: iced::Border {
-                        color: primary_color,
-                        width: 1.0,
-                        radius: 4.0.into(),
-                    },
-                    ..Default::default()
-                },
-                _ => button::Style {
-                    background: Some(iced::Background::Color(Color::from_rgba(
-                        text_color.r,
-                        text_color.g,
-                        text_color.b,
-                        0.1,
-                    ))),
-                    text_color,
-                    border: iced::Border {
-                        color: Color::from_rgba(text_color.r, text_color.g, text_color.b, 0.3),
-                        width: 1.0,
-                        radius: 4.0.into(),
-                    },
-                    ..Default::default()
-                },
+            let variant = match &self.tab == tab {
+                true => ButtonVariant::Primary,
+                false => ButtonVariant::Secondary,
             };
-            let label = text(String::from(tab))
-                .width(Fill)
-                .center()
-                .color(text_color);
+            // Label color comes from the button style so it follows hover/active states
+            let label = match compact {
+                true => text(tab.short_label()),
+                false => text(String::from(tab)),
+            }
+            .width(Fill)
+            .center();
             button(label)
-                .style(button_style)
+                .style(helpers::themed_button(variant, &theme_provider))
                 .on_press(Msg::TabChanged(tab.clone()))
                 .width(Fill)
                 .padding(8)
@@ -1,15 +1,28 @@
-use futures::StreamExt;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::{Stream, StreamExt};
 use iced::widget::Column;
-use iced::widget::{button, column, progress_bar, row, stack, text};
-use iced::{Element, Fill, Subscription};
+use iced::widget::{
+    button, column, container, progress_bar, row, scrollable, stack, text, text_input,
+};
+use iced::{Center, Color, Element, Fill, FillPortion, Font, Subscription};
 
-use crate::views::settings::IpScannerApp;
+use crate::views::settings::{IpScannerApp, PingThresholds, ResultsLayout, SortKey};
 use crate::{Msg, hero_image};
-use net_monkey_core::{ScanMessage, ScannedIp, create_network_scanner};
-use net_monkey_theme::helpers;
+use net_monkey_components::{ContextMenu, LabelWithHint};
+use net_monkey_core::{
+    HostState, NoopProcessor, ProbeError, ScanConfig, ScanMessage, ScanTarget, ScannedIp,
+    create_multi_adapter_scanner, create_network_scanner_with,
+};
+use net_monkey_theme::ThemeProvider;
+use net_monkey_theme::helpers::{self, ButtonVariant};
+use tokio::sync::mpsc::UnboundedReceiver;
 
 pub fn view(app: &IpScannerApp) -> Column<'_, Msg> {
-    let theme_colors = app.config.theme_provider().colors();
+    let theme_colors = app.theme_provider().colors();
     if app.ips.is_empty() {
         let mut scan_button = button(
             text("Scan Network")
@@ -18,7 +31,10 @@ pub fn view(app: &IpScannerApp) -> Column<'_, Msg> {
                 .size(20)
                 .color(theme_colors.text_color()),
         )
-        .style(button::primary)
+        .style(helpers::themed_button(
+            ButtonVariant::Primary,
+            &app.theme_provider(),
+        ))
         .width(Fill)
         .padding(12);
 
@@ -42,126 +58,1048 @@ pub fn view(app: &IpScannerApp) -> Column<'_, Msg> {
 
         let welcome_container = helpers::menu_container(
             column![stack!(hero_image(), scan_button), status_text].spacing(20),
-            &app.config.theme_provider(),
+            &app.theme_provider(),
         );
 
-        column![welcome_container]
+        column![welcome_container].push_maybe(failure_banner(app))
     } else {
-        let ping = app.ips.iter().map(|ip| ip.ping_elem(theme_colors));
-        let ips = app.ips.iter().map(|ip| ip.ips_elem(theme_colors));
-        let ports = app.ips.iter().map(|ip| ip.ports_elem(theme_colors));
+        // The header sits outside the scrollable so it stays pinned while results scroll
+        column![header(app)]
+            .push_maybe(failure_banner(app))
+            .push(filter_input(app))
+            .push_maybe(host_detail(app))
+            .push(results(app))
+            .spacing(20)
+            .height(Fill)
+    }
+}
 
-        let progress_container = helpers::sub_menu_container(
-            progress_bar(0.0..=255.0, app.scan_progress as f32),
-            &app.config.theme_provider(),
-        );
+/// Scan controls, progress and the finished scan's summary, kept fixed
+/// above the results list
+fn header(app: &IpScannerApp) -> Element<'_, Msg> {
+    let now = app.now.unwrap_or_else(Instant::now);
+    // Nothing to report until a scan has started
+    let status = app.scan_stats.started.map(|_| {
+        text(app.scan_stats.status(now))
+            .size(14)
+            .color(app.theme_provider().colors().text_color())
+    });
+    let summary = app.scan_stats.summary(&app.ips).map(|summary| {
+        text(summary)
+            .size(14)
+            .color(app.theme_provider().colors().text_color())
+    });
 
-        let results_container = helpers::menu_container(
+    helpers::sub_menu_container(
+        column![
             row![
-                helpers::sub_menu_container(
-                    column![
-                        text("Ping (ms)").size(16),
-                        Column::with_children(ping).spacing(5)
-                    ]
-                    .spacing(10),
-                    &app.config.theme_provider(),
-                ),
-                helpers::sub_menu_container(
-                    column![
-                        text("IP Address").size(16),
-                        Column::with_children(ips).spacing(5)
-                    ]
-                    .spacing(10),
-                    &app.config.theme_provider(),
-                ),
-                helpers::sub_menu_container(
-                    column![
-                        text("Open Ports").size(16),
-                        Column::with_children(ports).spacing(5)
-                    ]
-                    .spacing(10),
-                    &app.config.theme_provider(),
-                ),
+                progress_bar(0.0..=1.0, app.scan.progress),
+                button(text("Cancel"))
+                    .style(helpers::themed_button(
+                        ButtonVariant::Danger,
+                        &app.theme_provider()
+                    ))
+                    .on_press_maybe(app.scanning().then_some(Msg::CancelScan)),
+                button(text("Export PNG"))
+                    .style(helpers::themed_button(
+                        ButtonVariant::Secondary,
+                        &app.theme_provider()
+                    ))
+                    .on_press(Msg::ExportImage),
+                button(text("Export Metrics"))
+                    .style(helpers::themed_button(
+                        ButtonVariant::Secondary,
+                        &app.theme_provider()
+                    ))
+                    .on_press(Msg::ExportMetrics),
+            ]
+            .align_y(Center)
+            .spacing(10),
+        ]
+        .push_maybe(status)
+        .push_maybe(summary)
+        .spacing(5),
+        &app.theme_provider(),
+    )
+    .into()
+}
+
+/// Why the last scan found nothing, when its probes failed for a reason
+/// worth telling the user about
+fn failure_banner(app: &IpScannerApp) -> Option<Element<'_, Msg>> {
+    let summary = app.scan_stats.failure_summary()?;
+    let banner = text(summary)
+        .size(14)
+        .color(app.theme_provider().colors().warning_color());
+    Some(helpers::sub_menu_container(banner, &app.theme_provider()).into())
+}
+
+/// Timing and host counts for the running or last scan
+#[derive(Debug, Clone, Default)]
+pub struct ScanStats {
+    pub started: Option<Instant>,
+    pub finished: Option<Instant>,
+    /// Hosts that replied, from the results received so far
+    pub responded: usize,
+    pub scanned: usize,
+    pub total: usize,
+    /// How many probes failed for each reason
+    pub failures: BTreeMap<ProbeError, usize>,
+}
+
+impl ScanStats {
+    /// Hosts scanned before a running scan's failures are explained
+    pub const FAILURE_SAMPLE: usize = 16;
+
+    pub fn start(now: Instant) -> Self {
+        Self {
+            started: Some(now),
+            ..Self::default()
+        }
+    }
+
+    pub fn progress(&mut self, scanned: usize, total: usize) {
+        self.scanned = scanned;
+        self.total = total;
+    }
+
+    /// Count failed probes by reason, with every [`ProbeError::Other`]
+    /// counted under the first one's message
+    pub fn fail(&mut self, errors: impl IntoIterator<Item = ProbeError>) {
+        for error in errors {
+            let error = match error {
+                ProbeError::Other(_) => self
+                    .failures
+                    .keys()
+                    .find(|seen| matches!(seen, ProbeError::Other(_)))
+                    .cloned()
+                    .unwrap_or(error),
+                error => error,
+            };
+            *self.failures.entry(error).or_default() += 1;
+        }
+    }
+
+    /// Explanation of the most common failure, once probes have failed
+    /// without a single host responding
+    ///
+    /// Waits for the scan to finish or [`Self::FAILURE_SAMPLE`] hosts to be
+    /// scanned, so the first few timeouts of a big range don't raise it.
+    pub fn failure_summary(&self) -> Option<String> {
+        let sampled = self.finished.is_some() || self.scanned >= Self::FAILURE_SAMPLE;
+        if self.responded > 0 || !sampled {
+            return None;
+        }
+        let (error, &count) = self
+            .failures
+            .iter()
+            .max_by_key(|&(error, count)| (count, error))?;
+        let hint = match error {
+            ProbeError::PermissionDenied => {
+                " Pinging needs admin rights, run as administrator or probe with TCP connects in Settings."
+            }
+            ProbeError::Unreachable => {
+                " Check the adapter is connected and the starting IP is on its network."
+            }
+            ProbeError::Timeout => {
+                " Hosts may be off or blocking ping, try probing with TCP connects in Settings."
+            }
+            ProbeError::Other(_) => "",
+        };
+        let probes = match count {
+            1 => "probe",
+            _ => "probes",
+        };
+        Some(format!(
+            "No hosts answered, {count} {probes} failed: {error}.{hint}"
+        ))
+    }
+
+    /// Stop the clock, counting every address as scanned unless the scan
+    /// was cancelled part way
+    pub fn finish(&mut self, now: Instant, cancelled: bool) {
+        if self.finished.is_some() {
+            return;
+        }
+        self.finished = Some(now);
+        if !cancelled {
+            self.scanned = self.total.max(self.scanned);
+        }
+    }
+
+    /// Time since the scan started, frozen once it has finished
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        match self.started {
+            Some(started) => self
+                .finished
+                .unwrap_or(now)
+                .saturating_duration_since(started),
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Time left assuming the rest of the range goes as fast as the start
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        if self.finished.is_some() || self.scanned == 0 || self.total == 0 {
+            return None;
+        }
+        let left = self.total.saturating_sub(self.scanned) as f64 / self.scanned as f64;
+        Some(self.elapsed(now).mul_f64(left))
+    }
+
+    /// One line such as "0:42 elapsed · 12 of 120 hosts responded · ~3:08 left"
+    pub fn status(&self, now: Instant) -> String {
+        let mut status = format!(
+            "{} elapsed · {} of {} hosts responded",
+            format_clock(self.elapsed(now)),
+            self.responded,
+            self.scanned
+        );
+        if let Some(remaining) = self.remaining(now) {
+            status += &format!(" · ~{} left", format_clock(remaining));
+        }
+        status
+    }
+
+    /// Totals for a finished scan, such as
+    /// "12 hosts alive · 7 unique open ports · ping 1–48ms · took 0:42"
+    pub fn summary(&self, ips: &[ScannedIp]) -> Option<String> {
+        let finished = self.finished?;
+        let alive: Vec<&ScannedIp> = ips.iter().filter(|ip| ip.alive).collect();
+        let ports: BTreeSet<u16> = alive
+            .iter()
+            .flat_map(|ip| ip.ports.iter().copied())
+            .collect();
+        let hosts = match alive.len() {
+            1 => "host",
+            _ => "hosts",
+        };
+        let mut summary = format!(
+            "{} {hosts} alive · {} unique open ports",
+            alive.len(),
+            ports.len()
+        );
+        // Filtered hosts were found without a ping to time
+        let pinged = || alive.iter().filter(|ip| ip.state == HostState::Up);
+        let fastest = pinged().map(|ip| ip.ping).min();
+        let slowest = pinged().map(|ip| ip.ping).max();
+        if let (Some(fastest), Some(slowest)) = (fastest, slowest) {
+            summary += &format!(" · ping {fastest}–{slowest}ms");
+        }
+        summary += &format!(" · took {}", format_clock(self.elapsed(finished)));
+        Some(summary)
+    }
+}
+
+/// Minutes and seconds, or hours once a scan runs that long
+fn format_clock(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..3600 => format!("{}:{:02}", secs / 60, secs % 60),
+        _ => format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60),
+    }
+}
+
+/// Narrows the results to matching hosts, showing how many are left
+fn filter_input(app: &IpScannerApp) -> Element<'_, Msg> {
+    let shown = app
+        .ips
+        .iter()
+        .filter(|ip| matches_filter(ip, &app.results_filter))
+        .count();
+
+    row![
+        text_input("Filter by IP, interface or open port", &app.results_filter)
+            .on_input(Msg::FilterResults)
+            .size(16)
+            .padding(8)
+            .width(Fill),
+        text(format!("{shown} of {}", app.ips.len()))
+            .size(16)
+            .color(app.theme_provider().colors().text_color()),
+    ]
+    .align_y(Center)
+    .spacing(10)
+    .into()
+}
+
+/// Whether `ip` matches the results filter
+///
+/// The filter matches part of the address or interface name, or an open
+/// port exactly. An empty filter matches every host.
+fn matches_filter(ip: &ScannedIp, filter: &str) -> bool {
+    let filter = filter.trim().to_lowercase();
+    filter.is_empty()
+        || ip.ip.to_string().contains(&filter)
+        || ip
+            .interface
+            .as_ref()
+            .is_some_and(|interface| interface.to_lowercase().contains(&filter))
+        || ip.ports.iter().any(|port| port.to_string() == filter)
+}
+
+/// Latency breakdown for the host selected in the results list
+fn host_detail(app: &IpScannerApp) -> Option<Element<'_, Msg>> {
+    let selected = app.selected_host?;
+    let host = app.ips.iter().find(|ip| ip.ip == selected)?;
+
+    let summary = match host.latency_stats() {
+        Some(stats) => format!(
+            "min {} · avg {} · max {} · p50 {} · p95 {} · p99 {}",
+            format_ms(stats.min),
+            format_ms(stats.mean),
+            format_ms(stats.max),
+            format_ms(stats.p50),
+            format_ms(stats.p95),
+            format_ms(stats.p99)
+        ),
+        None => String::from("No ping samples"),
+    };
+    let samples = host
+        .samples
+        .iter()
+        .map(|sample| format_ms(*sample))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Some(
+        helpers::sub_menu_container(
+            column![
+                row![
+                    text(host.ip.to_string()).size(18).width(Fill),
+                    button(text("Rescan"))
+                        .style(helpers::themed_button(
+                            ButtonVariant::Secondary,
+                            &app.theme_provider()
+                        ))
+                        .on_press(Msg::RescanHost(selected)),
+                    button(text("Close"))
+                        .style(helpers::themed_button(
+                            ButtonVariant::Text,
+                            &app.theme_provider()
+                        ))
+                        .on_press(Msg::SelectHost(selected)),
+                ]
+                .align_y(Center)
+                .spacing(10),
+                text(summary).size(16),
+                text(format!("Samples: {samples}")).size(14),
             ]
-            .spacing(15),
-            &app.config.theme_provider(),
+            .spacing(5),
+            &app.theme_provider(),
+        )
+        .into(),
+    )
+}
+
+/// Milliseconds with microsecond precision
+fn format_ms(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Results list limited to hosts matching the filter, where only the rows
+/// scroll and the sortable headings stay above them
+fn results(app: &IpScannerApp) -> Element<'_, Msg> {
+    let shown: Vec<&ScannedIp> = app
+        .ips
+        .iter()
+        .filter(|ip| matches_filter(ip, &app.results_filter))
+        .collect();
+    let (headings, rows) = match app.config.results_layout {
+        ResultsLayout::Columns => result_columns(app, &shown),
+        ResultsLayout::Table => result_table(app, &shown),
+    };
+
+    helpers::menu_container(
+        column![headings, scrollable(rows).height(Fill)].spacing(10),
+        &app.theme_provider(),
+    )
+    .height(Fill)
+    .into()
+}
+
+/// Width share of the ping, IP, ports, OS, MAC and monitor age columns, so
+/// the pinned headings line up with the rows scrolling under them
+const COLUMN_PORTIONS: [u16; 6] = [1, 3, 3, 1, 3, 1];
+
+/// A column per field, each in its own container, returned as the headings
+/// and the rows under them
+fn result_columns<'a>(
+    app: &'a IpScannerApp,
+    shown: &[&'a ScannedIp],
+) -> (Element<'a, Msg>, Element<'a, Msg>) {
+    let theme_colors = app.theme_provider().colors();
+    let boxed = |content: Element<'a, Msg>, portion: u16| -> Element<'a, Msg> {
+        helpers::sub_menu_container(content, &app.theme_provider())
+            .width(FillPortion(portion))
+            .into()
+    };
+    // Every column is built from the same hosts so their rows stay aligned
+    // Right-clicking any cell of a host's row opens its actions
+    let ping = shown.iter().map(|ip| {
+        host_menu(
+            ip,
+            ip.ping_elem(
+                theme_colors,
+                app.config.raw_ping,
+                app.config.ping_thresholds(),
+            ),
+        )
+    });
+    let ips = shown
+        .iter()
+        .map(|ip| host_menu(ip, ip.ips_elem(theme_colors)));
+    let ports = shown
+        .iter()
+        .map(|ip| host_menu(ip, ip.ports_elem(theme_colors)));
+    let os = shown
+        .iter()
+        .map(|ip| host_menu(ip, ip.os_elem(theme_colors)));
+    let macs = shown
+        .iter()
+        .map(|ip| host_menu(ip, ip.mac_elem(theme_colors)));
+
+    let [ping_w, ip_w, ports_w, os_w, mac_w, age_w] = COLUMN_PORTIONS;
+    let mut headings = row![
+        boxed(sort_heading(app, "Ping (ms)", SortKey::Ping), ping_w),
+        boxed(sort_heading(app, "IP Address", SortKey::Ip), ip_w),
+        boxed(sort_heading(app, "Open Ports", SortKey::Ports), ports_w),
+        boxed(os_heading(app), os_w),
+        boxed(mac_heading(app), mac_w),
+    ]
+    .spacing(15);
+    let mut columns = row![
+        boxed(Column::with_children(ping).spacing(5).into(), ping_w),
+        boxed(Column::with_children(ips).spacing(5).into(), ip_w),
+        boxed(Column::with_children(ports).spacing(5).into(), ports_w),
+        boxed(Column::with_children(os).spacing(5).into(), os_w),
+        boxed(Column::with_children(macs).spacing(5).into(), mac_w),
+    ]
+    .spacing(15);
+    if app.config.monitor {
+        headings = headings.push(boxed(text("Updated").size(16).into(), age_w));
+        columns = columns.push(boxed(age_column(app, shown), age_w));
+    }
+    (headings.into(), columns.into())
+}
+
+/// One row per host, so a cell that wraps pushes the rest of its row down
+/// with it, returned as the headings and the rows under them
+fn result_table<'a>(
+    app: &'a IpScannerApp,
+    shown: &[&'a ScannedIp],
+) -> (Element<'a, Msg>, Element<'a, Msg>) {
+    let theme_colors = app.theme_provider().colors();
+    let cell = |content: Element<'a, Msg>, portion: u16| -> Element<'a, Msg> {
+        container(content)
+            .width(FillPortion(portion))
+            .align_y(Center)
+            .into()
+    };
+    let heading = |label: &'static str| text(label).size(16).width(Fill).center().into();
+
+    let [ping_w, ip_w, ports_w, os_w, mac_w, age_w] = COLUMN_PORTIONS;
+    let mut headings = row![
+        cell(sort_heading(app, "Ping (ms)", SortKey::Ping), ping_w),
+        cell(sort_heading(app, "IP Address", SortKey::Ip), ip_w),
+        cell(sort_heading(app, "Open Ports", SortKey::Ports), ports_w),
+        cell(os_heading(app), os_w),
+        cell(mac_heading(app), mac_w),
+    ]
+    .spacing(15);
+    if app.config.monitor {
+        headings = headings.push(cell(heading("Updated"), age_w));
+    }
+
+    let rows = shown.iter().map(|ip| {
+        let ping = ip.ping_elem(
+            theme_colors,
+            app.config.raw_ping,
+            app.config.ping_thresholds(),
+        );
+        let mut cells = row![
+            cell(ping, ping_w),
+            cell(ip.ips_elem(theme_colors), ip_w),
+            cell(ip.ports_elem(theme_colors), ports_w),
+            cell(ip.os_elem(theme_colors), os_w),
+            cell(ip.mac_elem(theme_colors), mac_w),
+        ]
+        .spacing(15)
+        .align_y(Center);
+        if app.config.monitor {
+            cells = cells.push(cell(age_cell(app, ip), age_w));
+        }
+        host_menu(ip, cells.into())
+    });
+
+    (
+        headings.into(),
+        Column::with_children(rows).spacing(5).into(),
+    )
+}
+
+/// Column heading that sorts the results by `key` when clicked
+fn sort_heading<'a>(app: &IpScannerApp, label: &str, key: SortKey) -> Element<'a, Msg> {
+    button(text(format!("{label}{}", app.results_sort.arrow(key))).size(16))
+        .padding(0)
+        .style(helpers::themed_button(
+            ButtonVariant::Text,
+            &app.theme_provider(),
+        ))
+        .on_press(Msg::SortResults(key))
+        .into()
+}
+
+fn os_heading(app: &IpScannerApp) -> Element<'_, Msg> {
+    LabelWithHint::new(
+        "OS (guess)",
+        "Guessed from the reply TTL. Hosts can change their TTL and routers lower it, so treat this as a hint only",
+    )
+    .hint_icon('!')
+    .hint_icon_color(app.theme_provider().colors().warning_color())
+    .text_size(16.0)
+    .theme(app.theme_provider())
+    .into_element()
+}
+
+fn mac_heading(app: &IpScannerApp) -> Element<'_, Msg> {
+    LabelWithHint::new(
+        "MAC Address",
+        "Read from the ARP table, so only hosts on the local segment have one. The vendor comes from a small built-in list",
+    )
+    .text_size(16.0)
+    .theme(app.theme_provider())
+    .into_element()
+}
+
+/// Wrap one cell of a host's row with the right-click actions for that host
+fn host_menu<'a>(host: &ScannedIp, cell: Element<'a, Msg>) -> Element<'a, Msg> {
+    let mut menu = ContextMenu::new(cell)
+        .item("Copy IP", Msg::CopyToClipboard(host.ip.to_string()))
+        .item("Copy as CSV row", Msg::CopyToClipboard(csv_row(host)));
+    if let Some(&port) = host.ports.first() {
+        menu = menu.item(
+            format!("Open TCP to port {port}"),
+            Msg::OpenTcpTo { ip: host.ip, port },
         );
+    }
+    menu.item("Rescan this host", Msg::RescanHost(host.ip))
+        .into()
+}
+
+/// The host as "ip,ping_ms,state,ports,mac,vendor,os", with ports separated
+/// by spaces and unknown fields left empty
+pub fn csv_row(host: &ScannedIp) -> String {
+    let ports = host
+        .ports
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    [
+        host.ip.to_string(),
+        host.ping.to_string(),
+        host.state.label().to_string(),
+        ports,
+        host.mac.clone().unwrap_or_default(),
+        host.vendor().unwrap_or_default().to_string(),
+        host.os_hint
+            .map(|guess| guess.label().to_string())
+            .unwrap_or_default(),
+    ]
+    .join(",")
+}
+
+/// How long ago each host was last refreshed, fading to the warning color as it goes stale
+fn age_column<'a>(app: &'a IpScannerApp, shown: &[&ScannedIp]) -> Element<'a, Msg> {
+    Column::with_children(shown.iter().map(|ip| age_cell(app, ip)))
+        .spacing(5)
+        .into()
+}
+
+fn age_cell<'a>(app: &IpScannerApp, ip: &ScannedIp) -> Element<'a, Msg> {
+    let theme_colors = app.theme_provider().colors();
+    let now = app.now.unwrap_or_else(Instant::now);
+    let (label, color) = match app.last_updated.get(&ip.ip) {
+        Some(updated) => {
+            let age = now.saturating_duration_since(*updated);
+            let color = mix(
+                theme_colors.border_color(),
+                theme_colors.warning_color(),
+                staleness(age, app.config.monitor_interval()),
+            );
+            (format_age(age), color)
+        }
+        None => (String::from("-"), theme_colors.border_color()),
+    };
+    text(label)
+        .width(Fill)
+        .center()
+        .style(move |_theme| iced::widget::text::Style { color: Some(color) })
+        .into()
+}
 
-        column![progress_container, results_container].spacing(20)
+/// Compact relative time such as "2s ago" or "3m ago"
+fn format_age(age: Duration) -> String {
+    match age.as_secs() {
+        secs @ 0..60 => format!("{secs}s ago"),
+        secs @ 60..3600 => format!("{}m ago", secs / 60),
+        secs => format!("{}h ago", secs / 3600),
     }
 }
 
-pub fn subscription() -> Subscription<Msg> {
+/// 0.0 while a result is within one monitor interval, rising to 1.0 once a
+/// whole further interval has passed without an update
+fn staleness(age: Duration, interval: Duration) -> f32 {
+    let interval = interval.as_secs_f32().max(1.0);
+    ((age.as_secs_f32() - interval) / interval).clamp(0.0, 1.0)
+}
+
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    Color::from_rgba(
+        lerp(from.r, to.r),
+        lerp(from.g, to.g),
+        lerp(from.b, to.b),
+        lerp(from.a, to.a),
+    )
+}
+
+/// Run a scan, delivering results to the app at most once per `refresh`
+///
+/// When `targets` is non-empty each adapter's subnet is swept from that
+/// adapter in parallel, otherwise the `base`/`prefix` range is scanned.
+pub fn subscription(
+    refresh: Duration,
+    targets: Vec<ScanTarget>,
+    config: ScanConfig,
+    (base, prefix): (IpAddr, u8),
+    ports: Vec<u16>,
+) -> Subscription<Msg> {
     iced::Subscription::run_with_id(
         std::any::TypeId::of::<()>(),
-        futures::stream::once(async {
-            let rx = create_network_scanner().await;
-
-            // Create a stream from the receiver
-            futures::stream::unfold(rx, |mut rx| async move {
-                rx.recv().await.map(|scan_msg| {
-                    let msg = match scan_msg {
-                        ScanMessage::Result(scanned_ip) => Msg::PingResult(scanned_ip),
-                        ScanMessage::Complete => Msg::ScanComplete,
-                    };
-                    (msg, rx)
-                })
-            })
+        futures::stream::once(async move {
+            let (rx, cancel) = match targets.is_empty() {
+                true => {
+                    create_network_scanner_with(
+                        base,
+                        prefix,
+                        ports,
+                        config,
+                        Arc::new(NoopProcessor),
+                    )
+                    .await
+                }
+                false => {
+                    create_multi_adapter_scanner(targets, ports, config, Arc::new(NoopProcessor))
+                        .await
+                }
+            };
+
+            // Hand the app its cancel token before any results, then coalesce
+            // results so a fast scan doesn't rebuild the view per host
+            futures::stream::once(async { Msg::ScanStarted(cancel) })
+                .chain(batch_results(rx, refresh))
         })
         .flatten(),
     )
 }
 
+/// Buffer scan results and emit them as one `Msg::PingResults` per `interval`.
+///
+/// The scanner keeps sending at full speed; only the messages reaching the app
+/// are throttled. Each batch is followed by the latest `Msg::ScanProgress`, and
+/// a batch is flushed early when the scan completes.
+fn batch_results(
+    rx: UnboundedReceiver<ScanMessage>,
+    interval: Duration,
+) -> impl Stream<Item = Msg> {
+    // State is the receiver (dropped once the scan has finished) and messages
+    // waiting to be sent from the last flush
+    futures::stream::unfold(
+        (Some(rx), VecDeque::new()),
+        move |(rx, mut pending)| async move {
+            if let Some(msg) = pending.pop_front() {
+                return Some((msg, (rx, pending)));
+            }
+            let mut rx = rx?;
+
+            let mut batch = Vec::new();
+            let mut failures = Vec::new();
+            let mut progress = None;
+            let mut deadline = None;
+            let mut closed = false;
+            let complete = loop {
+                let next = match deadline {
+                    None => rx.recv().await,
+                    Some(deadline) => match tokio::time::timeout_at(deadline, rx.recv()).await {
+                        Ok(next) => next,
+                        Err(_) => break false,
+                    },
+                };
+                match next {
+                    Some(ScanMessage::Result(scanned_ip)) => {
+                        deadline.get_or_insert_with(|| tokio::time::Instant::now() + interval);
+                        batch.push(scanned_ip);
+                    }
+                    Some(ScanMessage::Error { error, .. }) => {
+                        deadline.get_or_insert_with(|| tokio::time::Instant::now() + interval);
+                        failures.push(error);
+                    }
+                    // A finished range is reported by the `Complete` that follows,
+                    // so the app never sees full progress before the last results
+                    Some(ScanMessage::Progress { scanned, total }) if scanned < total => {
+                        deadline.get_or_insert_with(|| tokio::time::Instant::now() + interval);
+                        progress = Some((scanned, total));
+                    }
+                    Some(ScanMessage::Progress { .. }) => {}
+                    Some(ScanMessage::Complete) => break true,
+                    None => {
+                        closed = true;
+                        break false;
+                    }
+                }
+            };
+
+            if !batch.is_empty() {
+                pending.push_back(Msg::PingResults(batch));
+            }
+            if !failures.is_empty() {
+                pending.push_back(Msg::ProbesFailed(failures));
+            }
+            if let Some((scanned, total)) = progress.filter(|_| !complete) {
+                pending.push_back(Msg::ScanProgress { scanned, total });
+            }
+            if complete {
+                pending.push_back(Msg::ScanComplete);
+            }
+            // The stream ends once there's nothing left to send from a finished scan
+            let rx = (!complete && !closed).then_some(rx);
+            let msg = pending.pop_front()?;
+            Some((msg, (rx, pending)))
+        },
+    )
+}
+
 /// Extension trait for ScannedIp to provide UI element methods
 pub trait ScannedIpExt {
-    fn ping_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg>;
+    fn ping_elem(
+        &self,
+        theme_colors: net_monkey_theme::SimpleColors,
+        raw: bool,
+        thresholds: PingThresholds,
+    ) -> Element<'_, Msg>;
     fn ips_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg>;
     fn ports_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg>;
+    fn os_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg>;
+    fn mac_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg>;
 }
 
 impl ScannedIpExt for ScannedIp {
-    fn ping_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg> {
-        // Color-code ping times: green for fast, yellow for medium, red for slow
-        let ping_text = text(self.ping.to_string() + "ms").width(Fill).center();
+    fn ping_elem(
+        &self,
+        theme_colors: net_monkey_theme::SimpleColors,
+        raw: bool,
+        thresholds: PingThresholds,
+    ) -> Element<'_, Msg> {
+        let label = match self.latency_stats() {
+            Some(stats) if raw => format_ms(stats.p50),
+            _ => self.ping.to_string() + "ms",
+        };
+        // Color-code ping times along a gradient from green for fast to red for slow
+        let color = thresholds.color(self.ping, theme_colors);
+        text(label)
+            .font(Font::MONOSPACE)
+            .width(Fill)
+            .center()
+            .style(move |_theme| iced::widget::text::Style { color: Some(color) })
+            .into()
+    }
 
-        if self.ping < 50 {
-            ping_text.style(move |_theme| iced::widget::text::Style {
-                color: Some(theme_colors.success_color()),
-            })
-        } else if self.ping < 150 {
-            ping_text.style(move |_theme| iced::widget::text::Style {
-                color: Some(theme_colors.warning_color()),
-            })
-        } else {
-            ping_text.style(move |_theme| iced::widget::text::Style {
-                color: Some(theme_colors.danger_color()),
-            })
-        }
+    fn ips_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg> {
+        let ip = match &self.interface {
+            Some(interface) => format!("{} [{interface}]", self.ip),
+            None => self.ip.to_string(),
+        };
+        // Label hosts that aren't plainly up so filtered hosts stand out
+        let (label, color) = match self.state {
+            HostState::Up => (ip, theme_colors.text_color()),
+            HostState::Filtered => (
+                format!("{ip} ({})", self.state.label()),
+                theme_colors.warning_color(),
+            ),
+            HostState::Down => (
+                format!("{ip} ({})", self.state.label()),
+                theme_colors.danger_color(),
+            ),
+        };
+
+        let theme = ThemeProvider::fallback(theme_colors);
+        row![
+            // Clicking a host opens its latency detail
+            button(
+                text(label)
+                    .font(Font::MONOSPACE)
+                    .width(Fill)
+                    .center()
+                    .style(move |_theme| iced::widget::text::Style { color: Some(color) }),
+            )
+            .padding(0)
+            .style(helpers::themed_button(ButtonVariant::Text, &theme))
+            .on_press(Msg::SelectHost(self.ip)),
+            button(text("Copy").size(12))
+                .padding([0, 4])
+                .style(helpers::themed_button(ButtonVariant::Secondary, &theme))
+                .on_press(Msg::CopyToClipboard(self.ip.to_string())),
+        ]
+        .align_y(Center)
+        .spacing(5)
         .into()
     }
 
-    fn ips_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg> {
-        text(self.ip.to_string())
+    fn ports_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg> {
+        if self.ports.is_empty() {
+            return text(self.ports_to_string())
+                .width(Fill)
+                .center()
+                .style(move |_theme| iced::widget::text::Style {
+                    color: Some(theme_colors.danger_color()),
+                })
+                .into();
+        }
+
+        // Each open port opens the TCP client pointed at it
+        let theme = ThemeProvider::fallback(theme_colors);
+        let count = text(format!("{} open", self.ports.len()))
+            .size(12)
+            .color(theme_colors.border_color());
+        let ports = self.ports.iter().map(|&port| {
+            button(text(port.to_string()).size(12).font(Font::MONOSPACE))
+                .padding([0, 4])
+                .style(helpers::themed_button(ButtonVariant::Secondary, &theme))
+                .on_press(Msg::OpenTcpTo { ip: self.ip, port })
+                .into()
+        });
+
+        row![count]
+            .extend(ports)
+            .align_y(Center)
+            .spacing(5)
+            .wrap()
+            .into()
+    }
+
+    fn os_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg> {
+        // Prefixed with "~" so it doesn't read as a confirmed identification
+        let label = match self.os_hint {
+            Some(guess) => format!("~{}", guess.label()),
+            None => String::from("?"),
+        };
+
+        text(label)
             .width(Fill)
             .center()
             .style(move |_theme| iced::widget::text::Style {
-                color: Some(theme_colors.text.into()),
+                color: Some(theme_colors.border_color()),
             })
             .into()
     }
 
-    fn ports_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg> {
-        let ports_text = text(self.ports_to_string()).width(Fill).center();
+    fn mac_elem(&self, theme_colors: net_monkey_theme::SimpleColors) -> Element<'_, Msg> {
+        let label = match (&self.mac, self.vendor()) {
+            (Some(mac), Some(vendor)) => format!("{mac} · {vendor}"),
+            (Some(mac), None) => mac.clone(),
+            (None, _) => String::from("-"),
+        };
 
-        if self.ports.is_empty() {
-            ports_text.style(move |_theme| iced::widget::text::Style {
-                color: Some(theme_colors.danger_color()),
-            })
-        } else {
-            ports_text.style(move |_theme| iced::widget::text::Style {
+        text(label)
+            .font(Font::MONOSPACE)
+            .width(Fill)
+            .center()
+            .style(move |_theme| iced::widget::text::Style {
                 color: Some(theme_colors.text_color()),
             })
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[tokio::test]
+    async fn test_batch_results_collapses_burst() {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        for n in 0..100 {
+            let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, n));
+            tx.send(ScanMessage::Result(ScannedIp::new(ip, true, 1, Vec::new())))
+                .unwrap();
         }
-        .into()
+        tx.send(ScanMessage::Complete).unwrap();
+
+        let msgs: Vec<Msg> = batch_results(rx, Duration::from_millis(100))
+            .collect()
+            .await;
+
+        assert_eq!(msgs.len(), 2);
+        assert!(matches!(&msgs[0], Msg::PingResults(batch) if batch.len() == 100));
+        assert!(matches!(msgs[1], Msg::ScanComplete));
+    }
+
+    #[tokio::test]
+    async fn test_batch_results_forwards_latest_progress() {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        tx.send(ScanMessage::Result(ScannedIp::new(ip, true, 1, Vec::new())))
+            .unwrap();
+        for scanned in 1..=3 {
+            tx.send(ScanMessage::Progress { scanned, total: 4 })
+                .unwrap();
+        }
+        drop(tx);
+
+        let msgs: Vec<Msg> = batch_results(rx, Duration::from_millis(100))
+            .collect()
+            .await;
+
+        assert_eq!(msgs.len(), 2);
+        assert!(matches!(&msgs[0], Msg::PingResults(batch) if batch.len() == 1));
+        assert!(matches!(
+            msgs[1],
+            Msg::ScanProgress {
+                scanned: 3,
+                total: 4
+            }
+        ));
+    }
+
+    #[test]
+    fn test_scan_stats_status_line() {
+        let start = Instant::now();
+        let mut stats = ScanStats::start(start);
+        stats.progress(64, 256);
+        stats.responded = 5;
+
+        let now = start + Duration::from_secs(30);
+        assert_eq!(stats.remaining(now), Some(Duration::from_secs(90)));
+        assert_eq!(
+            stats.status(now),
+            "0:30 elapsed · 5 of 64 hosts responded · ~1:30 left"
+        );
+
+        // A cancelled scan keeps the count it reached
+        let mut cancelled = stats.clone();
+        cancelled.finish(now, true);
+        cancelled.finish(now, false);
+        assert_eq!(
+            cancelled.status(now),
+            "0:30 elapsed · 5 of 64 hosts responded"
+        );
+
+        // The clock stops with the scan
+        stats.finish(now, false);
+        let later = now + Duration::from_secs(3600);
+        assert_eq!(stats.elapsed(later), Duration::from_secs(30));
+        assert_eq!(
+            stats.status(later),
+            "0:30 elapsed · 5 of 256 hosts responded"
+        );
+        assert_eq!(format_clock(Duration::from_secs(3725)), "1:02:05");
+    }
+
+    #[test]
+    fn test_scan_summary() {
+        let start = Instant::now();
+        let mut stats = ScanStats::start(start);
+        let host = |last: u8, alive, ping, ports: Vec<u16>| {
+            ScannedIp::new(
+                IpAddr::V4(Ipv4Addr::new(10, 0, 0, last)),
+                alive,
+                ping,
+                ports,
+            )
+        };
+        let ips = [
+            host(1, true, 3, vec![22, 80]),
+            host(2, true, 48, vec![80, 443]),
+            host(3, false, 0, vec![]),
+        ];
+        // Nothing to sum up while the scan is still running
+        assert_eq!(stats.summary(&ips), None);
+
+        stats.finish(start + Duration::from_secs(42), false);
+        assert_eq!(
+            stats.summary(&ips).unwrap(),
+            "2 hosts alive · 3 unique open ports · ping 3–48ms · took 0:42"
+        );
+        assert_eq!(
+            stats.summary(&ips[2..]).unwrap(),
+            "0 hosts alive · 0 unique open ports · took 0:42"
+        );
+    }
+
+    #[test]
+    fn test_failure_summary() {
+        let mut stats = ScanStats::start(Instant::now());
+        assert_eq!(stats.failure_summary(), None);
+
+        stats.fail([ProbeError::Timeout, ProbeError::PermissionDenied]);
+        stats.fail([ProbeError::PermissionDenied]);
+        // Too early to tell, only a couple of hosts in
+        stats.progress(2, 256);
+        assert_eq!(stats.failure_summary(), None);
+
+        stats.progress(ScanStats::FAILURE_SAMPLE, 256);
+        let summary = stats.failure_summary().unwrap();
+        assert!(summary.starts_with("No hosts answered, 2 probes failed: permission denied."));
+
+        // Other errors count together whatever their message
+        let other = |message: &str| ProbeError::Other(String::from(message));
+        stats.fail([other("host is down"), other("address in use")]);
+        stats.fail([other("os error 113")]);
+        let summary = stats.failure_summary().unwrap();
+        assert!(summary.starts_with("No hosts answered, 3 probes failed: host is down."));
+
+        // Timeouts are normal once anything on the network answers
+        stats.responded = 1;
+        assert_eq!(stats.failure_summary(), None);
+    }
+
+    #[test]
+    fn test_csv_row() {
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 42));
+        let host = ScannedIp::new(ip, true, 3, vec![22, 80]);
+        assert_eq!(csv_row(&host), "192.168.1.42,3,up,22 80,,,");
+
+        let down = ScannedIp::new(ip, false, 0, Vec::new());
+        assert_eq!(csv_row(&down), "192.168.1.42,0,down,,,,");
+    }
+
+    #[test]
+    fn test_matches_filter() {
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 42));
+        let host = ScannedIp::new(ip, true, 1, vec![22, 8080]).with_interface("eth0");
+
+        assert!(matches_filter(&host, ""));
+        assert!(matches_filter(&host, " 1.42 "));
+        assert!(matches_filter(&host, "ETH"));
+        assert!(matches_filter(&host, "8080"));
+        // Ports match whole numbers only, so 80 doesn't pick up 8080
+        assert!(!matches_filter(&host, "80"));
+        assert!(!matches_filter(&host, "10.0"));
+    }
+
+    #[test]
+    fn test_age_formatting_and_staleness() {
+        assert_eq!(format_age(Duration::from_millis(2500)), "2s ago");
+        assert_eq!(format_age(Duration::from_secs(45)), "45s ago");
+        assert_eq!(format_age(Duration::from_secs(190)), "3m ago");
+        assert_eq!(format_age(Duration::from_secs(7200)), "2h ago");
+
+        let interval = Duration::from_secs(30);
+        assert_eq!(staleness(Duration::from_secs(10), interval), 0.0);
+        assert_eq!(staleness(Duration::from_secs(45), interval), 0.5);
+        assert_eq!(staleness(Duration::from_secs(600), interval), 1.0);
     }
 }
@@ -1,6 +1,7 @@
 pub mod ip_scan;
 pub mod settings;
 pub mod tcp_client;
+pub mod theme_edit;
 
 pub mod udp_client;
 // pub use self::ip_scan::{view, subscription};
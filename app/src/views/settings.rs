@@ -1,58 +1,554 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fs::read_to_string;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
 
 use crate::Msg;
+use crate::keybindings::{self, Action, BindError, KeyCombo};
+use crate::views::ip_scan::ScanStats;
+use crate::views::theme_edit::{self, ThemeEditor};
 
 use iced::Alignment::Center;
-use iced::Element;
-use iced::Length::Fill;
-use iced::widget::{column, scrollable, text, text_input};
+use iced::Length::{Fill, Fixed};
+use iced::font::Family;
+use iced::task::Handle;
+use iced::widget::{
+    Column, Row, button, checkbox, column, container, radio, scrollable, slider, text, text_input,
+};
+use iced::{Element, Font};
 use iced_widget::{horizontal_rule, row};
-use net_monkey_components::{LabelWithHint, SubnetSlider, TextInputDropdown};
-use net_monkey_core::{NetworkAdapter, ScannedIp};
-use net_monkey_theme::ThemeProvider;
+use net_monkey_components::{
+    LabelWithHint, NumberInput, SubnetSlider, TextInputDropdown, TextInputWithHint, is_ip_char,
+};
+use net_monkey_core::{
+    CancelToken, ConnectionEvent, ConnectionHandle, NetworkAdapter, PacketEncoding, ProbeMethod,
+    ScanConfig, ScanOrder, ScanTarget, ScannedIp, TaskState, load_scan_results, save_scan_results,
+};
+use net_monkey_theme::helpers::{self, ButtonVariant};
+use net_monkey_theme::{SimpleColors, ThemeDefinition, ThemeManager, ThemeProvider};
 use serde::{Deserialize, Serialize};
 
+/// One row per action with a button that captures the next key press,
+/// followed by why the last key pressed for it was refused
+fn keybinding_rows(app: &IpScannerApp) -> Column<'_, Msg> {
+    let colors = app.theme_provider().colors();
+    Column::with_children(Action::ALL.iter().flat_map(|action| {
+        let label = match (app.rebinding, app.config.keybindings.get(action)) {
+            (Some(rebinding), _) if rebinding == *action => String::from("Press a key..."),
+            (_, Some(combo)) => combo.to_string(),
+            (_, None) => String::from("Unbound"),
+        };
+        let error = match &app.rebind_error {
+            Some((failed, e)) if failed == action => Some(
+                text(format!("Couldn't bind it, {e}"))
+                    .size(14)
+                    .color(colors.danger_color())
+                    .into(),
+            ),
+            _ => None,
+        };
+        let row = row![
+            text(action.label()).size(18).width(Fill),
+            button(text(label).size(18))
+                .style(helpers::themed_button(
+                    ButtonVariant::Secondary,
+                    &app.theme_provider()
+                ))
+                .on_press(Msg::Rebind(*action)),
+        ]
+        .align_y(Center)
+        .spacing(8)
+        .into();
+        std::iter::once(row).chain(error)
+    }))
+    // Fixed, but listed so they can be found
+    .push(row![
+        text("Jump to Tab").size(18).width(Fill),
+        text("Ctrl+1 to Ctrl+4").size(18),
+    ])
+    .push(row![
+        text("Undo / Redo Theme Color").size(18).width(Fill),
+        text("Ctrl+Z / Ctrl+Y").size(18),
+    ])
+    .spacing(8)
+}
+
+/// Slider for [`AppConfig::ui_scale`] with the scale it's set to
+fn ui_scale_slider(app: &IpScannerApp) -> Element<'_, Msg> {
+    let scale = app.ui_scale_drag.unwrap_or(app.config.ui_scale());
+    row![
+        slider(AppConfig::UI_SCALE_RANGE, scale, Msg::UiScaleDrag)
+            .step(0.05f32)
+            .on_release(Msg::UiScaleReleased),
+        text(format!("{:.0}%", scale * 100.0))
+            .size(18)
+            .width(Fixed(60.0)),
+    ]
+    .align_y(Center)
+    .spacing(8)
+    .into()
+}
+
+/// One checkbox per adapter to include its subnet in multi-adapter scans
+fn adapter_checkboxes(app: &IpScannerApp) -> Column<'_, Msg> {
+    Column::with_children(app.adaptors.iter().map(|adapter| {
+        let name = adapter.name.clone();
+        checkbox(
+            format!("{adapter} /{}", adapter.prefix),
+            app.config.scan_adapters.contains(&adapter.name),
+        )
+        .on_toggle(move |on| Msg::Config(ChangeConfig::ScanAdapter(name.clone(), on)))
+        .text_size(18)
+        .into()
+    }))
+    .spacing(8)
+}
+
+/// The Ports field, flagging any tokens that were left out of the scan
+fn ports_input(app: &IpScannerApp) -> Element<'_, Msg> {
+    let field = app.config.ports_field();
+    let error = ParsedPorts::parse(&field).error();
+    TextInputWithHint::new(
+        field,
+        "Ports List",
+        "Ports from 1 to 65535 or ranges like 20-25, separated by commas",
+        |s| Msg::Config(ChangeConfig::Ports(s)),
+    )
+    .error(error)
+    .text_size(24.0)
+    .theme(app.theme_provider())
+    .into_element()
+}
+
+/// A labelled stepper for one numeric `ScanConfig` field
+fn scan_setting<'a>(
+    app: &'a IpScannerApp,
+    label: &str,
+    hint: &str,
+    value: u64,
+    range: std::ops::RangeInclusive<u64>,
+    apply: fn(&mut ScanConfig, u64),
+) -> Element<'a, Msg> {
+    let scan = app.config.scan.clone();
+    column![
+        LabelWithHint::new(label, hint)
+            .text_size(18.0)
+            .theme(app.theme_provider())
+            .into_element(),
+        NumberInput::new(value, range, move |v| {
+            let mut scan = scan.clone();
+            apply(&mut scan, v);
+            Msg::Config(ChangeConfig::Scan(scan))
+        })
+        .text_size(18.0)
+        .theme(app.theme_provider())
+        .into_element(),
+    ]
+    .spacing(4)
+    .into()
+}
+
+/// Collapsible panel exposing the scanner's tuning knobs
+fn advanced_scan_settings(app: &IpScannerApp) -> Element<'_, Msg> {
+    let toggle = button(text(match app.show_advanced_scan {
+        true => "▾ Advanced scan settings",
+        false => "▸ Advanced scan settings",
+    }))
+    .style(helpers::themed_button(
+        ButtonVariant::Text,
+        &app.theme_provider(),
+    ))
+    .on_press(Msg::ToggleAdvancedScan);
+    if !app.show_advanced_scan {
+        return toggle.into();
+    }
+
+    let scan = &app.config.scan;
+    let range = |r: std::ops::RangeInclusive<usize>| *r.start() as u64..=*r.end() as u64;
+    column![
+        toggle,
+        scan_setting(
+            app,
+            "Concurrency",
+            "Maximum pings in flight at once (1-2048). Lower this if the network or OS drops replies",
+            scan.max_concurrent as u64,
+            range(ScanConfig::MAX_CONCURRENT),
+            |scan, v| scan.max_concurrent = v as usize,
+        ),
+        scan_setting(
+            app,
+            "Ping timeout (ms)",
+            "How long to wait for each host to reply (50-60000). Every dead host waits this long, so raise it only for slow or distant networks",
+            scan.timeout_ms,
+            ScanConfig::TIMEOUT_MS,
+            |scan, v| scan.timeout_ms = v,
+        ),
+        scan_setting(
+            app,
+            "Retries",
+            "Extra pings sent to hosts that didn't reply (0-10)",
+            scan.retries as u64,
+            *ScanConfig::RETRIES.start() as u64..=*ScanConfig::RETRIES.end() as u64,
+            |scan, v| scan.retries = v.min(u8::MAX as u64) as u8,
+        ),
+        scan_setting(
+            app,
+            "Pings per Host",
+            "Echo requests sent to each host (1-10). The reported ping is their average, which smooths out jitter",
+            scan.pings_per_host as u64,
+            *ScanConfig::PINGS_PER_HOST.start() as u64..=*ScanConfig::PINGS_PER_HOST.end() as u64,
+            |scan, v| scan.pings_per_host = v.min(u8::MAX as u64) as u8,
+        ),
+        scan_setting(
+            app,
+            "Inter-packet Delay (ms)",
+            "Pause between starting probes (0-10000). Useful on rate limited networks",
+            scan.packet_delay_ms,
+            ScanConfig::PACKET_DELAY_MS,
+            |scan, v| scan.packet_delay_ms = v,
+        ),
+        scan_setting(
+            app,
+            "Max Packets per Second",
+            "Most pings and port connects sent per second (0-100000, 0 for no limit)",
+            scan.max_pps.map_or(0, u64::from),
+            0..=*ScanConfig::MAX_PPS.end() as u64,
+            |scan, v| scan.max_pps = (v > 0).then(|| v.min(u32::MAX as u64) as u32),
+        ),
+        scan_setting(
+            app,
+            "Payload Size (bytes)",
+            "Bytes of data in each echo request (0-1472)",
+            scan.payload_size as u64,
+            range(ScanConfig::PAYLOAD_SIZE),
+            |scan, v| scan.payload_size = v as usize,
+        ),
+        checkbox("Randomize host order", scan.order == ScanOrder::Random)
+            .on_toggle(|random| {
+                Msg::Config(ChangeConfig::ScanOrder(match random {
+                    true => ScanOrder::Random,
+                    false => ScanOrder::Sequential,
+                }))
+            })
+            .text_size(18),
+        checkbox(
+            "Probe with TCP connects instead of ping (no admin rights needed)",
+            scan.probe == ProbeMethod::TcpConnect
+        )
+        .on_toggle(|tcp| {
+            Msg::Config(ChangeConfig::ProbeMethod(match tcp {
+                true => ProbeMethod::TcpConnect,
+                false => ProbeMethod::Icmp,
+            }))
+        })
+        .text_size(18),
+        checkbox("List hosts that didn't answer as down", scan.report_down)
+            .on_toggle(|on| Msg::Config(ChangeConfig::ReportDown(on)))
+            .text_size(18),
+    ]
+    .spacing(10)
+    .into()
+}
+
+/// Threshold fields with sample chips showing where each color starts
+fn ping_thresholds(app: &IpScannerApp) -> Element<'_, Msg> {
+    let theme_provider = app.theme_provider();
+    let colors = theme_provider.colors();
+    let field = |label: &'static str,
+                 value: u64,
+                 hint: &'static str,
+                 change: fn(String) -> ChangeConfig| {
+        row![
+            text(label).size(18).width(Fill),
+            TextInputWithHint::new(value.to_string(), label, hint, move |s| Msg::Config(
+                change(s)
+            ))
+            .width(Fixed(160.0))
+            .text_size(18.0)
+            .theme(theme_provider.clone())
+            .into_element(),
+        ]
+        .align_y(Center)
+        .spacing(8)
+    };
+
+    let thresholds = app.config.ping_thresholds();
+    let chip = |label: String, ping: u64| {
+        let color = thresholds.color(ping as u128, colors);
+        container(text(label).size(14).color(colors.background_color()))
+            .padding([2, 8])
+            .style(move |_| container::Style {
+                background: Some(iced::Background::Color(color)),
+                border: iced::Border {
+                    radius: 8.0.into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+    };
+    let PingThresholds { good_ms, warn_ms } = thresholds;
+    let mut col = column![
+        field(
+            "Good Ping (ms)",
+            app.config.ping_good_ms,
+            "Pings up to this are shown green",
+            ChangeConfig::PingGoodMs
+        ),
+        field(
+            "Slow Ping (ms)",
+            app.config.ping_warn_ms,
+            "Pings fade from green through yellow to red between the good threshold and this",
+            ChangeConfig::PingWarnMs
+        ),
+        row![
+            chip(format!("≤ {good_ms}ms"), good_ms),
+            chip(
+                format!("{}ms", good_ms.midpoint(warn_ms)),
+                good_ms.midpoint(warn_ms)
+            ),
+            chip(format!("≥ {warn_ms}ms"), warn_ms),
+        ]
+        .spacing(8),
+    ]
+    .spacing(8);
+    let entered = PingThresholds {
+        good_ms: app.config.ping_good_ms,
+        warn_ms: app.config.ping_warn_ms,
+    };
+    if !entered.is_valid() {
+        col = col.push(
+            text("The good threshold must be below the slow one, using the defaults until it is")
+                .size(14)
+                .color(colors.danger_color()),
+        );
+    }
+    col.into()
+}
+
+/// Recall a saved range from the dropdown, or name the current one to save it
+fn favorites(app: &IpScannerApp) -> Element<'_, Msg> {
+    let theme_provider = app.theme_provider();
+    let name = app.favorite_name.trim();
+    let exists = app.config.favorites.iter().any(|f| f.name == name);
+    let picker: TextInputDropdown<_, _, Msg, iced::Theme> = TextInputDropdown::new(
+        app.config.favorites.as_slice(),
+        app.favorite_name.clone(),
+        Msg::FavoriteName,
+        |favorite: SavedScan| Msg::Config(ChangeConfig::RecallFavorite(favorite.name)),
+    )
+    .text_size(18);
+    row![
+        iced::Element::from(picker),
+        button(text(match exists {
+            true => "Update",
+            false => "Save",
+        }))
+        .style(helpers::themed_button(
+            ButtonVariant::Primary,
+            &theme_provider
+        ))
+        .on_press_maybe(
+            (!name.is_empty()).then(|| Msg::Config(ChangeConfig::SaveFavorite(name.to_string())))
+        ),
+        button(text("Delete"))
+            .style(helpers::themed_button(
+                ButtonVariant::Danger,
+                &theme_provider
+            ))
+            .on_press_maybe(
+                exists.then(|| Msg::Config(ChangeConfig::RemoveFavorite(name.to_string())))
+            ),
+    ]
+    .align_y(Center)
+    .spacing(8)
+    .into()
+}
+
+/// The starting IP field, focused whenever the Settings tab opens
+pub fn starting_ip_id() -> text_input::Id {
+    text_input::Id::new("starting_ip")
+}
+
 pub fn view<'a>(app: &'a IpScannerApp) -> Element<'a, Msg> {
-    let items = app.adaptors.clone();
-    println!("{items:?}");
+    let items: Vec<_> = app
+        .config
+        .allowed_adapters(&app.adaptors)
+        .cloned()
+        .collect();
+    let down = items.iter().filter(|a| !a.is_up).cloned().collect();
     let ip_sel: TextInputDropdown<_, _, Msg, iced::Theme> = TextInputDropdown::new(
         items,
         app.config.starting_ip.to_string(),
         |s| Msg::Config(ChangeConfig::StartingIp(s)),
         |s| Msg::Config(ChangeConfig::StartingIp(s.ip_address)),
     )
-    .text_size(24);
-    let subnet_slider = SubnetSlider::new(app.config.subnet_mask, Msg::subnet_mask)
+    .dimmed(down)
+    .enabled(app.loaded)
+    .input_filter(is_ip_char)
+    .valid(app.config.starting_ip_valid())
+    .error_color(app.theme_provider().colors().danger_color())
+    .id(starting_ip_id())
+    .text_size(24)
+    .font(Font::MONOSPACE);
+    let ipv6 = app.config.forced_ip_mode == ForcedIPMode::V6;
+    let prefix = match ipv6 {
+        true => app.config.ipv6_prefix,
+        false => app.config.subnet_mask,
+    };
+    let mut subnet_slider = SubnetSlider::new(prefix, Msg::subnet_mask)
+        .ipv6(ipv6)
         .text_size(24.0)
         .height(45.0)
-        .into_element();
+        .show_host_count(true)
+        // IPv6 sweeps wider than a /112 are cut short by the scanner
+        .warn_below_prefix(if ipv6 { 112 } else { 22 });
+    if let Ok(ip) = app.config.starting_ip.trim().parse() {
+        subnet_slider = subnet_slider.host(ip);
+    }
 
     scrollable(
         column![
             text("Network Configuration").size(22),
             horizontal_rule(2),
+            LabelWithHint::new(
+                "Favorites",
+                "Pick a saved range to fill in the Starting IP, Subnet Mask and Ports, or type a name and save the current ones"
+            )
+            .text_size(18.0)
+            .theme(app.theme_provider())
+            .into_element(),
+            favorites(app),
             text("Starting IP").size(18),
             iced::Element::from(ip_sel),
             text("Subnet Mask").size(18),
-            subnet_slider,
+            subnet_slider.into_element(),
+            LabelWithHint::new(
+                "IP Family",
+                "Which address family to scan. If the Starting IP is of another family the first matching adapter is scanned"
+            )
+            .text_size(18.0)
+            .theme(app.theme_provider())
+            .into_element(),
+            Row::with_children(ForcedIPMode::ALL.iter().map(|(mode, label)| {
+                radio(
+                    *label,
+                    *mode as usize,
+                    Some(app.config.forced_ip_mode as usize),
+                    |mode| Msg::Config(ChangeConfig::ForcedIPMode(mode)),
+                )
+                .text_size(18)
+                .into()
+            }))
+            .spacing(20),
+            LabelWithHint::new(
+                "Source Adapters",
+                "Scan each checked adapter's subnet from that adapter in parallel. Leave all unchecked to scan the Starting IP range"
+            )
+            .text_size(18.0)
+            .theme(app.theme_provider())
+            .into_element(),
+            adapter_checkboxes(app),
             LabelWithHint::new(
                 "Ports List",
-                "Comma-separated list of ports to scan (e.g., 80, 443, 22)"
+                "Comma-separated list of ports and ranges to scan (e.g., 80, 443, 20-25)"
             )
             .text_size(18.0)
-            .theme(app.config.theme_provider())
+            .theme(app.theme_provider())
+            .into_element(),
+            ports_input(app),
+            advanced_scan_settings(app),
+            LabelWithHint::new(
+                "Results Refresh (ms)",
+                "How often new scan results are shown. Higher values reduce UI churn on fast scans"
+            )
+            .text_size(18.0)
+            .theme(app.theme_provider())
+            .into_element(),
+            text_input(
+                "Results Refresh (ms)",
+                &app.config.results_refresh_ms.to_string()
+            )
+            .on_input(|s| Msg::Config(ChangeConfig::ResultsRefreshMs(s)))
+            .size(24),
+            checkbox("Monitor mode (rescan continuously)", app.config.monitor)
+                .on_toggle(|monitor| Msg::Config(ChangeConfig::Monitor(monitor)))
+                .text_size(18),
+            LabelWithHint::new(
+                "Monitor Interval (s)",
+                "Pause between rescans in monitor mode. Results older than this are shown as going stale"
+            )
+            .text_size(18.0)
+            .theme(app.theme_provider())
+            .into_element(),
+            NumberInput::new(app.config.monitor_interval_secs, 1..=3600, |secs| {
+                Msg::Config(ChangeConfig::MonitorInterval(secs))
+            })
+            .text_size(18.0)
+            .theme(app.theme_provider())
             .into_element(),
-            text_input("Ports List", &app.config.ports_to_string())
-                .on_input(|s| Msg::Config(ChangeConfig::Ports(s)))
-                .size(24),
             text("Appearance").size(22),
             horizontal_rule(2),
-            LabelWithHint::new("Theme", app.config.theme_provider().name())
+            ping_thresholds(app),
+            checkbox("Show raw ping (median, in µs precision)", app.config.raw_ping)
+                .on_toggle(|raw| Msg::Config(ChangeConfig::RawPing(raw)))
+                .text_size(18),
+            checkbox(
+                "Show results as a table, one row per host",
+                app.config.results_layout == ResultsLayout::Table
+            )
+            .on_toggle(|table| {
+                Msg::Config(ChangeConfig::ResultsLayout(match table {
+                    true => ResultsLayout::Table,
+                    false => ResultsLayout::Columns,
+                }))
+            })
+            .text_size(18),
+            LabelWithHint::new(
+                "UI Scale",
+                "Size of all text and controls, applied when the slider is let go"
+            )
+            .text_size(18.0)
+            .theme(app.theme_provider())
+            .into_element(),
+            ui_scale_slider(app),
+            LabelWithHint::new(
+                "Font",
+                "Used for everything but addresses, ports and packets, which are always monospace. Applied on the next launch"
+            )
+            .text_size(18.0)
+            .theme(app.theme_provider())
+            .into_element(),
+            Row::with_children(FontChoice::ALL.iter().map(|(font, label)| {
+                radio(*label, *font, Some(app.config.font), |font| {
+                    Msg::Config(ChangeConfig::Font(font))
+                })
+                .text_size(18)
+                .into()
+            }))
+            .spacing(20),
+            LabelWithHint::new("Theme", app.theme_provider().name())
                 .text_size(18.0)
-                .theme(app.config.theme_provider())
+                .theme(app.theme_provider())
                 .into_element(),
             row![text("COSMIC Theme (System-managed)").size(24).width(Fill),].spacing(8),
+            checkbox(
+                "Follow the system light/dark mode",
+                app.config.follow_system_theme
+            )
+            .on_toggle(|follow| Msg::Config(ChangeConfig::FollowSystemTheme(follow)))
+            .text_size(18),
+            theme_edit::view(app),
+            LabelWithHint::new(
+                "Keybindings",
+                "Click a binding then press the new key combo. Escape cancels, Tab is reserved"
+            )
+            .text_size(22.0)
+            .theme(app.theme_provider())
+            .into_element(),
+            horizontal_rule(2),
+            keybinding_rows(app),
         ]
         .align_x(Center)
         .spacing(12)
@@ -72,22 +568,85 @@ pub struct ConnectionData {
     pub ip_port: String,
     pub ip_address: String,
     pub current_packet: String,
+    /// Whether `current_packet` is text or hex bytes, and how traffic is shown
+    pub encoding: PacketEncoding,
     pub connections: Vec<IpAddr>,
     pub history: Vec<String>,
+    /// Send/receive tasks of the open connections, aborted on teardown
+    pub tasks: Vec<Handle>,
+    /// Sends packets on the open socket, once it has opened
+    pub socket: Option<ConnectionHandle>,
 }
 impl ConnectionData {
+    /// Keep hold of a connection task so it can be stopped with the connection
+    pub fn track(&mut self, handle: Handle) {
+        self.tasks.push(handle);
+    }
+
+    /// Stop every connection task and forget the open connections
+    pub fn teardown(&mut self) {
+        for handle in self.tasks.drain(..) {
+            handle.abort();
+        }
+        // Dropping the handle closes the socket
+        self.socket = None;
+        self.connections.clear();
+    }
+
+    /// Address and port entered for the connection
+    pub fn socket_addr(&self) -> Result<SocketAddr, String> {
+        let ip = IpAddr::parse_ascii(self.ip_address.trim().as_bytes())
+            .map_err(|_| format!("{} isn't a valid IP address", self.ip_address))?;
+        match self.ip_port.trim().parse::<u16>() {
+            Ok(port) if port > 0 => Ok(SocketAddr::new(ip, port)),
+            _ => Err(format!("Port must be 1-65535, not {}", self.ip_port)),
+        }
+    }
+
+    /// Bytes of the packet being composed, or why it can't be sent
+    pub fn packet(&self) -> Result<Vec<u8>, String> {
+        self.encoding.encode(&self.current_packet)
+    }
+
+    /// Record an event from the socket in the history
+    pub fn on_event(&mut self, event: ConnectionEvent) {
+        match event {
+            ConnectionEvent::Opened(socket) => {
+                self.socket = Some(socket);
+                if let Some(ip) = self.connections.first() {
+                    self.history.push(format!("Connected to {ip}"));
+                }
+            }
+            ConnectionEvent::Sent(packet) => {
+                let packet = self.encoding.render(&packet);
+                self.history.push(format!("> {packet}"));
+            }
+            ConnectionEvent::Received(packet) => {
+                let packet = self.encoding.render(&packet);
+                self.history.push(format!("< {packet}"));
+            }
+            ConnectionEvent::Error(e) => self.history.push(format!("Error: {e}")),
+            ConnectionEvent::NoReply => self.history.push(String::from("No reply")),
+            ConnectionEvent::Closed => {
+                self.history.push(String::from("Connection closed"));
+                self.teardown();
+            }
+        }
+    }
+
     pub fn update(&mut self, msg: Msg) {
         match msg {
             Msg::ChangePacket(pak) => self.current_packet = pak,
             Msg::ChangeIpAddress(ip) => self.ip_address = ip,
             Msg::ChangeIpPort(port) => self.ip_port = port,
-            Msg::SendPacket => self.history.push(self.current_packet.clone()),
-            Msg::ConnectionToggle if self.connections.is_empty() => {
-                if let Ok(conn) = IpAddr::parse_ascii(self.ip_address.as_bytes()) {
-                    self.connections.push(conn)
+            Msg::PacketEncoding(encoding) => self.encoding = encoding,
+            Msg::SendPacket => match (&self.socket, self.packet()) {
+                (Some(socket), Ok(packet)) => {
+                    socket.send(packet);
                 }
-            }
-            Msg::ConnectionToggle => self.connections.clear(),
+                (Some(_), Err(e)) => self.history.push(format!("Not sent: {e}")),
+                (None, _) => self.history.push(String::from("Not connected")),
+            },
             _ => {}
         }
     }
@@ -99,7 +658,14 @@ pub struct IpScannerApp {
     pub tab: ModeTab,
     // IP Scanner
     pub ips: Vec<ScannedIp>,
-    pub scan_progress: u8,
+    /// Progress of the current scan, which runs while the state is active
+    pub scan: TaskState,
+    /// Stops the running scan, set once the scanner has started
+    pub scan_cancel: Option<CancelToken>,
+    /// Timing and host counts for the scan status line
+    pub scan_stats: ScanStats,
+    /// COSMIC or OS-matched theme, kept so it isn't looked up on every redraw
+    pub system_theme: ThemeProvider,
     pub loaded: bool,
     pub tcp_client: ConnectionData,
     pub udp_client: ConnectionData,
@@ -108,13 +674,140 @@ pub struct IpScannerApp {
     // Settings
     pub adaptors: Vec<NetworkAdapter>,
     pub config: AppConfig,
+    /// Host whose latency detail is expanded in the results
+    pub selected_host: Option<IpAddr>,
+    /// Text typed into the filter above the results
+    pub results_filter: String,
+    /// Theme from `--theme` or `NO_COLOR`, used until the theme is changed in settings
+    pub theme_override: Option<ThemeDefinition>,
+    /// UI scale slider position while it's held, applied on release so the
+    /// slider doesn't resize under the pointer
+    pub ui_scale_drag: Option<f32>,
+    /// Order of `ips`, kept as results arrive
+    pub results_sort: ResultSort,
+    pub theme_editor: ThemeEditor,
+    pub show_advanced_scan: bool,
+    /// Name typed into the favorites field
+    pub favorite_name: String,
+    /// Action waiting for its next key press in settings
+    pub rebinding: Option<Action>,
+    /// Action whose last rebind was refused, and why
+    pub rebind_error: Option<(Action, BindError)>,
+    /// When each host's result was last refreshed, for the monitor age column
+    pub last_updated: HashMap<IpAddr, Instant>,
+    /// Time of the latest frame tick, so ages are measured from one instant
+    pub now: Option<Instant>,
+    /// Bumped on every config change, so only the last of a burst is saved
+    pub config_generation: u64,
+    /// Generation last written to disk
+    pub saved_generation: u64,
 }
 
 impl IpScannerApp {
     pub fn loaded(&mut self, c: AppConfig, a: Vec<NetworkAdapter>) {
+        self.tab = c.last_tab.clone();
         self.config = c;
         self.adaptors = a;
         self.loaded = true;
+        self.theme_editor.load(&AppConfig::theme_manager());
+        self.ips = load_scan_results(AppConfig::scan_results_path());
+        self.ips.sort_by_key(|result| result.ip);
+        self.ips.dedup_by_key(|result| result.ip);
+    }
+
+    /// Add a scan result keeping `ips` in the chosen sort order
+    ///
+    /// Rescanned hosts, including ones reloaded from the last run, replace
+    /// their old row instead of repeating.
+    pub fn record_result(&mut self, result: ScannedIp) {
+        insert_sorted(&mut self.ips, result, &self.results_sort);
+    }
+
+    /// Reorder `ips` after the sort key or direction changed
+    pub fn sort_results(&mut self) {
+        let sort = self.results_sort;
+        self.ips.sort_by(|a, b| sort.compare(a, b));
+    }
+
+    /// Write the config to disk, marking every change so far as saved
+    pub fn save_config(&mut self) {
+        if let Err(e) = self.config.save() {
+            eprintln!("Failed to save config: {e}");
+        }
+        self.saved_generation = self.config_generation;
+    }
+
+    /// Whether the config has changed since it was last saved
+    pub fn config_save_pending(&self) -> bool {
+        self.config_generation != self.saved_generation
+    }
+
+    /// Keep the results so they're shown again on the next start
+    pub fn save_results(&self) {
+        if let Err(e) = save_scan_results(&self.ips, AppConfig::scan_results_path()) {
+            eprintln!("Failed to save scan results: {e}");
+        }
+    }
+
+    /// Theme for the views, see [`AppConfig::theme_provider`]
+    pub fn theme_provider(&self) -> ThemeProvider {
+        match &self.theme_override {
+            Some(theme) => ThemeProvider::fallback(theme.colors),
+            None => self.config.theme_provider_with(&self.system_theme),
+        }
+    }
+
+    /// Pick up a COSMIC theme switch or a change in the OS appearance
+    pub fn refresh_theme(&mut self) {
+        match self.system_theme.is_cosmic_active() {
+            true => {
+                if let Err(e) = self.system_theme.refresh() {
+                    eprintln!("{e}");
+                }
+            }
+            false => self.system_theme = ThemeProvider::default(),
+        }
+    }
+
+    pub fn scanning(&self) -> bool {
+        self.scan.is_active()
+    }
+
+    /// Range the next single-range scan covers, see [`AppConfig::scan_range`]
+    pub fn scan_range(&self) -> Option<(IpAddr, u8)> {
+        self.config.scan_range(&self.adaptors)
+    }
+
+    /// Client/server state behind a connection tab
+    pub fn connection_mut(&mut self, tab: &ModeTab) -> Option<&mut ConnectionData> {
+        match tab {
+            ModeTab::TCPclient => Some(&mut self.tcp_client),
+            ModeTab::UDPclient => Some(&mut self.udp_client),
+            ModeTab::TCPserver => Some(&mut self.tcp_server),
+            ModeTab::UDPserver => Some(&mut self.udp_server),
+            ModeTab::IpScan | ModeTab::Settings => None,
+        }
+    }
+
+    /// Tear down every connection, used when the app closes
+    pub fn close_connections(&mut self) {
+        for conn in [
+            &mut self.tcp_client,
+            &mut self.udp_client,
+            &mut self.tcp_server,
+            &mut self.udp_server,
+        ] {
+            conn.teardown();
+        }
+    }
+
+    /// Subnets of the adapters selected for multi-adapter scanning
+    pub fn scan_targets(&self) -> Vec<ScanTarget> {
+        self.adaptors
+            .iter()
+            .filter(|adapter| self.config.scan_adapters.contains(&adapter.name))
+            .filter_map(ScanTarget::from_adapter)
+            .collect()
     }
 }
 
@@ -122,27 +815,211 @@ impl IpScannerApp {
 //     state: combo_box::State::new(adaptors.into()),
 //     selected: None,
 // };
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub starting_ip: String,
     pub subnet_mask: u8,
+    /// Prefix swept around an IPv6 starting IP
+    pub ipv6_prefix: u8,
     pub ports: Vec<u16>,
     pub forced_ip_mode: ForcedIPMode,
+    pub scan: ScanConfig,
+    /// Minimum time between scan result updates reaching the UI
+    pub results_refresh_ms: u64,
+    /// Pings faster than this are shown green
+    pub ping_good_ms: u64,
+    /// Pings faster than this, but not good, are shown yellow
+    pub ping_warn_ms: u64,
+    /// Match the OS light/dark preference instead of `dark_theme`. On for
+    /// new configs, but off for ones saved before it existed so their
+    /// chosen theme still applies
+    #[serde(default)]
+    pub follow_system_theme: bool,
+    pub dark_theme: bool,
+    /// Theme saved from the theme editor, used instead of dark/light when set
+    pub custom_theme: Option<ThemeDefinition>,
+    /// Show the precise median ping instead of whole milliseconds
+    pub raw_ping: bool,
+    pub results_layout: ResultsLayout,
+    /// Multiplies every size in the UI, for high-DPI displays
+    pub ui_scale: f32,
+    /// Default font, read once at launch
+    pub font: FontChoice,
+    /// Names of adapters whose subnets are scanned in parallel
+    pub scan_adapters: Vec<String>,
+    /// Rescan automatically after each scan completes
+    pub monitor: bool,
+    /// Pause between monitor rescans
+    pub monitor_interval_secs: u64,
+    pub keybindings: HashMap<Action, KeyCombo>,
+    /// Named scan ranges that can be recalled from settings
+    pub favorites: Vec<SavedScan>,
+    /// Tab open when the app was last used, reopened on launch
+    pub last_tab: ModeTab,
+    /// Where the main window was left, restored on launch
+    pub window: WindowGeometry,
+    /// The Ports field as typed, so rejected tokens stay visible until fixed
+    #[serde(skip)]
+    pub ports_input: Option<String>,
 }
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             starting_ip: String::from("192.168.1.1"),
             subnet_mask: 24,
+            ipv6_prefix: IPV6_SCAN_PREFIX,
             ports: vec![80, 443],
             forced_ip_mode: ForcedIPMode::Any,
+            scan: ScanConfig::default(),
+            results_refresh_ms: 100,
+            ping_good_ms: PingThresholds::default().good_ms,
+            ping_warn_ms: PingThresholds::default().warn_ms,
+            follow_system_theme: true,
+            dark_theme: true,
+            custom_theme: None,
+            raw_ping: false,
+            results_layout: ResultsLayout::default(),
+            ui_scale: 1.0,
+            font: FontChoice::default(),
+            scan_adapters: Vec::new(),
+            monitor: false,
+            monitor_interval_secs: 30,
+            keybindings: keybindings::default_keybindings(),
+            favorites: Vec::new(),
+            last_tab: ModeTab::default(),
+            window: WindowGeometry::default(),
+            ports_input: None,
         }
     }
 }
 impl AppConfig {
     /// Get theme provider for this config
     pub fn theme_provider(&self) -> ThemeProvider {
-        ThemeProvider::default()
+        self.theme_provider_with(&ThemeProvider::default())
+    }
+    /// Theme provider for this config, using `system` while following the OS
+    pub fn theme_provider_with(&self, system: &ThemeProvider) -> ThemeProvider {
+        match (
+            &self.custom_theme,
+            self.follow_system_theme,
+            self.dark_theme,
+        ) {
+            (Some(theme), _, _) => ThemeProvider::fallback(theme.colors),
+            (None, true, _) => system.clone(),
+            (None, false, true) => ThemeProvider::fallback(SimpleColors::DARK),
+            (None, false, false) => ThemeProvider::fallback(SimpleColors::LIGHT),
+        }
+    }
+    /// Whether the built-in theme in use is the dark one
+    pub fn is_dark(&self) -> bool {
+        match self.follow_system_theme {
+            true => net_monkey_theme::system::prefers_dark(),
+            false => self.dark_theme,
+        }
+    }
+    /// The theme in use, as it would be exported
+    pub fn active_theme(&self) -> ThemeDefinition {
+        let (name, colors) = match self.is_dark() {
+            true => ("Dark", SimpleColors::DARK),
+            false => ("Light", SimpleColors::LIGHT),
+        };
+        self.custom_theme.clone().unwrap_or(ThemeDefinition {
+            name: String::from(name),
+            colors,
+        })
+    }
+    /// Themes and theme editor drafts live in the data directory
+    pub fn theme_manager() -> ThemeManager {
+        ThemeManager::new(Self::data_file_path("themes"))
+    }
+    pub fn action_for(&self, combo: &KeyCombo) -> Option<Action> {
+        keybindings::action_for(&self.keybindings, combo)
+    }
+    pub fn bind_key(&mut self, action: Action, combo: KeyCombo) -> Result<(), BindError> {
+        keybindings::bind(&mut self.keybindings, action, combo)
+    }
+    /// Thresholds for coloring pings, the defaults while they're out of order
+    pub fn ping_thresholds(&self) -> PingThresholds {
+        let thresholds = PingThresholds {
+            good_ms: self.ping_good_ms,
+            warn_ms: self.ping_warn_ms,
+        };
+        match thresholds.is_valid() {
+            true => thresholds,
+            false => PingThresholds::default(),
+        }
+    }
+    pub fn results_refresh(&self) -> Duration {
+        Duration::from_millis(self.results_refresh_ms)
+    }
+    /// Base address and prefix of the range to scan
+    ///
+    /// The starting IP is used when it's of the forced IP family, otherwise
+    /// the first adapter of that family is scanned instead.
+    pub fn scan_range(&self, adapters: &[NetworkAdapter]) -> Option<(IpAddr, u8)> {
+        let starting = self
+            .starting_ip
+            .trim()
+            .parse()
+            .ok()
+            .filter(|ip| self.forced_ip_mode.allows(ip));
+        match starting {
+            Some(ip @ IpAddr::V6(_)) => Some((ip, self.ipv6_prefix)),
+            Some(ip) => Some((ip, self.subnet_mask)),
+            None => self
+                .allowed_adapters(adapters)
+                .find_map(|adapter| Some((adapter.ip_address.parse().ok()?, adapter.prefix))),
+        }
+    }
+    /// Smallest and largest UI scale the slider offers
+    pub const UI_SCALE_RANGE: RangeInclusive<f32> = 0.75..=2.5;
+    /// UI scale kept within [`Self::UI_SCALE_RANGE`], even if the file was edited by hand
+    pub fn ui_scale(&self) -> f32 {
+        self.ui_scale
+            .clamp(*Self::UI_SCALE_RANGE.start(), *Self::UI_SCALE_RANGE.end())
+    }
+    /// Narrowest layout width, the size iced lays the UI out in, that fits
+    /// the full tab labels
+    pub const FULL_TAB_LABELS_WIDTH: f32 = 600.0;
+    /// Whether the window is too narrow for the full tab labels
+    pub fn compact_tabs(&self) -> bool {
+        self.window.layout_width(self.ui_scale()) < Self::FULL_TAB_LABELS_WIDTH
+    }
+    /// Whether the starting IP parses as an address, or is empty so an
+    /// adapter's is used
+    pub fn starting_ip_valid(&self) -> bool {
+        let ip = self.starting_ip.trim();
+        ip.is_empty() || IpAddr::parse_ascii(ip.as_bytes()).is_ok()
+    }
+    /// Adapters whose address is of a family `forced_ip_mode` permits
+    pub fn allowed_adapters<'a>(
+        &self,
+        adapters: &'a [NetworkAdapter],
+    ) -> impl Iterator<Item = &'a NetworkAdapter> {
+        let mode = self.forced_ip_mode;
+        adapters.iter().filter(move |adapter| {
+            adapter
+                .ip_address
+                .parse()
+                .is_ok_and(|ip: IpAddr| mode.allows(&ip))
+        })
+    }
+    /// Prefix the slider shows for `starting_ip`, which depends on its family
+    fn prefix_for(&self, starting_ip: &str) -> u8 {
+        match starting_ip.trim().parse::<IpAddr>() {
+            Ok(IpAddr::V6(_)) => self.ipv6_prefix,
+            _ => self.subnet_mask,
+        }
+    }
+    pub fn monitor_interval(&self) -> Duration {
+        Duration::from_secs(self.monitor_interval_secs)
+    }
+    /// Text for the Ports field, as typed if it's been edited
+    pub fn ports_field(&self) -> String {
+        self.ports_input
+            .clone()
+            .unwrap_or_else(|| self.ports_to_string())
     }
     pub fn ports_to_string(&self) -> String {
         self.ports
@@ -170,11 +1047,84 @@ impl AppConfig {
     pub fn update(&mut self, change: ChangeConfig) {
         match change {
             ChangeConfig::StartingIp(ip) => self.starting_ip = ip,
-            ChangeConfig::SubnetMask(mask) => self.subnet_mask = mask.parse().unwrap_or_default(),
-            ChangeConfig::Ports(ports) => {
-                self.ports = ports.split(',').filter_map(|p| p.parse().ok()).collect()
+            // The slider edits the IPv6 prefix while IPv6 is forced
+            ChangeConfig::SubnetMask(mask) => {
+                let mask = mask.parse().unwrap_or_default();
+                match self.forced_ip_mode {
+                    ForcedIPMode::V6 => self.ipv6_prefix = mask,
+                    _ => self.subnet_mask = mask,
+                }
+            }
+            ChangeConfig::Ports(input) => {
+                self.ports = ParsedPorts::parse(&input).ports;
+                self.ports_input = Some(input);
             }
             ChangeConfig::ForcedIPMode(mode) => self.forced_ip_mode = mode.into(),
+            ChangeConfig::PingGoodMs(ms) => {
+                if let Ok(ms) = ms.trim().parse() {
+                    self.ping_good_ms = ms;
+                }
+            }
+            ChangeConfig::PingWarnMs(ms) => {
+                if let Ok(ms) = ms.trim().parse() {
+                    self.ping_warn_ms = ms;
+                }
+            }
+            ChangeConfig::ResultsRefreshMs(ms) => {
+                if let Ok(ms) = ms.trim().parse() {
+                    self.results_refresh_ms = ms;
+                }
+            }
+            // The first toggle from a custom theme returns to the built-in one,
+            // and toggling while following the OS picks the opposite of what it shows
+            ChangeConfig::ToggleTheme => {
+                if self.custom_theme.take().is_none() {
+                    self.dark_theme = !self.is_dark();
+                    self.follow_system_theme = false;
+                }
+            }
+            ChangeConfig::FollowSystemTheme(follow) => self.follow_system_theme = follow,
+            ChangeConfig::RawPing(raw) => self.raw_ping = raw,
+            ChangeConfig::ResultsLayout(layout) => self.results_layout = layout,
+            ChangeConfig::UiScale(scale) => self.ui_scale = scale,
+            ChangeConfig::Font(font) => self.font = font,
+            ChangeConfig::Monitor(monitor) => self.monitor = monitor,
+            ChangeConfig::MonitorInterval(secs) => self.monitor_interval_secs = secs,
+            ChangeConfig::Scan(scan) => self.scan = scan,
+            ChangeConfig::ScanOrder(order) => self.scan.order = order,
+            ChangeConfig::ProbeMethod(probe) => self.scan.probe = probe,
+            ChangeConfig::ReportDown(on) => self.scan.report_down = on,
+            ChangeConfig::ScanAdapter(name, true) => {
+                if !self.scan_adapters.contains(&name) {
+                    self.scan_adapters.push(name);
+                }
+            }
+            ChangeConfig::ScanAdapter(name, false) => self.scan_adapters.retain(|n| n != &name),
+            // Saving under an existing name replaces that favorite
+            ChangeConfig::SaveFavorite(name) => {
+                let favorite = SavedScan {
+                    name: name.trim().to_string(),
+                    starting_ip: self.starting_ip.clone(),
+                    subnet_mask: self.prefix_for(&self.starting_ip),
+                    ports: self.ports.clone(),
+                };
+                match self.favorites.iter_mut().find(|f| f.name == favorite.name) {
+                    Some(existing) => *existing = favorite,
+                    None => self.favorites.push(favorite),
+                }
+            }
+            ChangeConfig::RecallFavorite(name) => {
+                if let Some(favorite) = self.favorites.iter().find(|f| f.name == name).cloned() {
+                    match favorite.starting_ip.trim().parse::<IpAddr>() {
+                        Ok(IpAddr::V6(_)) => self.ipv6_prefix = favorite.subnet_mask,
+                        _ => self.subnet_mask = favorite.subnet_mask,
+                    }
+                    self.starting_ip = favorite.starting_ip;
+                    self.ports = favorite.ports;
+                    self.ports_input = None;
+                }
+            }
+            ChangeConfig::RemoveFavorite(name) => self.favorites.retain(|f| f.name != name),
         }
     }
     pub fn load() -> Option<Self> {
@@ -192,9 +1142,19 @@ impl AppConfig {
 
     /// Get the config file path based on build mode
     fn config_file_path() -> String {
+        Self::data_file_path("config.json")
+    }
+
+    /// The last scan's results, kept next to the config
+    pub fn scan_results_path() -> String {
+        Self::data_file_path("scan_results.json")
+    }
+
+    /// Get the path of a file in the app data directory based on build mode
+    pub fn data_file_path(file_name: &str) -> String {
         #[cfg(debug_assertions)]
         {
-            // In debug mode, find the workspace root and use app/data/config.json
+            // In debug mode, find the workspace root and use app/data/<file_name>
             if let Ok(current_dir) = std::env::current_dir() {
                 let mut path = current_dir;
                 // Look for workspace Cargo.toml (contains [workspace]) to identify workspace root
@@ -208,39 +1168,322 @@ impl AppConfig {
                     }
                     if !path.pop() {
                         // Fallback if we can't find workspace root
-                        return "app/data/config.json".to_string();
+                        return format!("app/data/{file_name}");
                     }
                 }
                 path.push("app");
                 path.push("data");
-                path.push("config.json");
+                path.push(file_name);
                 path.to_string_lossy().to_string()
             } else {
-                "app/data/config.json".to_string()
+                format!("app/data/{file_name}")
             }
         }
         #[cfg(not(debug_assertions))]
         {
             // In release mode, use current working directory
-            "data/config.json".to_string()
+            format!("data/{file_name}")
         }
     }
 }
-// Implementation on App to prevent config being overwritten on load
+fn insert_sorted(ips: &mut Vec<ScannedIp>, result: ScannedIp, sort: &ResultSort) {
+    // A rescan can change the ping or ports, so the old row may need to move
+    if let Some(index) = ips.iter().position(|ip| ip.ip == result.ip) {
+        ips.remove(index);
+    }
+    let index = ips.partition_point(|ip| sort.compare(ip, &result) != Ordering::Greater);
+    ips.insert(index, result);
+}
+
 impl Drop for IpScannerApp {
     fn drop(&mut self) {
-        if let Err(e) = self.config.save() {
-            eprintln!("Failed to save config: {e}");
+        self.close_connections();
+        // Only a change made just before closing can still be waiting to be saved
+        if self.config_save_pending() {
+            self.save_config();
+        }
+    }
+}
+/// Size and, where the platform reports it, position of the main window, in
+/// the unscaled logical units the window is opened with
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowGeometry {
+    pub width: f32,
+    pub height: f32,
+    /// Top left corner, unknown on platforms that don't report window moves
+    pub position: Option<(f32, f32)>,
+}
+impl Default for WindowGeometry {
+    fn default() -> Self {
+        Self {
+            width: 500.0,
+            height: 800.0,
+            position: None,
+        }
+    }
+}
+impl WindowGeometry {
+    /// Smaller than this is a minimized window rather than a size to restore
+    const MIN_SIZE: f32 = 200.0;
+    /// Windows parks minimized windows around -32000
+    const MIN_POSITION: f32 = -10_000.0;
+
+    /// Width iced lays the UI out in at the UI `scale`, as `Resized` reports it
+    pub fn layout_width(&self, scale: f32) -> f32 {
+        self.width / scale
+    }
+
+    /// Record a resize, returning whether it was kept
+    ///
+    /// iced reports the size divided by the UI `scale`, so it's multiplied
+    /// back to what the window is opened with.
+    pub fn resized(&mut self, width: f32, height: f32, scale: f32) -> bool {
+        let (width, height) = (width * scale, height * scale);
+        if width < Self::MIN_SIZE || height < Self::MIN_SIZE {
+            return false;
+        }
+        (self.width, self.height) = (width, height);
+        true
+    }
+
+    /// Record a move reported at the UI `scale`, returning whether it was kept
+    pub fn moved(&mut self, x: f32, y: f32, scale: f32) -> bool {
+        let (x, y) = (x * scale, y * scale);
+        if x < Self::MIN_POSITION || y < Self::MIN_POSITION {
+            return false;
+        }
+        self.position = Some((x, y));
+        true
+    }
+}
+/// Where ping times change color in the results and exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PingThresholds {
+    /// Up to this is green
+    pub good_ms: u64,
+    /// Red from here up, with a gradient through yellow from `good_ms`
+    pub warn_ms: u64,
+}
+impl Default for PingThresholds {
+    fn default() -> Self {
+        Self {
+            good_ms: 50,
+            warn_ms: 150,
+        }
+    }
+}
+impl PingThresholds {
+    /// Whether the good threshold sits below the warning one
+    pub fn is_valid(&self) -> bool {
+        self.good_ms < self.warn_ms
+    }
+    /// Green up to `good_ms`, fading through yellow to red at `warn_ms`
+    pub fn color(&self, ping: u128, colors: SimpleColors) -> iced::Color {
+        colors.latency_color(ping, self.good_ms.into(), self.warn_ms.into())
+    }
+}
+
+/// Ports read from the comma separated Ports field, where `20-25` is a range
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedPorts {
+    /// Each port once, in the order first listed
+    pub ports: Vec<u16>,
+    /// Tokens that aren't a port from 1 to 65535 or a range of them, or
+    /// that would take the total past [`Self::MAX_PORTS`]
+    pub rejected: Vec<String>,
+}
+impl ParsedPorts {
+    /// Most ports in all, as every port is probed on every host
+    pub const MAX_PORTS: usize = 1024;
+
+    pub fn parse(input: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut seen: HashSet<u16> = HashSet::new();
+        for token in input.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            let new: Vec<u16> = match parse_port_range(token) {
+                Some(range) => range.filter(|port| !seen.contains(port)).collect(),
+                None => {
+                    parsed.rejected.push(token.to_string());
+                    continue;
+                }
+            };
+            match parsed.ports.len() + new.len() <= Self::MAX_PORTS {
+                true => {
+                    seen.extend(&new);
+                    parsed.ports.extend(new);
+                }
+                false => parsed.rejected.push(token.to_string()),
+            }
+        }
+        parsed
+    }
+
+    /// Why the rejected tokens were left out, if there are any
+    pub fn error(&self) -> Option<String> {
+        (!self.rejected.is_empty()).then(|| {
+            format!(
+                "Ignored {}: use ports 1-65535 or ranges like 20-25, up to {} ports in all",
+                self.rejected.join(", "),
+                Self::MAX_PORTS
+            )
+        })
+    }
+}
+
+/// A port or ascending `start-end` range, all within 1..=65535
+fn parse_port_range(token: &str) -> Option<std::ops::RangeInclusive<u16>> {
+    let port = |s: &str| s.trim().parse::<u16>().ok().filter(|&p| p > 0);
+    let range = match token.split_once('-') {
+        Some((start, end)) => port(start)?..=port(end)?,
+        None => port(token).map(|p| p..=p)?,
+    };
+    (!range.is_empty() && range.len() <= ParsedPorts::MAX_PORTS).then_some(range)
+}
+
+/// A scan range saved under a name in the favorites list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedScan {
+    pub name: String,
+    pub starting_ip: String,
+    pub subnet_mask: u8,
+    pub ports: Vec<u16>,
+}
+impl std::fmt::Display for SavedScan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}/{})",
+            self.name, self.starting_ip, self.subnet_mask
+        )
+    }
+}
+
+/// Default prefix for an IPv6 starting IP, sweeping its low 64 bits
+pub const IPV6_SCAN_PREFIX: u8 = 64;
+
+/// How scan results are laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ResultsLayout {
+    /// A container per field, side by side
+    #[default]
+    Columns,
+    /// One row per host, keeping each host's cells level when one wraps
+    Table,
+}
+
+/// Font used for text that isn't addresses, ports or packets, which are
+/// always monospace so their digits line up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FontChoice {
+    #[default]
+    SansSerif,
+    Serif,
+    Monospace,
+}
+
+impl FontChoice {
+    pub const ALL: [(FontChoice, &str); 3] = [
+        (FontChoice::SansSerif, "Sans-serif"),
+        (FontChoice::Serif, "Serif"),
+        (FontChoice::Monospace, "Monospace"),
+    ];
+
+    /// The system's font of this family
+    pub fn font(self) -> Font {
+        match self {
+            FontChoice::SansSerif => Font::DEFAULT,
+            FontChoice::Serif => Font {
+                family: Family::Serif,
+                ..Font::DEFAULT
+            },
+            FontChoice::Monospace => Font::MONOSPACE,
         }
     }
 }
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+
+/// Field the scan results can be ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortKey {
+    Ping,
+    #[default]
+    Ip,
+    Ports,
+}
+
+/// Order of the scan results, picked by clicking a column heading
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ResultSort {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl ResultSort {
+    /// Sort by `key`, flipping the direction if it's already the sort key
+    pub fn toggle(&mut self, key: SortKey) {
+        match self.key == key {
+            true => self.descending = !self.descending,
+            false => {
+                *self = ResultSort {
+                    key,
+                    descending: false,
+                }
+            }
+        }
+    }
+
+    /// Order two results, falling back to their address on a tie
+    pub fn compare(&self, a: &ScannedIp, b: &ScannedIp) -> Ordering {
+        let order = match self.key {
+            SortKey::Ping => a.ping.cmp(&b.ping),
+            SortKey::Ip => Ordering::Equal,
+            SortKey::Ports => a.ports.len().cmp(&b.ports.len()),
+        }
+        .then(a.ip.cmp(&b.ip));
+        let order = match self.descending {
+            true => order.reverse(),
+            false => order,
+        };
+        match self.key {
+            // Hosts that didn't answer have no ping to compare, so they stay last
+            SortKey::Ping => (!a.alive).cmp(&!b.alive).then(order),
+            _ => order,
+        }
+    }
+
+    /// Arrow shown after the heading of the active column
+    pub fn arrow(&self, key: SortKey) -> &'static str {
+        match (self.key == key, self.descending) {
+            (false, _) => "",
+            (true, false) => " \u{25B2}",
+            (true, true) => " \u{25BC}",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
 pub enum ForcedIPMode {
     Any,
     V4,
     V6,
 }
+impl ForcedIPMode {
+    pub const ALL: [(ForcedIPMode, &str); 3] = [
+        (ForcedIPMode::Any, "Any"),
+        (ForcedIPMode::V4, "IPv4"),
+        (ForcedIPMode::V6, "IPv6"),
+    ];
+
+    /// Whether `ip` is of a family this mode permits
+    pub fn allows(&self, ip: &IpAddr) -> bool {
+        match self {
+            ForcedIPMode::Any => true,
+            ForcedIPMode::V4 => ip.is_ipv4(),
+            ForcedIPMode::V6 => ip.is_ipv6(),
+        }
+    }
+}
 impl From<usize> for ForcedIPMode {
     fn from(mode: usize) -> Self {
         match mode {
@@ -250,7 +1493,7 @@ impl From<usize> for ForcedIPMode {
         }
     }
 }
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum ModeTab {
     #[default]
     IpScan,
@@ -262,6 +1505,20 @@ pub enum ModeTab {
 }
 
 /// Basic to string conversion for ModeTab
+impl ModeTab {
+    /// Label that still fits when the tab row is squeezed
+    pub fn short_label(&self) -> &'static str {
+        match self {
+            ModeTab::IpScan => "Scan",
+            ModeTab::TCPclient => "TCP",
+            ModeTab::TCPserver => "TCP Srv",
+            ModeTab::UDPclient => "UDP",
+            ModeTab::UDPserver => "UDP Srv",
+            ModeTab::Settings => "Prefs",
+        }
+    }
+}
+
 impl From<&ModeTab> for String {
     fn from(tab: &ModeTab) -> Self {
         match tab {
@@ -282,6 +1539,428 @@ pub enum ChangeConfig {
     SubnetMask(String),
     Ports(String),
     ForcedIPMode(usize),
+    ResultsRefreshMs(String),
+    PingGoodMs(String),
+    PingWarnMs(String),
+    ToggleTheme,
+    FollowSystemTheme(bool),
+    RawPing(bool),
+    ResultsLayout(ResultsLayout),
+    UiScale(f32),
+    Font(FontChoice),
+    Monitor(bool),
+    MonitorInterval(u64),
+    Scan(ScanConfig),
+    ScanOrder(ScanOrder),
+    ProbeMethod(ProbeMethod),
+    ReportDown(bool),
+    ScanAdapter(String, bool),
+    /// Store the current range and ports under a name
+    SaveFavorite(String),
+    RecallFavorite(String),
+    RemoveFavorite(String),
 }
 
-// Helper function to parse hex color
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_teardown_aborts_connection_tasks() {
+        let (_task, handle) = iced::Task::perform(async {}, |_| Msg::Testing).abortable();
+        let mut conn = ConnectionData {
+            connections: vec![IpAddr::from([127, 0, 0, 1])],
+            ..Default::default()
+        };
+        conn.track(handle.clone());
+        assert!(!handle.is_aborted());
+
+        conn.teardown();
+        assert!(handle.is_aborted());
+        assert!(conn.tasks.is_empty());
+        assert!(conn.connections.is_empty());
+    }
+
+    #[test]
+    fn test_connection_address_and_events() {
+        let mut conn = ConnectionData {
+            ip_address: String::from("10.0.0.9"),
+            ip_port: String::from("0"),
+            ..Default::default()
+        };
+        assert!(conn.socket_addr().is_err());
+        conn.ip_port = String::from("65536");
+        assert!(conn.socket_addr().is_err());
+        conn.ip_port = String::from(" 8080 ");
+        assert_eq!(conn.socket_addr(), Ok("10.0.0.9:8080".parse().unwrap()));
+        conn.ip_address = String::from("10.0.0");
+        assert!(conn.socket_addr().is_err());
+
+        conn.update(Msg::SendPacket);
+        conn.on_event(ConnectionEvent::Sent(b"hello".to_vec()));
+        conn.on_event(ConnectionEvent::Received(b"hi".to_vec()));
+        conn.on_event(ConnectionEvent::Closed);
+        assert_eq!(
+            conn.history,
+            ["Not connected", "> hello", "< hi", "Connection closed"]
+        );
+    }
+
+    #[test]
+    fn test_ports_reject_invalid_tokens() {
+        let parsed = ParsedPorts::parse("80, abc,443,,99999, 0 ");
+        assert_eq!(parsed.ports, [80, 443]);
+        assert_eq!(parsed.rejected, ["abc", "99999", "0"]);
+        assert_eq!(
+            parsed.error().as_deref(),
+            Some(
+                "Ignored abc, 99999, 0: use ports 1-65535 or ranges like 20-25, up to 1024 ports in all"
+            )
+        );
+        assert_eq!(ParsedPorts::parse("22, 8080").error(), None);
+
+        let parsed = ParsedPorts::parse("20-25, 80, 25-20, 1-65535, 0-3, 7 - 8");
+        assert_eq!(parsed.ports, [20, 21, 22, 23, 24, 25, 80, 7, 8]);
+        assert_eq!(parsed.rejected, ["25-20", "1-65535", "0-3"]);
+        assert_eq!(ParsedPorts::parse("1-1024").ports.len(), 1024);
+
+        // Repeats are probed once, and the total is capped
+        let parsed = ParsedPorts::parse("80, 443, 80, 440-445");
+        assert_eq!(parsed.ports, [80, 443, 440, 441, 442, 444, 445]);
+        let parsed = ParsedPorts::parse("1-1000, 2000-2100, 22, 3000-3024, 4000-4023");
+        assert_eq!(parsed.ports.len(), 1024);
+        assert_eq!(parsed.rejected, ["2000-2100", "3000-3024"]);
+        assert!(parsed.error().is_some());
+
+        // The field keeps what was typed while the scan uses the valid ports
+        let mut config = AppConfig::default();
+        assert_eq!(config.ports_field(), "80, 443");
+        config.update(ChangeConfig::Ports(String::from("22, abc")));
+        assert_eq!(config.ports, [22]);
+        assert_eq!(config.ports_field(), "22, abc");
+    }
+
+    #[test]
+    fn test_hex_packets() {
+        let mut conn = ConnectionData {
+            current_packet: String::from("01 zz"),
+            ..Default::default()
+        };
+        assert!(conn.packet().is_ok());
+        conn.update(Msg::PacketEncoding(PacketEncoding::Hex));
+        assert!(conn.packet().is_err());
+        conn.update(Msg::ChangePacket(String::from("01 ff")));
+        assert_eq!(conn.packet(), Ok(vec![0x01, 0xff]));
+
+        conn.on_event(ConnectionEvent::Received(vec![0x01, 0xff]));
+        assert_eq!(conn.history, [format!("< 0000  {:<47}  ..", "01 ff")]);
+    }
+
+    #[test]
+    fn test_insert_sorted_keeps_ips_sorted_and_unique() {
+        let mut ips = Vec::new();
+        for last in [20, 3, 100, 3] {
+            let ip = IpAddr::from([192, 168, 1, last]);
+            insert_sorted(
+                &mut ips,
+                ScannedIp::new(ip, true, u128::from(last), vec![]),
+                &ResultSort::default(),
+            );
+        }
+        // Numeric, not string, order
+        let order: Vec<String> = ips.iter().map(|r| r.ip.to_string()).collect();
+        assert_eq!(order, ["192.168.1.3", "192.168.1.20", "192.168.1.100"]);
+
+        // A rescan replaces the row
+        let ip = IpAddr::from([192, 168, 1, 20]);
+        insert_sorted(
+            &mut ips,
+            ScannedIp::new(ip, false, 0, vec![]),
+            &ResultSort::default(),
+        );
+        assert_eq!(ips.len(), 3);
+        assert!(!ips[1].alive);
+    }
+
+    #[test]
+    fn test_result_sort_toggles_and_keeps_silent_hosts_last() {
+        let host = |last: u8, alive, ping| {
+            ScannedIp::new(IpAddr::from([10, 0, 0, last]), alive, ping, vec![])
+        };
+        let mut app = IpScannerApp::default();
+        app.ips = vec![host(1, true, 30), host(2, false, 0), host(3, true, 5)];
+        let order = |app: &IpScannerApp| -> Vec<u8> {
+            app.ips
+                .iter()
+                .map(|ip| match ip.ip {
+                    IpAddr::V4(ip) => ip.octets()[3],
+                    IpAddr::V6(_) => unreachable!(),
+                })
+                .collect()
+        };
+
+        app.results_sort.toggle(SortKey::Ping);
+        app.sort_results();
+        assert_eq!(order(&app), [3, 1, 2]);
+        assert_eq!(app.results_sort.arrow(SortKey::Ping), " \u{25B2}");
+        assert_eq!(app.results_sort.arrow(SortKey::Ip), "");
+
+        app.results_sort.toggle(SortKey::Ping);
+        app.sort_results();
+        assert_eq!(order(&app), [1, 3, 2]);
+
+        // New results land in place
+        app.record_result(host(4, true, 10));
+        assert_eq!(order(&app), [1, 4, 3, 2]);
+    }
+
+    #[test]
+    fn test_compact_tabs_follow_window_width() {
+        let mut config = AppConfig::default();
+        // The default window is too narrow for the full labels
+        assert!(config.compact_tabs());
+        config.window.resized(600.0, 800.0, 1.0);
+        assert!(!config.compact_tabs());
+
+        // The labels shorten at the same layout width whatever the scale
+        config.ui_scale = 1.5;
+        config.window.resized(600.0, 800.0, 1.5);
+        assert!(!config.compact_tabs());
+        config.window.resized(599.0, 800.0, 1.5);
+        assert!(config.compact_tabs());
+
+        // Scaling up without resizing leaves less room for each label
+        config.ui_scale = 1.0;
+        config.window.resized(800.0, 800.0, 1.0);
+        assert!(!config.compact_tabs());
+        config.ui_scale = 1.5;
+        assert!(config.compact_tabs());
+    }
+
+    #[test]
+    fn test_ui_scale_is_clamped() {
+        let mut config = AppConfig::default();
+        assert_eq!(config.ui_scale(), 1.0);
+        config.update(ChangeConfig::UiScale(1.5));
+        assert_eq!(config.ui_scale(), 1.5);
+        // A hand-edited config can't make the UI vanish
+        config.ui_scale = 0.0;
+        assert_eq!(config.ui_scale(), 0.75);
+        config.ui_scale = 10.0;
+        assert_eq!(config.ui_scale(), 2.5);
+    }
+
+    #[test]
+    fn test_ping_thresholds_fall_back_when_out_of_order() {
+        let colors = SimpleColors::DARK;
+        let mut config = AppConfig::default();
+        config.update(ChangeConfig::PingGoodMs(String::from("20")));
+        config.update(ChangeConfig::PingWarnMs(String::from("80")));
+        let thresholds = config.ping_thresholds();
+        assert_eq!(thresholds.color(19, colors), colors.success_color());
+        assert_eq!(thresholds.color(20, colors), colors.success_color());
+        assert_eq!(thresholds.color(50, colors), colors.warning_color());
+        assert_eq!(thresholds.color(80, colors), colors.danger_color());
+        assert_eq!(thresholds.color(500, colors), colors.danger_color());
+
+        // Mid-edit values that cross over keep the defaults in use
+        config.update(ChangeConfig::PingWarnMs(String::from("8")));
+        assert_eq!(config.ping_thresholds(), PingThresholds::default());
+    }
+
+    #[test]
+    fn test_favorites_save_and_recall() {
+        let mut config = AppConfig::default();
+        config.update(ChangeConfig::StartingIp(String::from("10.0.0.1")));
+        config.update(ChangeConfig::SubnetMask(String::from("16")));
+        config.update(ChangeConfig::SaveFavorite(String::from(" Office ")));
+        config.update(ChangeConfig::StartingIp(String::from("fd00::1")));
+        config.update(ChangeConfig::Ports(String::from("22")));
+        config.ipv6_prefix = 120;
+        config.update(ChangeConfig::SaveFavorite(String::from("Lab")));
+        assert_eq!(config.favorites.len(), 2);
+        assert_eq!(config.favorites[0].name, "Office");
+        assert_eq!(config.favorites[1].subnet_mask, 120);
+
+        config.update(ChangeConfig::RecallFavorite(String::from("Office")));
+        assert_eq!(config.starting_ip, "10.0.0.1");
+        assert_eq!(config.subnet_mask, 16);
+        assert_eq!(config.ports, vec![80, 443]);
+
+        // Saving under a used name replaces it
+        config.update(ChangeConfig::SaveFavorite(String::from("Lab")));
+        assert_eq!(config.favorites.len(), 2);
+        assert_eq!(config.favorites[1].starting_ip, "10.0.0.1");
+
+        config.update(ChangeConfig::RemoveFavorite(String::from("Office")));
+        assert_eq!(config.favorites.len(), 1);
+    }
+
+    #[test]
+    fn test_cached_system_theme_used_while_following() {
+        let mut app = IpScannerApp::default();
+        app.system_theme = ThemeProvider::fallback(SimpleColors::LIGHT);
+        assert_eq!(app.theme_provider().colors(), SimpleColors::LIGHT);
+
+        app.config.follow_system_theme = false;
+        assert_eq!(app.theme_provider().colors(), SimpleColors::DARK);
+    }
+
+    #[test]
+    fn test_window_geometry_ignores_minimized() {
+        let mut window = WindowGeometry::default();
+        assert!(window.resized(1200.0, 900.0, 1.0));
+        assert!(window.moved(-1920.0, 40.0, 1.0));
+        // What a minimized window reports on Windows
+        assert!(!window.resized(0.0, 0.0, 1.0));
+        assert!(!window.moved(-32000.0, -32000.0, 1.0));
+        assert_eq!(
+            window,
+            WindowGeometry {
+                width: 1200.0,
+                height: 900.0,
+                position: Some((-1920.0, 40.0)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_window_geometry_round_trips_at_scale() {
+        let mut window = WindowGeometry::default();
+        assert!(window.resized(600.0, 450.0, 2.0));
+        assert!(window.moved(50.0, 20.0, 2.0));
+        let saved = window;
+        assert_eq!((saved.width, saved.height), (1200.0, 900.0));
+
+        // Reopened at the saved size, iced reports it divided by the scale again
+        for _ in 0..3 {
+            window.resized(saved.width / 2.0, saved.height / 2.0, 2.0);
+            let (x, y) = saved.position.unwrap();
+            window.moved(x / 2.0, y / 2.0, 2.0);
+            assert_eq!(window, saved);
+        }
+    }
+
+    #[test]
+    fn test_last_tab_survives_reload() {
+        let config = AppConfig {
+            last_tab: ModeTab::TCPclient,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        let reloaded: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded.last_tab, ModeTab::TCPclient);
+
+        // Configs saved before the tab was remembered open on the scan tab
+        let old: AppConfig = serde_json::from_str(r#"{"starting_ip": "10.0.0.1"}"#).unwrap();
+        assert_eq!(old.last_tab, ModeTab::IpScan);
+    }
+
+    #[test]
+    fn test_old_config_keeps_saved_theme() {
+        let old: AppConfig =
+            serde_json::from_str(r#"{"starting_ip": "10.0.0.1", "dark_theme": false}"#).unwrap();
+        assert!(!old.follow_system_theme);
+        assert!(!old.is_dark());
+        assert!(AppConfig::default().follow_system_theme);
+    }
+
+    #[test]
+    fn test_theme_choice_survives_reload() {
+        let reload = |config: &AppConfig| -> AppConfig {
+            serde_json::from_str(&serde_json::to_string(config).unwrap()).unwrap()
+        };
+
+        let mut config = AppConfig {
+            follow_system_theme: false,
+            ..Default::default()
+        };
+        config.update(ChangeConfig::ToggleTheme);
+        assert_eq!(
+            reload(&config).theme_provider().colors(),
+            SimpleColors::LIGHT
+        );
+
+        // Toggling while following the OS switches to the opposite of it
+        config = AppConfig::default();
+        let system_dark = config.is_dark();
+        config.update(ChangeConfig::ToggleTheme);
+        assert!(!config.follow_system_theme);
+        assert_eq!(reload(&config).is_dark(), !system_dark);
+
+        config.custom_theme = Some(ThemeDefinition {
+            name: String::from("Night Shift"),
+            colors: SimpleColors::from_accent([0.5, 0.2, 0.9, 1.0], true),
+        });
+        let reloaded = reload(&config);
+        assert_eq!(reloaded.active_theme(), config.active_theme());
+        assert_eq!(
+            reloaded.theme_provider().colors(),
+            config.theme_provider().colors()
+        );
+    }
+
+    #[test]
+    fn test_scan_range_respects_forced_ip_mode() {
+        let adapters = vec![
+            NetworkAdapter {
+                ip_address: String::from("10.0.0.5"),
+                prefix: 16,
+                ..Default::default()
+            },
+            NetworkAdapter {
+                ip_address: String::from("fd00::5"),
+                prefix: 64,
+                ..Default::default()
+            },
+        ];
+        let mut config = AppConfig::default();
+        let starting = (IpAddr::from([192, 168, 1, 1]), 24);
+        assert_eq!(config.scan_range(&adapters), Some(starting));
+
+        config.forced_ip_mode = ForcedIPMode::V4;
+        assert_eq!(config.scan_range(&adapters), Some(starting));
+
+        let allowed = |config: &AppConfig| {
+            config
+                .allowed_adapters(&adapters)
+                .map(|a| a.ip_address.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(allowed(&config), ["10.0.0.5"]);
+
+        // An IPv4 starting IP falls back to the first IPv6 adapter
+        config.forced_ip_mode = ForcedIPMode::V6;
+        assert_eq!(allowed(&config), ["fd00::5"]);
+        let v6 = "fd00::5".parse().unwrap();
+        assert_eq!(config.scan_range(&adapters), Some((v6, 64)));
+        assert_eq!(config.scan_range(&[]), None);
+
+        config.starting_ip = String::from("fd00:1::1");
+        let starting_v6 = "fd00:1::1".parse().unwrap();
+        assert_eq!(
+            config.scan_range(&adapters),
+            Some((starting_v6, IPV6_SCAN_PREFIX))
+        );
+
+        // The slider edits the IPv6 prefix while IPv6 is forced
+        config.update(ChangeConfig::SubnetMask(String::from("112")));
+        assert_eq!(config.scan_range(&adapters), Some((starting_v6, 112)));
+        assert_eq!(config.subnet_mask, 24);
+    }
+
+    #[test]
+    fn test_starting_ip_valid() {
+        let mut config = AppConfig::default();
+        assert!(config.starting_ip_valid());
+        for (ip, valid) in [
+            (" fd00::1 ", true),
+            ("", true),
+            ("192.168.1.", false),
+            ("192.168.1.256", false),
+        ] {
+            config.starting_ip = String::from(ip);
+            assert_eq!(config.starting_ip_valid(), valid, "{ip:?}");
+        }
+    }
+}
@@ -1,14 +1,16 @@
 use iced::Alignment::Center;
+use iced::Font;
 use iced::Length::{Fill, FillPortion};
-use iced::widget::{Column, button, column, row, scrollable, text, text_input};
+use iced::widget::{Column, button, checkbox, column, row, scrollable, text, text_input};
 use net_monkey_components::TextInputDropdown;
+use net_monkey_core::PacketEncoding;
 
 use crate::Msg;
 use crate::views::settings::IpScannerApp;
 use net_monkey_theme::helpers;
 
 pub fn view<'a>(app: &'a IpScannerApp) -> Column<'a, Msg> {
-    let theme_colors = app.config.theme_provider().colors();
+    let theme_colors = app.theme_provider().colors();
 
     let (connected_text, connected_color) = match app.tcp_client.connections.is_empty() {
         true => ("Connect", theme_colors.primary_color()),
@@ -26,17 +28,14 @@ pub fn view<'a>(app: &'a IpScannerApp) -> Column<'a, Msg> {
         .collect::<Vec<String>>();
     let ip_sel: TextInputDropdown<_, _, Msg, iced::Theme> = TextInputDropdown::new(
         items,
-        app.tcp_client
-            .connections
-            .first()
-            .map_or_default(|c| c.to_string()),
+        app.tcp_client.ip_address.clone(),
         Msg::ChangeIpAddress,
         Msg::ChangeIpAddress,
     );
     // Create themed connection controls container
     let connection_controls = helpers::themed_container(
         row![
-            ip_sel.text_size(24),
+            ip_sel.text_size(24).font(Font::MONOSPACE),
             row![
                 text_input("Port", &app.tcp_client.ip_port)
                     .on_input(Msg::ChangeIpPort)
@@ -55,27 +54,51 @@ pub fn view<'a>(app: &'a IpScannerApp) -> Column<'a, Msg> {
         .align_y(Center)
         .spacing(15)
         .width(Fill),
-        &app.config.theme_provider(),
+        &app.theme_provider(),
     );
 
     // Create themed history container with scrollable content
     let history_container = helpers::sub_menu_container(
-        scrollable(text(history).color(theme_colors.text_color()).width(Fill))
-            .height(Fill)
-            .width(Fill),
-        &app.config.theme_provider(),
+        scrollable(
+            text(history)
+                .font(Font::MONOSPACE)
+                .color(theme_colors.text_color())
+                .width(Fill),
+        )
+        .height(Fill)
+        .width(Fill),
+        &app.theme_provider(),
     )
     .height(Fill);
 
+    let hex = app.tcp_client.encoding == PacketEncoding::Hex;
+    // Invalid hex is outlined and can't be sent
+    let packet = app.tcp_client.packet().ok();
+    let invalid = packet.is_none();
+    let danger = theme_colors.danger_color();
     let packet_sending = helpers::themed_container(
         row![
+            checkbox("Hex", hex)
+                .on_toggle(|hex| Msg::PacketEncoding(match hex {
+                    true => PacketEncoding::Hex,
+                    false => PacketEncoding::Ascii,
+                }))
+                .text_size(24),
             text_input("Message to socket", &app.tcp_client.current_packet)
                 .on_input(Msg::ChangePacket)
+                .font(Font::MONOSPACE)
                 .size(24)
                 .width(FillPortion(3))
-                .padding(8),
+                .padding(8)
+                .style(move |theme, status| {
+                    let mut style = text_input::default(theme, status);
+                    if invalid {
+                        style.border.color = danger;
+                    }
+                    style
+                }),
             button(text("Send Packet").size(24))
-                .on_press(Msg::SendPacket)
+                .on_press_maybe(packet.map(|_| Msg::SendPacket))
                 .width(FillPortion(1))
                 .height(Fill)
                 .padding(8),
@@ -83,7 +106,7 @@ pub fn view<'a>(app: &'a IpScannerApp) -> Column<'a, Msg> {
         .align_y(Center)
         .spacing(15)
         .width(Fill),
-        &app.config.theme_provider(),
+        &app.theme_provider(),
     );
 
     column![connection_controls, history_container, packet_sending]
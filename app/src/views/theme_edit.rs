@@ -0,0 +1,797 @@
+use std::collections::{HashMap, VecDeque};
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use iced::Alignment::Center;
+use iced::Length::{Fill, Fixed};
+use iced::widget::{Column, Row, button, column, container, row, slider, stack, text, text_input};
+use iced::{Background, Border, Color, Element};
+use net_monkey_theme::helpers::{self, ButtonVariant};
+use net_monkey_theme::{
+    Hsl, MIN_TEXT_CONTRAST, SimpleColors, ThemeDefinition, ThemeDraft, ThemeManager,
+};
+
+use crate::Msg;
+use crate::keybindings::KeyCombo;
+use crate::views::settings::IpScannerApp;
+
+/// Editable color fields of a theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorType {
+    Background,
+    Text,
+    Primary,
+    Success,
+    Warning,
+    Danger,
+}
+
+impl ColorType {
+    pub const ALL: &[ColorType] = &[
+        ColorType::Background,
+        ColorType::Text,
+        ColorType::Primary,
+        ColorType::Success,
+        ColorType::Warning,
+        ColorType::Danger,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorType::Background => "Background",
+            ColorType::Text => "Text",
+            ColorType::Primary => "Primary",
+            ColorType::Success => "Success",
+            ColorType::Warning => "Warning",
+            ColorType::Danger => "Danger",
+        }
+    }
+
+    fn get(&self, colors: &SimpleColors) -> [f32; 4] {
+        match self {
+            ColorType::Background => colors.background,
+            ColorType::Text => colors.text,
+            ColorType::Primary => colors.primary,
+            ColorType::Success => colors.success,
+            ColorType::Warning => colors.warning,
+            ColorType::Danger => colors.danger,
+        }
+    }
+
+    fn get_mut<'a>(&self, colors: &'a mut SimpleColors) -> &'a mut [f32; 4] {
+        match self {
+            ColorType::Background => &mut colors.background,
+            ColorType::Text => &mut colors.text,
+            ColorType::Primary => &mut colors.primary,
+            ColorType::Success => &mut colors.success,
+            ColorType::Warning => &mut colors.warning,
+            ColorType::Danger => &mut colors.danger,
+        }
+    }
+}
+
+/// Most edits that can be undone, dropping the oldest past that
+pub const UNDO_LIMIT: usize = 50;
+
+/// State of the theme editor in settings
+#[derive(Debug, Default)]
+pub struct ThemeEditor {
+    /// Working copy while editing, mirrored to disk as a draft
+    pub draft: Option<ThemeDraft>,
+    /// Colors of the theme the draft started from, for resetting one color
+    pub base_colors: Option<SimpleColors>,
+    /// Colors before each edit, newest last
+    pub undo: VecDeque<SimpleColors>,
+    /// Colors undone since the last edit, newest last
+    pub redo: Vec<SimpleColors>,
+    /// Set while a slider is held, so one drag is one undo step
+    dragging: bool,
+    /// Raw text of each hex field so partial input isn't thrown away
+    pub hex_inputs: HashMap<ColorType, String>,
+    /// Slider positions, kept so hue survives dragging through gray
+    pub hsl_inputs: HashMap<ColorType, Hsl>,
+    /// Name the theme will be saved under
+    pub name: String,
+    /// Draft left by a previous session, waiting to be resumed or discarded
+    pub recovered: Option<ThemeDraft>,
+    /// Themes that can be used as a starting point
+    pub themes: Vec<String>,
+    /// File a theme is imported from or exported to
+    pub file_path: String,
+    /// Outcome of the last import, export or failed save, shown as a banner
+    pub status: Option<Result<String, String>>,
+}
+
+impl ThemeEditor {
+    /// Pick up the theme list and any draft left from the last session
+    pub fn load(&mut self, manager: &ThemeManager) {
+        self.themes = manager.available_themes();
+        self.recovered = manager.load_draft();
+    }
+
+    fn start(&mut self, draft: ThemeDraft, base_colors: Option<SimpleColors>) {
+        self.name = format!("{} Custom", draft.base_theme);
+        self.base_colors = base_colors;
+        self.draft = Some(draft);
+        self.undo.clear();
+        self.redo.clear();
+        self.sync_inputs();
+        self.recovered = None;
+    }
+
+    fn close(&mut self, manager: &ThemeManager) {
+        if let Err(e) = manager.discard_draft() {
+            eprintln!("Failed to remove theme draft: {e}");
+        }
+        self.draft = None;
+        self.base_colors = None;
+        self.undo.clear();
+        self.redo.clear();
+        self.recovered = None;
+        self.hex_inputs.clear();
+        self.hsl_inputs.clear();
+    }
+
+    /// Refill the hex fields from the draft after its colors were replaced
+    fn sync_inputs(&mut self) {
+        let Some(draft) = &self.draft else {
+            return;
+        };
+        self.hex_inputs = ColorType::ALL
+            .iter()
+            .map(|color_type| (*color_type, color_to_hex(color_type.get(&draft.colors))))
+            .collect();
+        self.hsl_inputs.clear();
+    }
+
+    /// Draft colors about to be changed, remembering them for undo
+    fn edit(&mut self) -> Option<&mut SimpleColors> {
+        let draft = self.draft.as_mut()?;
+        self.undo.push_back(draft.colors);
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+        Some(&mut draft.colors)
+    }
+
+    /// Like [`Self::edit`], but only the first step of a slider drag is remembered
+    fn drag(&mut self) -> Option<&mut SimpleColors> {
+        match std::mem::replace(&mut self.dragging, true) {
+            true => self.draft.as_mut().map(|draft| &mut draft.colors),
+            false => self.edit(),
+        }
+    }
+
+    fn save_draft(&self, manager: &ThemeManager) {
+        if let Some(draft) = &self.draft
+            && let Err(e) = manager.save_draft(draft)
+        {
+            eprintln!("Failed to save theme draft: {e}");
+        }
+    }
+
+    /// Handle an editor message, returning the theme to apply when one is saved
+    pub fn update(&mut self, msg: Msg, manager: &ThemeManager) -> Option<ThemeDefinition> {
+        match msg {
+            Msg::EditTheme(base) => match manager.load_theme(&base) {
+                Some(theme) => {
+                    self.start(
+                        ThemeDraft {
+                            base_theme: theme.name,
+                            colors: theme.colors,
+                        },
+                        Some(theme.colors),
+                    );
+                    self.save_draft(manager);
+                }
+                None => eprintln!("Theme {base} not found"),
+            },
+            Msg::ResumeThemeDraft => {
+                if let Some(draft) = self.recovered.take() {
+                    // The base may have been deleted since, leaving nothing to reset to
+                    let base_colors = manager.load_theme(&draft.base_theme).map(|t| t.colors);
+                    self.start(draft, base_colors);
+                }
+            }
+            Msg::ColorEdit {
+                color_type,
+                hex_value,
+            } => {
+                if let Some(color) = hex_to_color(&hex_value)
+                    && let Some(colors) = self.edit()
+                {
+                    *color_type.get_mut(colors) = color;
+                    self.hsl_inputs.remove(&color_type);
+                    self.save_draft(manager);
+                }
+                self.hex_inputs.insert(color_type, hex_value);
+            }
+            // The draft is written once the slider is released, not on every step
+            Msg::HslEdit { color_type, hsl } => {
+                if let Some(colors) = self.drag() {
+                    let color = color_type.get_mut(colors);
+                    *color = hsl.to_rgba(color[3]);
+                    let hex = color_to_hex(*color);
+                    self.hex_inputs.insert(color_type, hex);
+                    self.hsl_inputs.insert(color_type, hsl);
+                }
+            }
+            Msg::AlphaEdit { color_type, alpha } => {
+                if let Some(colors) = self.drag() {
+                    let color = color_type.get_mut(colors);
+                    color[3] = alpha;
+                    let hex = color_to_hex(*color);
+                    self.hex_inputs.insert(color_type, hex);
+                }
+            }
+            Msg::ResetColor(color_type) => {
+                let base = color_type.get(self.base_colors.as_ref()?);
+                *color_type.get_mut(self.edit()?) = base;
+                self.hex_inputs.insert(color_type, color_to_hex(base));
+                self.hsl_inputs.remove(&color_type);
+                self.save_draft(manager);
+            }
+            Msg::HslReleased => {
+                self.dragging = false;
+                self.save_draft(manager);
+            }
+            Msg::UndoThemeEdit => {
+                let previous = self.undo.pop_back()?;
+                let draft = self.draft.as_mut()?;
+                self.redo
+                    .push(std::mem::replace(&mut draft.colors, previous));
+                self.sync_inputs();
+                self.save_draft(manager);
+            }
+            Msg::RedoThemeEdit => {
+                let next = self.redo.pop()?;
+                let draft = self.draft.as_mut()?;
+                self.undo
+                    .push_back(std::mem::replace(&mut draft.colors, next));
+                self.sync_inputs();
+                self.save_draft(manager);
+            }
+            Msg::GenerateFromAccent => {
+                let colors = self.edit()?;
+                *colors = SimpleColors::from_accent(colors.primary, colors.is_dark());
+                self.sync_inputs();
+                self.save_draft(manager);
+            }
+            Msg::ThemeName(name) => self.name = name,
+            Msg::SaveTheme => {
+                let theme = ThemeDefinition {
+                    name: self.name.trim().to_string(),
+                    colors: self.draft.as_ref()?.colors,
+                };
+                if let Err(e) = manager.save_theme(&theme) {
+                    self.status = Some(Err(format!("Couldn't save {}: {e}", theme.name)));
+                    return None;
+                }
+                self.close(manager);
+                self.themes = manager.available_themes();
+                self.status = Some(Ok(format!("Saved {}", theme.name)));
+                return Some(theme);
+            }
+            Msg::DiscardTheme => self.close(manager),
+            Msg::ThemeFilePath(path) => self.file_path = path,
+            Msg::ImportTheme => {
+                self.status = Some(
+                    match manager.import_theme(Path::new(self.file_path.trim())) {
+                        Ok(theme) => {
+                            self.themes = manager.available_themes();
+                            Ok(format!("Imported {}", theme.name))
+                        }
+                        Err(e) => Err(format!("Couldn't import {}: {e}", self.file_path.trim())),
+                    },
+                );
+            }
+            Msg::ExportTheme(theme) => {
+                self.status = Some(match theme.export(Path::new(self.file_path.trim())) {
+                    Ok(()) => Ok(format!(
+                        "Exported {} to {}",
+                        theme.name,
+                        self.file_path.trim()
+                    )),
+                    Err(e) => Err(format!("Couldn't export {}: {e}", theme.name)),
+                });
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Ctrl+Z undoes a color edit, Ctrl+Y or Ctrl+Shift+Z redoes it
+pub fn shortcut(combo: &KeyCombo) -> Option<Msg> {
+    if !combo.ctrl || combo.alt || combo.logo {
+        return None;
+    }
+    match (combo.key.as_str(), combo.shift) {
+        ("z", false) => Some(Msg::UndoThemeEdit),
+        ("y", false) | ("z", true) => Some(Msg::RedoThemeEdit),
+        _ => None,
+    }
+}
+
+/// Parse `#RGB`, `#RRGGBB` or `#RRGGBBAA` into an RGBA color
+///
+/// The shorthand form expands each digit, so `#f80` is `#ff8800`. Colors
+/// without an alpha channel are opaque.
+pub fn hex_to_color(hex: &str) -> Option<[f32; 4]> {
+    let hex = hex.trim().strip_prefix('#')?;
+    let digits = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    let channels: Vec<u8> = match digits.len() {
+        3 => digits.iter().map(|d| d * 17).collect(),
+        6 | 8 => digits
+            .chunks(2)
+            .map(|pair| pair[0] * 16 + pair[1])
+            .collect(),
+        _ => return None,
+    };
+    let channel = |i: usize| channels.get(i).map_or(1.0, |v| *v as f32 / 255.0);
+    Some([channel(0), channel(1), channel(2), channel(3)])
+}
+
+/// Format a color as `#RRGGBB`, or `#RRGGBBAA` when it isn't opaque
+pub fn color_to_hex(color: [f32; 4]) -> String {
+    let [r, g, b, a] = color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+    match a {
+        255 => format!("#{r:02x}{g:02x}{b:02x}"),
+        _ => format!("#{r:02x}{g:02x}{b:02x}{a:02x}"),
+    }
+}
+
+/// Color preview drawn over a checkerboard, so transparency shows through
+fn swatch<'a>(color: [f32; 4], border: Color) -> Element<'a, Msg> {
+    const CELLS: u16 = 3;
+    let checker = Column::with_children((0..CELLS).map(|y| {
+        Row::with_children((0..CELLS).map(|x| {
+            let shade = match (x + y) % 2 {
+                0 => Color::from_rgb(0.8, 0.8, 0.8),
+                _ => Color::from_rgb(0.55, 0.55, 0.55),
+            };
+            container(text(""))
+                .width(Fixed(8.0))
+                .height(Fixed(8.0))
+                .style(move |_| container::Style {
+                    background: Some(Background::Color(shade)),
+                    ..Default::default()
+                })
+                .into()
+        }))
+        .into()
+    }));
+    let fill = container(text(""))
+        .width(Fixed(24.0))
+        .height(Fixed(24.0))
+        .style(move |_| container::Style {
+            background: Some(Background::Color(Color::from(color))),
+            border: Border {
+                color: border,
+                width: 1.0,
+                radius: 4.0.into(),
+            },
+            ..Default::default()
+        });
+    stack![checker, fill].into()
+}
+
+/// Path field with import/export buttons, plus the result of the last attempt
+///
+/// While editing, the draft is exported under its current name; otherwise the
+/// active theme is.
+fn file_transfer(app: &IpScannerApp) -> Element<'_, Msg> {
+    let editor = &app.theme_editor;
+    let theme_provider = app.theme_provider();
+    let export = match &editor.draft {
+        Some(draft) => ThemeDefinition {
+            name: editor.name.trim().to_string(),
+            colors: draft.colors,
+        },
+        None => app.config.active_theme(),
+    };
+    let has_path = !editor.file_path.trim().is_empty();
+
+    let mut col = column![
+        row![
+            text_input("Theme file path", &editor.file_path)
+                .on_input(Msg::ThemeFilePath)
+                .size(16),
+            button(text("Import"))
+                .style(helpers::themed_button(
+                    ButtonVariant::Secondary,
+                    &theme_provider
+                ))
+                .on_press_maybe(has_path.then_some(Msg::ImportTheme)),
+            button(text("Export"))
+                .style(helpers::themed_button(
+                    ButtonVariant::Secondary,
+                    &theme_provider
+                ))
+                .on_press_maybe(has_path.then_some(Msg::ExportTheme(export))),
+        ]
+        .align_y(Center)
+        .spacing(8)
+    ]
+    .spacing(6);
+    if let Some(status) = &editor.status {
+        let (message, color) = match status {
+            Ok(message) => (message, theme_provider.colors().success_color()),
+            Err(message) => (message, theme_provider.colors().danger_color()),
+        };
+        col = col.push(text(message.as_str()).size(14).color(color));
+    }
+    col.into()
+}
+
+pub fn view(app: &IpScannerApp) -> Element<'_, Msg> {
+    let editor = &app.theme_editor;
+    let theme_provider = app.theme_provider();
+
+    let Some(draft) = &editor.draft else {
+        let bases = Row::with_children(editor.themes.iter().map(|name| {
+            button(text(name.as_str()).size(16))
+                .style(helpers::themed_button(
+                    ButtonVariant::Secondary,
+                    &theme_provider,
+                ))
+                .on_press(Msg::EditTheme(name.clone()))
+                .into()
+        }))
+        .spacing(8);
+
+        let mut col = Column::new().spacing(8);
+        if let Some(recovered) = &editor.recovered {
+            col = col.push(
+                row![
+                    text(format!(
+                        "Unsaved edits to {} were found",
+                        recovered.base_theme
+                    ))
+                    .size(16)
+                    .width(Fill),
+                    button(text("Resume"))
+                        .style(helpers::themed_button(
+                            ButtonVariant::Primary,
+                            &theme_provider
+                        ))
+                        .on_press(Msg::ResumeThemeDraft),
+                    button(text("Discard"))
+                        .style(helpers::themed_button(
+                            ButtonVariant::Danger,
+                            &theme_provider
+                        ))
+                        .on_press(Msg::DiscardTheme),
+                ]
+                .align_y(Center)
+                .spacing(8),
+            );
+        }
+        return col
+            .push(text("Edit a copy of").size(18))
+            .push(bases)
+            .push(file_transfer(app))
+            .into();
+    };
+
+    let border = theme_provider.colors().border_color();
+    let danger = theme_provider.colors().danger_color();
+    let rows = ColorType::ALL.iter().map(|color_type| {
+        let color = color_type.get(&draft.colors);
+        let hex = editor
+            .hex_inputs
+            .get(color_type)
+            .cloned()
+            .unwrap_or_else(|| color_to_hex(color));
+        // Unparseable input keeps the last good color and is outlined until fixed
+        let invalid = hex_to_color(&hex).is_none();
+        let changed = editor
+            .base_colors
+            .is_some_and(|base| color_type.get(&base) != color);
+        let hsl = editor
+            .hsl_inputs
+            .get(color_type)
+            .copied()
+            .unwrap_or_else(|| Hsl::from_rgba(color));
+        let color_type = *color_type;
+        let channel = |label: &'static str,
+                       range: RangeInclusive<f32>,
+                       value: f32,
+                       step: f32,
+                       to_hsl: fn(Hsl, f32) -> Hsl| {
+            row![
+                text(label).size(14),
+                slider(range, value, move |value| Msg::HslEdit {
+                    color_type,
+                    hsl: to_hsl(hsl, value),
+                })
+                .step(step)
+                .on_release(Msg::HslReleased),
+            ]
+            .align_y(Center)
+            .spacing(4)
+        };
+        let sliders = row![
+            channel("H", 0.0..=360.0, hsl.h, 1.0, |hsl, h| Hsl { h, ..hsl }),
+            channel("S", 0.0..=1.0, hsl.s, 0.01, |hsl, s| Hsl { s, ..hsl }),
+            channel("L", 0.0..=1.0, hsl.l, 0.01, |hsl, l| Hsl { l, ..hsl }),
+            row![
+                text("A").size(14),
+                slider(0.0..=1.0, color[3], move |alpha| Msg::AlphaEdit {
+                    color_type,
+                    alpha,
+                })
+                .step(0.01f32)
+                .on_release(Msg::HslReleased),
+            ]
+            .align_y(Center)
+            .spacing(4),
+        ]
+        .spacing(12);
+        let fields = row![
+            text(color_type.label()).size(16).width(Fill),
+            swatch(color, border),
+            text_input("#RRGGBB[AA]", &hex)
+                .on_input(move |hex_value| Msg::ColorEdit {
+                    color_type,
+                    hex_value,
+                })
+                .width(Fixed(110.0))
+                .size(16)
+                .style(move |theme, status| {
+                    let mut style = text_input::default(theme, status);
+                    if invalid {
+                        style.border.color = danger;
+                    }
+                    style
+                }),
+            // Only offered once the color differs from the base theme's
+            button(text("\u{21BA}").size(16))
+                .padding([2, 6])
+                .style(helpers::themed_button(ButtonVariant::Text, &theme_provider))
+                .on_press_maybe(changed.then_some(Msg::ResetColor(color_type))),
+        ]
+        .align_y(Center)
+        .spacing(8);
+        column![fields, sliders].spacing(4).into()
+    });
+
+    // Flag unreadable combinations while they're being picked
+    let contrast = draft.colors.text_on_background_ratio();
+    let contrast_color = match contrast < MIN_TEXT_CONTRAST {
+        true => theme_provider.colors().danger_color(),
+        false => theme_provider.colors().text_color(),
+    };
+
+    column![
+        text(format!("Editing a copy of {}", draft.base_theme)).size(18),
+        text_input("Theme name", &editor.name)
+            .on_input(Msg::ThemeName)
+            .size(16),
+        Column::with_children(rows).spacing(6),
+        row![
+            container(
+                text("Sample text")
+                    .size(16)
+                    .color(Color::from(draft.colors.text))
+            )
+            .padding(6)
+            .style(move |_| container::Style {
+                background: Some(Background::Color(Color::from(draft.colors.background))),
+                ..Default::default()
+            }),
+            text(format!("Contrast {contrast:.1}:1"))
+                .size(16)
+                .color(contrast_color),
+        ]
+        .align_y(Center)
+        .spacing(8),
+        row![
+            button(text("Save"))
+                .style(helpers::themed_button(
+                    ButtonVariant::Primary,
+                    &theme_provider
+                ))
+                .on_press_maybe((!editor.name.trim().is_empty()).then_some(Msg::SaveTheme)),
+            button(text("Discard"))
+                .style(helpers::themed_button(
+                    ButtonVariant::Danger,
+                    &theme_provider
+                ))
+                .on_press(Msg::DiscardTheme),
+            // Rebuilds the other colors around Primary, keeping dark or light
+            button(text("Generate from accent"))
+                .style(helpers::themed_button(
+                    ButtonVariant::Secondary,
+                    &theme_provider
+                ))
+                .on_press(Msg::GenerateFromAccent),
+        ]
+        .spacing(8),
+        file_transfer(app),
+    ]
+    .spacing(8)
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A manager over an empty directory, so a draft left by an earlier run
+    /// isn't picked up
+    fn temp_manager(name: &str) -> ThemeManager {
+        let dir = std::env::temp_dir().join(format!("net_monkey_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        ThemeManager::new(dir)
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let color = hex_to_color("#3366ff").unwrap();
+        assert_eq!(color_to_hex(color), "#3366ff");
+        assert_eq!(hex_to_color("3366ff"), None);
+        assert_eq!(hex_to_color("#zz66ff"), None);
+        assert_eq!(hex_to_color("#3366f"), None);
+        assert_eq!(hex_to_color("#3366ff8"), None);
+    }
+
+    #[test]
+    fn test_hex_short_and_alpha_forms() {
+        assert_eq!(hex_to_color("#fff"), Some([1.0, 1.0, 1.0, 1.0]));
+        assert_eq!(hex_to_color("#ffffff"), Some([1.0, 1.0, 1.0, 1.0]));
+        assert_eq!(
+            hex_to_color("#ffffff80"),
+            Some([1.0, 1.0, 1.0, 128.0 / 255.0])
+        );
+        assert_eq!(hex_to_color("#36f"), hex_to_color("#3366ff"));
+
+        let translucent = hex_to_color("#ffffff80").unwrap();
+        assert_eq!(color_to_hex(translucent), "#ffffff80");
+    }
+
+    #[test]
+    fn test_invalid_hex_keeps_color() {
+        let manager = temp_manager("edit");
+        let mut editor = ThemeEditor::default();
+        editor.update(Msg::EditTheme(String::from("Dark")), &manager);
+
+        let edit = |hex_value: &str| Msg::ColorEdit {
+            color_type: ColorType::Primary,
+            hex_value: hex_value.to_string(),
+        };
+        editor.update(edit("#102030"), &manager);
+        let applied = editor.draft.as_ref().unwrap().colors.primary;
+        assert_eq!(color_to_hex(applied), "#102030");
+
+        // The bad text is kept for the user to fix, the color is not
+        editor.update(edit("#1020"), &manager);
+        assert_eq!(editor.draft.as_ref().unwrap().colors.primary, applied);
+        assert_eq!(editor.hex_inputs[&ColorType::Primary], "#1020");
+        // The draft on disk has the last good color
+        assert_eq!(manager.load_draft().unwrap().colors.primary, applied);
+
+        editor.update(Msg::DiscardTheme, &manager);
+        let _ = std::fs::remove_dir_all(manager.dir());
+    }
+
+    #[test]
+    fn test_reset_color_restores_one_field() {
+        let manager = temp_manager("reset");
+        let mut editor = ThemeEditor::default();
+        editor.update(Msg::EditTheme(String::from("Dark")), &manager);
+        let base = editor.draft.as_ref().unwrap().colors;
+
+        for color_type in [ColorType::Primary, ColorType::Danger] {
+            editor.update(
+                Msg::ColorEdit {
+                    color_type,
+                    hex_value: String::from("#102030"),
+                },
+                &manager,
+            );
+        }
+        editor.update(Msg::ResetColor(ColorType::Primary), &manager);
+
+        let colors = editor.draft.as_ref().unwrap().colors;
+        assert_eq!(colors.primary, base.primary);
+        assert_eq!(color_to_hex(colors.danger), "#102030");
+        assert_eq!(
+            editor.hex_inputs[&ColorType::Primary],
+            color_to_hex(base.primary)
+        );
+
+        editor.update(Msg::DiscardTheme, &manager);
+        let _ = std::fs::remove_dir_all(manager.dir());
+    }
+
+    #[test]
+    fn test_undo_redo_color_edits() {
+        let manager = temp_manager("undo");
+        let mut editor = ThemeEditor::default();
+        editor.update(Msg::EditTheme(String::from("Dark")), &manager);
+        let original = editor.draft.as_ref().unwrap().colors.primary;
+        let primary =
+            |editor: &ThemeEditor| color_to_hex(editor.draft.as_ref().unwrap().colors.primary);
+
+        for hex in ["#102030", "#405060"] {
+            editor.update(
+                Msg::ColorEdit {
+                    color_type: ColorType::Primary,
+                    hex_value: hex.to_string(),
+                },
+                &manager,
+            );
+        }
+        // A whole slider drag is a single step
+        for alpha in [0.9, 0.5] {
+            editor.update(
+                Msg::AlphaEdit {
+                    color_type: ColorType::Primary,
+                    alpha,
+                },
+                &manager,
+            );
+        }
+        editor.update(Msg::HslReleased, &manager);
+        assert_eq!(primary(&editor), "#40506080");
+
+        editor.update(Msg::UndoThemeEdit, &manager);
+        assert_eq!(primary(&editor), "#405060");
+        assert_eq!(editor.hex_inputs[&ColorType::Primary], "#405060");
+        editor.update(Msg::UndoThemeEdit, &manager);
+        editor.update(Msg::UndoThemeEdit, &manager);
+        assert_eq!(editor.draft.as_ref().unwrap().colors.primary, original);
+        // Nothing left to undo
+        editor.update(Msg::UndoThemeEdit, &manager);
+        assert_eq!(editor.draft.as_ref().unwrap().colors.primary, original);
+
+        editor.update(Msg::RedoThemeEdit, &manager);
+        assert_eq!(primary(&editor), "#102030");
+        // A new edit drops what was left to redo
+        editor.update(Msg::ResetColor(ColorType::Primary), &manager);
+        assert!(editor.redo.is_empty());
+
+        assert_eq!(
+            shortcut(&KeyCombo::new("z").ctrl().shift()).map(|msg| format!("{msg:?}")),
+            Some(String::from("RedoThemeEdit"))
+        );
+        assert!(shortcut(&KeyCombo::new("z")).is_none());
+
+        editor.update(Msg::DiscardTheme, &manager);
+        let _ = std::fs::remove_dir_all(manager.dir());
+    }
+
+    #[test]
+    fn test_alpha_edit_updates_hex() {
+        let manager = temp_manager("alpha");
+        let mut editor = ThemeEditor::default();
+        editor.update(Msg::EditTheme(String::from("Dark")), &manager);
+        editor.update(
+            Msg::ColorEdit {
+                color_type: ColorType::Warning,
+                hex_value: String::from("#102030"),
+            },
+            &manager,
+        );
+
+        let alpha = |alpha| Msg::AlphaEdit {
+            color_type: ColorType::Warning,
+            alpha,
+        };
+        editor.update(alpha(0.5), &manager);
+        assert_eq!(editor.hex_inputs[&ColorType::Warning], "#10203080");
+        assert_eq!(editor.draft.as_ref().unwrap().colors.warning[3], 0.5);
+
+        // Back to opaque drops the alpha digits
+        editor.update(alpha(1.0), &manager);
+        assert_eq!(editor.hex_inputs[&ColorType::Warning], "#102030");
+
+        editor.update(Msg::DiscardTheme, &manager);
+        let _ = std::fs::remove_dir_all(manager.dir());
+    }
+}
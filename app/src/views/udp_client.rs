@@ -1,14 +1,16 @@
 use iced::Alignment::Center;
+use iced::Font;
 use iced::Length::{Fill, FillPortion};
-use iced::widget::{Column, button, column, row, text, text_input};
+use iced::widget::{Column, button, checkbox, column, row, scrollable, text, text_input};
 use net_monkey_components::TextInputDropdown;
+use net_monkey_core::PacketEncoding;
 
 use crate::Msg;
 use crate::views::settings::IpScannerApp;
 use net_monkey_theme::helpers;
 
 pub fn view<'a>(app: &'a IpScannerApp) -> Column<'a, Msg> {
-    let theme_colors = app.config.theme_provider().colors();
+    let theme_colors = app.theme_provider().colors();
 
     let (connected_text, connected_color) = match app.udp_client.connections.is_empty() {
         true => ("Connect".to_string(), theme_colors.primary_color()),
@@ -31,17 +33,14 @@ pub fn view<'a>(app: &'a IpScannerApp) -> Column<'a, Msg> {
         .collect::<Vec<String>>();
     let ip_sel: TextInputDropdown<_, _, Msg, iced::Theme> = TextInputDropdown::new(
         items,
-        app.udp_client
-            .connections
-            .first()
-            .map_or_default(|c| c.to_string()),
+        app.udp_client.ip_address.clone(),
         Msg::ChangeIpAddress,
         Msg::ChangeIpAddress,
     );
     // Create themed connection controls container
     let connection_controls = helpers::themed_container(
         row![
-            ip_sel.text_size(24),
+            ip_sel.text_size(24).font(Font::MONOSPACE),
             row![
                 text_input("Port", &app.udp_client.ip_port)
                     .on_input(Msg::ChangeIpPort)
@@ -59,13 +58,19 @@ pub fn view<'a>(app: &'a IpScannerApp) -> Column<'a, Msg> {
         .align_y(Center)
         .spacing(15)
         .width(Fill),
-        &app.config.theme_provider(),
+        &app.theme_provider(),
     );
 
     // Create themed history container
     let history_container = helpers::sub_menu_container(
-        column![text(history).color(history_color).height(Fill),],
-        &app.config.theme_provider(),
+        scrollable(
+            text(history)
+                .font(Font::MONOSPACE)
+                .color(history_color)
+                .width(Fill),
+        )
+        .height(Fill),
+        &app.theme_provider(),
     );
 
     // Create themed info panel
@@ -76,12 +81,51 @@ pub fn view<'a>(app: &'a IpScannerApp) -> Column<'a, Msg> {
                 .color(theme_colors.text_color()),
             text_input("Ip Address", "").size(24),
         ],
-        &app.config.theme_provider(),
+        &app.theme_provider(),
+    );
+
+    let hex = app.udp_client.encoding == PacketEncoding::Hex;
+    // Invalid hex is outlined and can't be sent
+    let packet = app.udp_client.packet().ok();
+    let invalid = packet.is_none();
+    let danger = theme_colors.danger_color();
+    let packet_sending = helpers::themed_container(
+        row![
+            checkbox("Hex", hex)
+                .on_toggle(|hex| Msg::PacketEncoding(match hex {
+                    true => PacketEncoding::Hex,
+                    false => PacketEncoding::Ascii,
+                }))
+                .text_size(24),
+            text_input("Datagram to send", &app.udp_client.current_packet)
+                .on_input(Msg::ChangePacket)
+                .font(Font::MONOSPACE)
+                .on_submit(Msg::SendPacket)
+                .size(24)
+                .width(FillPortion(3))
+                .padding(8)
+                .style(move |theme, status| {
+                    let mut style = text_input::default(theme, status);
+                    if invalid {
+                        style.border.color = danger;
+                    }
+                    style
+                }),
+            button(text("Send Datagram").size(24))
+                .on_press_maybe(packet.map(|_| Msg::SendPacket))
+                .width(FillPortion(1))
+                .padding(8),
+        ]
+        .align_y(Center)
+        .spacing(15)
+        .width(Fill),
+        &app.theme_provider(),
     );
 
     let items = vec![
         connection_controls.into(),
         row![history_container, info_panel].spacing(10).into(),
+        packet_sending.into(),
     ];
 
     Column::with_children(items).align_x(Center).spacing(10)
@@ -0,0 +1,454 @@
+use iced::Pixels;
+use iced::overlay::menu::Catalog;
+use iced_core::clipboard::Clipboard;
+use iced_core::event::{self, Event};
+use iced_core::keyboard;
+use iced_core::layout::{self, Layout};
+use iced_core::mouse;
+use iced_core::overlay;
+use iced_core::renderer;
+use iced_core::text::{self};
+use iced_core::widget::{self, Operation, Tree, Widget};
+use iced_core::{Border, Color, Element, Length, Point, Rectangle, Size, Vector};
+
+/// Wraps `content` with a menu that opens at the cursor on right-click
+///
+/// Each item is a label and the message sent when it's clicked. The menu
+/// closes after a choice, on Escape, or on a click anywhere else. Colors come
+/// from the theme's menu [`Catalog`], the same as a pick list's options.
+///
+/// # Examples
+/// ```rust,ignore
+/// let row = ContextMenu::new(text(host.ip.to_string()))
+///     .item("Copy IP", Message::Copy(host.ip.to_string()))
+///     .item("Rescan this host", Message::Rescan(host.ip));
+/// ```
+pub struct ContextMenu<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer>
+where
+    Theme: Catalog,
+    Renderer: text::Renderer,
+{
+    content: Element<'a, Message, Theme, Renderer>,
+    items: Vec<(String, Message)>,
+    width: f32,
+    item_height: f32,
+    text_size: Pixels,
+}
+
+impl<'a, Message, Theme, Renderer> ContextMenu<'a, Message, Theme, Renderer>
+where
+    Theme: Catalog,
+    Renderer: text::Renderer,
+{
+    pub fn new(content: impl Into<Element<'a, Message, Theme, Renderer>>) -> Self {
+        Self {
+            content: content.into(),
+            items: Vec::new(),
+            width: 180.0,
+            item_height: 28.0,
+            text_size: Pixels(14.0),
+        }
+    }
+
+    /// Add an entry sending `message` when chosen
+    pub fn item(mut self, label: impl Into<String>, message: Message) -> Self {
+        self.items.push((label.into(), message));
+        self
+    }
+
+    /// Sets the width of the open menu
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn text_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.text_size = size.into();
+        self
+    }
+}
+
+/// Where the menu is open, if it is
+#[derive(Debug, Clone, Default)]
+struct State {
+    open_at: Option<Point>,
+    hovered: Option<usize>,
+}
+
+impl<'a, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for ContextMenu<'a, Message, Theme, Renderer>
+where
+    Theme: Catalog,
+    Renderer: text::Renderer,
+    Message: Clone,
+{
+    fn tag(&self) -> widget::tree::Tag {
+        widget::tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> widget::tree::State {
+        widget::tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut iced_core::Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) = event
+            && let Some(position) = cursor.position_over(layout.bounds())
+            && !self.items.is_empty()
+        {
+            let state: &mut State = tree.state.downcast_mut();
+            state.open_at = Some(position);
+            state.hovered = None;
+            return event::Status::Captured;
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let Tree {
+            state, children, ..
+        } = tree;
+        let state: &mut State = state.downcast_mut();
+
+        match state.open_at {
+            Some(position) => Some(overlay::Element::new(Box::new(MenuOverlay {
+                items: &self.items,
+                position: position + translation,
+                width: self.width,
+                item_height: self.item_height,
+                text_size: self.text_size,
+                state,
+            }))),
+            None => self.content.as_widget_mut().overlay(
+                &mut children[0],
+                layout,
+                renderer,
+                translation,
+            ),
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<ContextMenu<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: Clone + 'a,
+    Theme: Catalog + 'a,
+    Renderer: text::Renderer + 'a,
+{
+    fn from(menu: ContextMenu<'a, Message, Theme, Renderer>) -> Self {
+        Element::new(menu)
+    }
+}
+
+/// The open menu, drawn above everything else
+struct MenuOverlay<'b, Message> {
+    items: &'b [(String, Message)],
+    position: Point,
+    width: f32,
+    item_height: f32,
+    text_size: Pixels,
+    state: &'b mut State,
+}
+
+impl<Message> MenuOverlay<'_, Message> {
+    fn close(&mut self) {
+        self.state.open_at = None;
+        self.state.hovered = None;
+    }
+}
+
+/// Top left of a `size` menu opened at `cursor`, flipped to the cursor's
+/// other side where it would run off the `viewport`
+fn menu_position(cursor: Point, size: Size, viewport: Size) -> Point {
+    let x = match cursor.x + size.width > viewport.width {
+        true => cursor.x - size.width,
+        false => cursor.x,
+    };
+    let y = match cursor.y + size.height > viewport.height {
+        true => cursor.y - size.height,
+        false => cursor.y,
+    };
+    Point::new(x.max(0.0), y.max(0.0))
+}
+
+/// Index of the item at `y` in a menu starting at `top`
+fn item_at(top: f32, y: f32, item_height: f32, items: usize) -> Option<usize> {
+    let offset = y - top;
+    let index = (offset / item_height) as usize;
+    (offset >= 0.0 && index < items).then_some(index)
+}
+
+impl<Message, Theme, Renderer> overlay::Overlay<Message, Theme, Renderer>
+    for MenuOverlay<'_, Message>
+where
+    Theme: Catalog,
+    Renderer: text::Renderer,
+    Message: Clone,
+{
+    fn layout(&mut self, _renderer: &Renderer, bounds: Size) -> layout::Node {
+        let size = Size::new(self.width, self.item_height * self.items.len() as f32);
+        layout::Node::new(size).move_to(menu_position(self.position, size, bounds))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+    ) {
+        let bounds = layout.bounds();
+        let style = <Theme as Catalog>::style(theme, &<Theme as Catalog>::default());
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border: Border {
+                    radius: iced::border::Radius::new(4.0),
+                    ..style.border
+                },
+                shadow: iced_core::Shadow {
+                    color: Color::from_rgba(0.0, 0.0, 0.0, 0.2),
+                    offset: Vector::new(0.0, 2.0),
+                    blur_radius: 6.0,
+                },
+            },
+            style.background,
+        );
+
+        for (index, (label, _)) in self.items.iter().enumerate() {
+            let item_bounds = Rectangle {
+                x: bounds.x,
+                y: bounds.y + index as f32 * self.item_height,
+                width: bounds.width,
+                height: self.item_height,
+            };
+
+            let is_hovered = self.state.hovered == Some(index);
+            if is_hovered {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: item_bounds,
+                        border: Border::default(),
+                        shadow: iced_core::Shadow::default(),
+                    },
+                    style.selected_background,
+                );
+            }
+
+            renderer.fill_text(
+                text::Text {
+                    content: label.clone(),
+                    size: self.text_size,
+                    line_height: text::LineHeight::default(),
+                    font: renderer.default_font(),
+                    bounds: item_bounds.size(),
+                    horizontal_alignment: iced::alignment::Horizontal::Left,
+                    vertical_alignment: iced::alignment::Vertical::Center,
+                    shaping: text::Shaping::Advanced,
+                    wrapping: text::Wrapping::None,
+                },
+                Point::new(item_bounds.x + 10.0, item_bounds.center_y()),
+                match is_hovered {
+                    true => style.selected_text_color,
+                    false => style.text_color,
+                },
+                item_bounds,
+            );
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut iced_core::Shell<'_, Message>,
+    ) -> event::Status {
+        let bounds = layout.bounds();
+        let hovered = cursor
+            .position_over(bounds)
+            .and_then(|position| item_at(bounds.y, position.y, self.item_height, self.items.len()));
+
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                self.state.hovered = hovered;
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(index) = hovered {
+                    shell.publish(self.items[index].1.clone());
+                    self.close();
+                    return event::Status::Captured;
+                }
+                self.close();
+            }
+            // Another right-click either lands in the menu or opens a new one elsewhere
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                if cursor.is_over(bounds) {
+                    return event::Status::Captured;
+                }
+                self.close();
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                ..
+            }) => {
+                self.close();
+                return event::Status::Captured;
+            }
+            _ => {}
+        }
+
+        event::Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if cursor.is_over(layout.bounds()) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_menu_position_flips_at_edges() {
+        let menu = Size::new(180.0, 112.0);
+        let window = Size::new(800.0, 600.0);
+        assert_eq!(
+            menu_position(Point::new(100.0, 100.0), menu, window),
+            Point::new(100.0, 100.0)
+        );
+        // Near the bottom right corner it opens up and to the left
+        assert_eq!(
+            menu_position(Point::new(700.0, 550.0), menu, window),
+            Point::new(520.0, 438.0)
+        );
+        // Never pushed off the top left of a window too small for it
+        assert_eq!(
+            menu_position(Point::new(50.0, 50.0), menu, Size::new(100.0, 100.0)),
+            Point::ORIGIN
+        );
+    }
+
+    #[test]
+    fn test_item_at() {
+        assert_eq!(item_at(100.0, 100.0, 28.0, 4), Some(0));
+        assert_eq!(item_at(100.0, 130.0, 28.0, 4), Some(1));
+        assert_eq!(item_at(100.0, 211.0, 28.0, 4), Some(3));
+        assert_eq!(item_at(100.0, 213.0, 28.0, 4), None);
+        assert_eq!(item_at(100.0, 90.0, 28.0, 4), None);
+    }
+}
@@ -5,13 +5,15 @@
 //! the previous crude approximation methods that failed with Unicode text.
 
 use std::borrow::Borrow;
+use std::ops::Range;
+use std::panic::AssertUnwindSafe;
 use std::sync::{Arc, Mutex, OnceLock};
 
-use cosmic_text::{Attrs, Buffer, FontSystem, Metrics, Shaping};
+use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping};
 use iced::Pixels;
 
 use iced::widget::pick_list::Catalog;
-use iced_core::clipboard::Clipboard;
+use iced_core::clipboard::{self, Clipboard};
 use iced_core::event::{self, Event};
 use iced_core::keyboard;
 use iced_core::layout::{self, Layout};
@@ -19,8 +21,10 @@ use iced_core::mouse;
 use iced_core::overlay;
 use iced_core::renderer;
 use iced_core::text::{self};
-use iced_core::widget::{self, Tree, Widget};
+use iced_core::widget::operation::{self, Focusable, Operation};
+use iced_core::widget::{self, Id, Tree, Widget};
 use iced_core::{Border, Color, Length, Padding, Rectangle, Size, Vector};
+use iced_widget::runtime::{Action, Task, task};
 use iced_widget::text_input::Status;
 
 use super::selection_overlay::MultiselectOverlay;
@@ -53,6 +57,14 @@ use super::selection_overlay::MultiselectOverlay;
 /// .size(24.into())
 /// .width(Length::Fixed(300.0));
 /// ```
+///
+/// ## Multi-select
+/// ```rust,ignore
+/// // Rows get check boxes and clicks toggle them until the list is closed
+/// let dropdown = TextInputDropdown::new(adapters, filter, Message::Filter, Message::Pick)
+///     .multi_select(chosen.clone(), Message::ToggleAdapter)
+///     .on_done(Message::AdaptersChosen);
+/// ```
 pub struct TextInputDropdown<'a, T, L, Message, Theme = iced::Theme, Renderer = iced::Renderer>
 where
     T: ToString + PartialEq + Clone,
@@ -73,6 +85,16 @@ where
     text_shaping: text::Shaping,
     font: Option<Renderer::Font>,
     class: <Theme as Catalog>::Class<'a>,
+    id: Option<Id>,
+    /// Multi-select mode: items toggle and the list stays open
+    on_toggle: Option<Box<dyn Fn(T) -> Message + 'a>>,
+    selected: Vec<T>,
+    on_done: Option<Message>,
+    dimmed: Vec<T>,
+    enabled: bool,
+    input_filter: Option<fn(char) -> bool>,
+    valid: bool,
+    error_color: Color,
 }
 
 impl<'a, T, L, Message, Theme, Renderer> TextInputDropdown<'a, T, L, Message, Theme, Renderer>
@@ -101,9 +123,87 @@ where
             text_shaping: text::Shaping::default(),
             font: None,
             class: <Theme as Catalog>::default(),
+            id: None,
+            on_toggle: None,
+            selected: Vec::new(),
+            on_done: None,
+            dimmed: Vec::new(),
+            enabled: true,
+            input_filter: None,
+            valid: true,
+            error_color: DEFAULT_ERROR_COLOR,
         }
     }
 
+    /// Switch to multi-select: clicking an item calls `on_toggle` and keeps the
+    /// list open, with a check box showing which of the items are `selected`
+    pub fn multi_select<F>(mut self, selected: Vec<T>, on_toggle: F) -> Self
+    where
+        F: Fn(T) -> Message + 'a,
+    {
+        self.selected = selected;
+        self.on_toggle = Some(Box::new(on_toggle));
+        self
+    }
+
+    /// Fade these items in the list, such as options that are unavailable
+    /// right now but can still be picked
+    pub fn dimmed(mut self, dimmed: Vec<T>) -> Self {
+        self.dimmed = dimmed;
+        self
+    }
+
+    /// A disabled dropdown ignores input, drops focus and draws muted
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Only accept typed or pasted characters that `allowed` passes, such as
+    /// [`is_ip_char`] for an address field
+    pub fn input_filter(mut self, allowed: fn(char) -> bool) -> Self {
+        self.input_filter = Some(allowed);
+        self
+    }
+
+    /// Whether the value is acceptable; an invalid one is bordered in the
+    /// error color
+    pub fn valid(mut self, valid: bool) -> Self {
+        self.valid = valid;
+        self
+    }
+
+    /// Sets the border color for an invalid value, such as the theme's danger color
+    pub fn error_color(mut self, color: Color) -> Self {
+        self.error_color = color;
+        self
+    }
+
+    /// Message sent when a multi-select list is closed
+    pub fn on_done(mut self, message: Message) -> Self {
+        self.on_done = Some(message);
+        self
+    }
+
+    /// Close the list, reporting the finished selection in multi-select mode
+    fn close_list(&self, state: &mut State, shell: &mut iced_core::Shell<'_, Message>)
+    where
+        Message: Clone,
+    {
+        if std::mem::take(&mut state.is_open)
+            && self.on_toggle.is_some()
+            && let Some(done) = &self.on_done
+        {
+            shell.publish(done.clone());
+        }
+    }
+
+    /// Sets the [`Id`] used to target the dropdown with [`focus`]
+    pub fn id(mut self, id: impl Into<Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
     pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
         self.placeholder = Some(placeholder.into());
         self
@@ -123,6 +223,12 @@ where
         self.text_size = Some(size.into());
         self
     }
+
+    /// Sets the font of the input and its options, instead of the app default
+    pub fn font(mut self, font: Renderer::Font) -> Self {
+        self.font = Some(font);
+        self
+    }
 }
 
 impl<'a, T, L, Message, Theme, Renderer> Widget<Message, Theme, Renderer>
@@ -131,7 +237,7 @@ where
     T: ToString + PartialEq + Clone,
     L: Borrow<[T]> + 'a + std::fmt::Debug,
     Theme: Catalog + iced::widget::text_input::Catalog + iced::widget::button::Catalog,
-    Renderer: text::Renderer,
+    Renderer: text::Renderer<Font = iced::Font>,
     Message: Clone,
 {
     fn tag(&self) -> widget::tree::Tag {
@@ -142,6 +248,21 @@ where
         widget::tree::State::new(State::new())
     }
 
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        _layout: Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        let state: &mut State = tree.state.downcast_mut();
+        operation.focusable(state, self.id.as_ref());
+        // Focus moved here by an operation doesn't stick while disabled
+        if !self.enabled {
+            state.unfocus();
+        }
+    }
+
     fn on_event(
         &mut self,
         tree: &mut Tree,
@@ -149,11 +270,15 @@ where
         layout: Layout<'_>,
         cursor: mouse::Cursor,
         renderer: &Renderer,
-        _clipboard: &mut dyn Clipboard,
+        clipboard: &mut dyn Clipboard,
         shell: &mut iced_core::Shell<'_, Message>,
         _viewport: &Rectangle,
     ) -> event::Status {
         let state: &mut State = tree.state.downcast_mut();
+        if !self.enabled {
+            state.unfocus();
+            return event::Status::Ignored;
+        }
         let bounds = layout.bounds();
 
         // Button bounds (right side of the widget)
@@ -177,129 +302,215 @@ where
             Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
                 if let Some(position) = cursor.position() {
                     if button_bounds.contains(position) {
-                        state.is_open = !state.is_open;
+                        match state.is_open {
+                            true => self.close_list(state, shell),
+                            false => state.is_open = true,
+                        }
                         state.is_focused = true;
                         return event::Status::Captured;
                     } else if input_bounds.contains(position) {
                         state.is_focused = true;
-                        state.is_open = false;
+                        self.close_list(state, shell);
                         // Update cursor position
                         let relative_x = position.x - input_bounds.x - self.padding.left;
-                        state.cursor_position = self.cursor_position_from_x(relative_x, renderer);
-                        return event::Status::Captured;
-                    } else if state.is_open {
-                        // Check if clicking on dropdown items
-                        let dropdown_y = bounds.y + bounds.height;
-                        let item_height = 30.0;
-                        for (index, item) in self.items.borrow().iter().enumerate() {
-                            let item_y = dropdown_y + (index as f32 * item_height);
-                            let item_bounds = Rectangle {
-                                x: bounds.x,
-                                y: item_y,
-                                width: bounds.width,
-                                height: item_height,
-                            };
-
-                            if item_bounds.contains(position) {
-                                shell.publish((self.on_select)(item.clone()));
-                                state.is_open = false;
-                                state.is_focused = false;
-                                return event::Status::Captured;
+                        let clicked = self
+                            .cursor_position_from_x(relative_x, renderer)
+                            .min(self.value.len());
+                        let click =
+                            mouse::Click::new(position, mouse::Button::Left, state.last_click);
+                        match click.kind() {
+                            mouse::click::Kind::Single => {
+                                state.move_cursor(clicked, state.keyboard_modifiers.shift())
+                            }
+                            mouse::click::Kind::Double => {
+                                let word = word_at(&self.value, clicked);
+                                state.selection = Some(word.start);
+                                state.cursor_position = word.end;
+                            }
+                            mouse::click::Kind::Triple => {
+                                state.selection = Some(0);
+                                state.cursor_position = self.value.len();
                             }
                         }
-                        // Click outside dropdown - close it
-                        state.is_open = false;
-                        state.is_focused = false;
+                        state.last_click = Some(click);
+                        return event::Status::Captured;
                     } else {
-                        state.is_open = false;
+                        // Clicks on the list itself are captured by the overlay
+                        self.close_list(state, shell);
                         state.is_focused = false;
                     }
                 }
             }
-            Event::Mouse(mouse::Event::CursorMoved { position }) => {
-                if state.is_open {
-                    let dropdown_y = bounds.y + bounds.height;
-                    let item_height = 30.0;
-                    state.hovered_option = None;
-
-                    for (index, _) in self.items.borrow().iter().enumerate() {
-                        let item_y = dropdown_y + (index as f32 * item_height);
-                        let item_bounds = Rectangle {
-                            x: bounds.x,
-                            y: item_y,
-                            width: bounds.width,
-                            height: item_height,
-                        };
-
-                        if item_bounds.contains(*position) {
-                            state.hovered_option = Some(index);
-                            break;
-                        }
-                    }
-                }
-            }
             Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
                 if state.is_focused {
                     state.keyboard_modifiers = *modifiers;
                     match key {
+                        keyboard::Key::Character(c) if modifiers.command() => match c.as_str() {
+                            "v" => {
+                                if let Some(pasted) = clipboard.read(clipboard::Kind::Standard) {
+                                    let pasted = filter_chars(&pasted, self.input_filter);
+                                    state.delete_selection(&mut self.value);
+                                    state.cursor_position = insert_pasted(
+                                        &mut self.value,
+                                        state.cursor_position,
+                                        &pasted,
+                                    );
+                                    shell.publish((self.on_input)(self.value.clone()));
+                                }
+                                return event::Status::Captured;
+                            }
+                            "c" => {
+                                if let Some(selected) = state.selected_text(&self.value) {
+                                    clipboard
+                                        .write(clipboard::Kind::Standard, selected.to_string());
+                                }
+                                return event::Status::Captured;
+                            }
+                            "x" => {
+                                if let Some(cut) = state.delete_selection(&mut self.value) {
+                                    clipboard.write(clipboard::Kind::Standard, cut);
+                                    shell.publish((self.on_input)(self.value.clone()));
+                                }
+                                return event::Status::Captured;
+                            }
+                            "a" => {
+                                state.selection = Some(0);
+                                state.cursor_position = self.value.len();
+                                return event::Status::Captured;
+                            }
+                            _ => {}
+                        },
                         keyboard::Key::Character(c) => {
                             if !modifiers.control() && !modifiers.logo() {
-                                let char_str = c.to_string();
-                                self.value.insert_str(state.cursor_position, &char_str);
-                                state.cursor_position += char_str.len();
+                                let c = filter_chars(c, self.input_filter);
+                                // Swallow rejected keys rather than let them reach other widgets
+                                if c.is_empty() {
+                                    return event::Status::Captured;
+                                }
+                                state.delete_selection(&mut self.value);
+                                state.cursor_position =
+                                    insert_at(&mut self.value, state.cursor_position, &c);
                                 shell.publish((self.on_input)(self.value.clone()));
                                 return event::Status::Captured;
                             }
                         }
                         keyboard::Key::Named(keyboard::key::Named::Backspace) => {
-                            if state.cursor_position > 0 {
-                                let prev_cursor = state.cursor_position;
-                                state.cursor_position = prev_cursor.saturating_sub(1);
-                                if state.cursor_position < self.value.len() {
-                                    self.value.remove(state.cursor_position);
-                                }
+                            if state.delete_selection(&mut self.value).is_some() {
+                                shell.publish((self.on_input)(self.value.clone()));
+                                return event::Status::Captured;
+                            }
+                            if let Some(cursor) =
+                                delete_before(&mut self.value, state.cursor_position)
+                            {
+                                state.cursor_position = cursor;
                                 shell.publish((self.on_input)(self.value.clone()));
                                 return event::Status::Captured;
                             }
                         }
                         keyboard::Key::Named(keyboard::key::Named::Delete) => {
-                            if state.cursor_position < self.value.len() {
-                                self.value.remove(state.cursor_position);
+                            if state.delete_selection(&mut self.value).is_some() {
+                                shell.publish((self.on_input)(self.value.clone()));
+                                return event::Status::Captured;
+                            }
+                            if let Some(cursor) =
+                                delete_after(&mut self.value, state.cursor_position)
+                            {
+                                state.cursor_position = cursor;
                                 shell.publish((self.on_input)(self.value.clone()));
                                 return event::Status::Captured;
                             }
                         }
+                        // Shift extends the selection, otherwise an existing one collapses.
+                        // Ctrl moves by whitespace-delimited words
                         keyboard::Key::Named(keyboard::key::Named::ArrowLeft) => {
-                            state.cursor_position = state.cursor_position.saturating_sub(1);
+                            let to = match (modifiers.shift(), state.selection_range()) {
+                                (false, Some(range)) => range.start,
+                                _ if modifiers.control() => {
+                                    prev_word(&self.value, state.cursor_position)
+                                }
+                                _ => prev_boundary(&self.value, state.cursor_position),
+                            };
+                            state.move_cursor(to, modifiers.shift());
                             return event::Status::Captured;
                         }
                         keyboard::Key::Named(keyboard::key::Named::ArrowRight) => {
-                            state.cursor_position =
-                                (state.cursor_position + 1).min(self.value.len());
+                            let to = match (modifiers.shift(), state.selection_range()) {
+                                (false, Some(range)) => range.end,
+                                _ if modifiers.control() => {
+                                    next_word(&self.value, state.cursor_position)
+                                }
+                                _ => next_boundary(&self.value, state.cursor_position),
+                            };
+                            state.move_cursor(to, modifiers.shift());
                             return event::Status::Captured;
                         }
-                        keyboard::Key::Named(keyboard::key::Named::ArrowDown) => {
+                        keyboard::Key::Named(keyboard::key::Named::Home) => {
+                            state.move_cursor(0, modifiers.shift());
+                            return event::Status::Captured;
+                        }
+                        keyboard::Key::Named(keyboard::key::Named::End) => {
+                            state.move_cursor(self.value.len(), modifiers.shift());
+                            return event::Status::Captured;
+                        }
+                        // Arrows open the list, then move the highlight through it
+                        keyboard::Key::Named(
+                            named @ (keyboard::key::Named::ArrowDown
+                            | keyboard::key::Named::ArrowUp),
+                        ) => {
+                            let down = *named == keyboard::key::Named::ArrowDown;
                             if !state.is_open {
-                                state.is_open = true;
+                                if down {
+                                    state.is_open = true;
+                                    return event::Status::Captured;
+                                }
+                            } else {
+                                let len = self.items.borrow().len();
+                                state.hovered_option = step_option(state.hovered_option, len, down);
+                                if let Some(index) = state.hovered_option {
+                                    let visible = list_height(len, bounds.height);
+                                    state.scroll_offset = scroll_into_view(
+                                        state.scroll_offset,
+                                        index,
+                                        bounds.height,
+                                        visible,
+                                    );
+                                }
                                 return event::Status::Captured;
                             }
                         }
+                        // Enter picks the highlighted item, or submits once the list is closed.
+                        // In multi-select mode it toggles the item and the list stays open
+                        keyboard::Key::Named(keyboard::key::Named::Enter) if state.is_open => {
+                            let highlighted = state
+                                .hovered_option
+                                .and_then(|index| self.items.borrow().get(index).cloned());
+                            match (highlighted, &self.on_toggle) {
+                                (Some(item), Some(on_toggle)) => shell.publish(on_toggle(item)),
+                                (Some(item), None) => {
+                                    shell.publish((self.on_select)(item));
+                                    state.is_open = false;
+                                }
+                                (None, _) => self.close_list(state, shell),
+                            }
+                            return event::Status::Captured;
+                        }
                         keyboard::Key::Named(keyboard::key::Named::Enter) => {
                             if let Some(ref message) = self.on_submit {
                                 shell.publish(message.clone());
                                 return event::Status::Captured;
                             }
                         }
-                        keyboard::Key::Named(keyboard::key::Named::Escape) => {
-                            if state.is_open {
-                                state.is_open = false;
-                                return event::Status::Captured;
-                            }
+                        keyboard::Key::Named(keyboard::key::Named::Escape) if state.is_open => {
+                            self.close_list(state, shell);
+                            return event::Status::Captured;
                         }
                         _ => {}
                     }
                 }
             }
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.keyboard_modifiers = *modifiers;
+            }
             _ => {}
         }
 
@@ -313,11 +524,11 @@ where
         _renderer: &Renderer,
         translation: Vector,
     ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
-        let state: &State = tree.state.downcast_ref();
+        let state: &mut State = tree.state.downcast_mut();
 
         if state.is_open {
             let bounds = layout.bounds();
-            let dropdown_height = (self.items.borrow().len() as f32 * bounds.height).min(200.0);
+            let dropdown_height = list_height(self.items.borrow().len(), bounds.height);
             let dropdown_bounds = Rectangle {
                 x: bounds.x + translation.x,
                 y: bounds.y + bounds.height + 4.0 + translation.y,
@@ -328,6 +539,9 @@ where
             Some(overlay::Element::new(Box::new(MultiselectOverlay {
                 items: self.items.borrow().to_vec(),
                 on_select: &self.on_select,
+                on_toggle: self.on_toggle.as_deref(),
+                selected: &self.selected,
+                dimmed: &self.dimmed,
                 bounds: dropdown_bounds,
                 item_height: bounds.height,
                 text_size: self.text_size.unwrap_or(Pixels(14.0)),
@@ -336,7 +550,9 @@ where
                 text_shaping: self.text_shaping,
                 font: self.font,
                 class: <Theme as Catalog>::default(),
-                hovered_option: state.hovered_option,
+                hovered_option: &mut state.hovered_option,
+                scroll_offset: &mut state.scroll_offset,
+                is_open: &mut state.is_open,
             })))
         } else {
             None
@@ -409,7 +625,7 @@ where
         };
         let is_mouse_over = cursor.is_over(bounds);
 
-        let text_input_status = if self.items.borrow().is_empty() {
+        let text_input_status = if !self.enabled {
             Status::Disabled
         } else if state.is_focused || state.is_open {
             Status::Focused
@@ -428,45 +644,23 @@ where
 
         let pick_list_style = <Theme as Catalog>::style(theme, &self.class, pick_list_status);
 
-        // Draw text input background
-        let input_appearance = iced::widget::text_input::Style {
-            background: pick_list_style.background,
-            border: Border {
-                color: if state.is_focused {
-                    pick_list_style.border.color
-                } else {
-                    Color {
-                        r: pick_list_style.border.color.r * 0.7,
-                        g: pick_list_style.border.color.g * 0.7,
-                        b: pick_list_style.border.color.b * 0.7,
-                        a: pick_list_style.border.color.a,
-                    }
-                },
-                width: 1.0,
-                radius: iced::border::Radius::new(4.0),
-            },
-            icon: pick_list_style.text_color,
-            value: pick_list_style.text_color,
-            placeholder: Color {
-                r: pick_list_style.text_color.r * 0.5,
-                g: pick_list_style.text_color.g * 0.5,
-                b: pick_list_style.text_color.b * 0.5,
-                a: pick_list_style.text_color.a,
-            },
-            selection: Color {
-                r: pick_list_style.text_color.r,
-                g: pick_list_style.text_color.g * 0.8,
-                b: 1.0,
-                a: pick_list_style.text_color.a,
-            },
-        };
+        // The input part is styled like any other text input in the theme,
+        // including its focused border and disabled colors
+        let input_appearance = <Theme as iced::widget::text_input::Catalog>::style(
+            theme,
+            &<Theme as iced::widget::text_input::Catalog>::default(),
+            text_input_status,
+        );
 
         renderer.fill_quad(
             renderer::Quad {
                 bounds: input_bounds,
                 border: Border {
-                    color: input_appearance.border.color,
-                    width: input_appearance.border.width,
+                    color: match self.valid {
+                        true => input_appearance.border.color,
+                        false => self.error_color,
+                    },
+                    width: input_appearance.border.width.max(1.0),
                     radius: input_appearance.border.radius,
                 },
                 shadow: iced_core::Shadow::default(),
@@ -482,6 +676,27 @@ where
             height: input_bounds.height - self.padding.vertical(),
         };
 
+        // Draw selection highlight beneath the text
+        if state.is_focused
+            && let Some(range) = state.selection_range()
+        {
+            let start = self.cursor_x_position(range.start, renderer);
+            let end = self.cursor_x_position(range.end, renderer);
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle {
+                        x: text_bounds.x + start,
+                        y: text_bounds.y + 2.0,
+                        width: end - start,
+                        height: text_bounds.height - 4.0,
+                    },
+                    border: Border::default(),
+                    shadow: iced_core::Shadow::default(),
+                },
+                iced_core::Background::Color(input_appearance.selection),
+            );
+        }
+
         let display_text = if self.value.is_empty() {
             self.placeholder.as_deref().unwrap_or("")
         } else {
@@ -489,12 +704,7 @@ where
         };
 
         let text_color = match self.value.is_empty() && self.placeholder.is_some() {
-            true => Color {
-                r: input_appearance.value.r * 0.5,
-                g: input_appearance.value.g * 0.5,
-                b: input_appearance.value.b * 0.5,
-                a: input_appearance.value.a,
-            },
+            true => input_appearance.placeholder,
             false => input_appearance.value,
         };
 
@@ -539,20 +749,23 @@ where
             );
         }
 
-        // Draw dropdown button
-        let button_bg_color = match pick_list_style.background {
-            iced_core::Background::Color(color) => Color {
+        // Draw dropdown button, a shade darker than a plain color background
+        let button_background = match pick_list_style.background {
+            iced_core::Background::Color(color) => iced_core::Background::Color(Color {
                 r: color.r * 0.95,
                 g: color.g * 0.95,
                 b: color.b * 0.95,
                 a: color.a,
-            },
-            _ => Color::from_rgb(0.9, 0.9, 0.9),
+            }),
+            background => background,
         };
 
         let button_style = iced::widget::button::Style {
-            background: Some(iced_core::Background::Color(button_bg_color)),
-            text_color: pick_list_style.text_color,
+            background: Some(button_background),
+            text_color: match self.enabled {
+                true => pick_list_style.handle_color,
+                false => pick_list_style.handle_color.scale_alpha(0.5),
+            },
             border: Border {
                 color: pick_list_style.border.color,
                 width: 1.0,
@@ -595,99 +808,6 @@ where
                 Size::new(arrow_size, arrow_size),
             ),
         );
-
-        // Draw simple dropdown list if open
-        if state.is_open {
-            let dropdown_y = bounds.y + bounds.height + 4.0;
-            let dropdown_height = (self.items.borrow().len() as f32 * button_width).min(200.0);
-            let dropdown_bounds = Rectangle {
-                x: bounds.x,
-                y: dropdown_y,
-                width: bounds.width,
-                height: dropdown_height,
-            };
-
-            // Draw dropdown background
-            renderer.fill_quad(
-                renderer::Quad {
-                    bounds: dropdown_bounds,
-                    border: Border {
-                        color: pick_list_style.border.color,
-                        width: 1.0,
-                        radius: iced::border::Radius::new(4.0),
-                    },
-                    shadow: iced_core::Shadow::default(),
-                },
-                input_appearance.background,
-            );
-
-            // Draw dropdown items
-            let item_height = bounds.height;
-            for (index, item) in self.items.borrow().iter().enumerate() {
-                let item_y = dropdown_y + (index as f32 * item_height);
-                let item_bounds = Rectangle {
-                    x: bounds.x,
-                    y: item_y,
-                    width: bounds.width,
-                    height: item_height,
-                };
-
-                // Highlight hovered item
-                let is_hovered = state.hovered_option == Some(index);
-                if is_hovered {
-                    renderer.fill_quad(
-                        renderer::Quad {
-                            bounds: item_bounds,
-                            border: Border {
-                                color: pick_list_style.border.color,
-                                width: 1.0,
-                                radius: iced::border::Radius::new(4.0),
-                            },
-                            shadow: iced_core::Shadow::default(),
-                        },
-                        {
-                            let item_bg_color = match pick_list_style.background {
-                                iced_core::Background::Color(color) => Color {
-                                    r: color.r * 0.98,
-                                    g: color.g * 0.98,
-                                    b: color.b * 1.05,
-                                    a: color.a,
-                                },
-                                _ => Color::from_rgb(0.9, 0.95, 1.0),
-                            };
-                            iced_core::Background::Color(item_bg_color)
-                        },
-                    );
-                }
-
-                let text_color = match is_hovered {
-                    true => Color::BLACK,
-                    false => input_appearance.value,
-                };
-
-                // Draw item text
-                renderer.fill_text(
-                    text::Text {
-                        content: item.to_string(),
-                        size: text_size,
-                        line_height: self.text_line_height,
-                        font,
-                        bounds: item_bounds.size(),
-                        horizontal_alignment: iced::alignment::Horizontal::Left,
-                        vertical_alignment: iced::alignment::Vertical::Center,
-                        shaping: self.text_shaping,
-                        wrapping: text::Wrapping::None,
-                    },
-                    iced_core::Point::new(item_bounds.x + self.padding.left, item_bounds.y)
-                        + Vector {
-                            x: 0.0,
-                            y: item_bounds.height / 2.0,
-                        },
-                    text_color,
-                    item_bounds,
-                );
-            }
-        }
     }
 
     fn mouse_interaction(
@@ -707,7 +827,9 @@ where
             height: bounds.height,
         };
 
-        if cursor.is_over(button_bounds) || cursor.is_over(bounds) {
+        if !self.enabled {
+            mouse::Interaction::default()
+        } else if cursor.is_over(button_bounds) || cursor.is_over(bounds) {
             mouse::Interaction::Pointer
         } else {
             mouse::Interaction::default()
@@ -720,7 +842,12 @@ struct State {
     is_open: bool,
     is_focused: bool,
     cursor_position: usize,
+    /// Where the selection started; the cursor is its other end
+    selection: Option<usize>,
+    last_click: Option<mouse::Click>,
     hovered_option: Option<usize>,
+    /// How far the open list has been scrolled, in pixels
+    scroll_offset: f32,
 }
 
 impl State {
@@ -730,9 +857,59 @@ impl State {
             is_open: false,
             is_focused: false,
             cursor_position: 0,
+            selection: None,
+            last_click: None,
             hovered_option: None,
+            scroll_offset: 0.0,
         }
     }
+
+    /// Selected byte range, if any text is selected
+    fn selection_range(&self) -> Option<Range<usize>> {
+        let anchor = self.selection?;
+        let range = anchor.min(self.cursor_position)..anchor.max(self.cursor_position);
+        (!range.is_empty()).then_some(range)
+    }
+
+    fn selected_text<'v>(&self, value: &'v str) -> Option<&'v str> {
+        value.get(self.selection_range()?)
+    }
+
+    /// Remove the selected text from `value`, leaving the cursor at its start
+    fn delete_selection(&mut self, value: &mut String) -> Option<String> {
+        let range = self.selection_range();
+        self.selection = None;
+        let range = range.filter(|range| value.get(range.clone()).is_some())?;
+        self.cursor_position = range.start;
+        Some(value.drain(range).collect())
+    }
+
+    /// Move the cursor, extending the selection when `select` is set
+    fn move_cursor(&mut self, to: usize, select: bool) {
+        match select {
+            true => {
+                self.selection.get_or_insert(self.cursor_position);
+            }
+            false => self.selection = None,
+        }
+        self.cursor_position = to;
+    }
+}
+
+impl Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    fn focus(&mut self) {
+        self.is_focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.is_focused = false;
+        self.is_open = false;
+        self.selection = None;
+    }
 }
 
 impl Default for State {
@@ -746,50 +923,59 @@ where
     T: ToString + PartialEq + Clone,
     L: Borrow<[T]> + 'a + std::fmt::Debug,
     Theme: Catalog + iced::widget::text_input::Catalog + iced::widget::button::Catalog,
-    Renderer: text::Renderer,
+    Renderer: text::Renderer<Font = iced::Font>,
 {
     /// Calculate the X position of the cursor using cosmic-text for accurate measurement.
     ///
     /// This replaces the old approximation method that used a fixed 0.6 multiplier
     /// which failed badly with Unicode text, especially emoji and multi-byte characters.
-    fn cursor_x_position(&self, cursor_position: usize, _renderer: &Renderer) -> f32 {
+    fn cursor_x_position(&self, cursor_position: usize, renderer: &Renderer) -> f32 {
         if cursor_position == 0 || self.value.is_empty() {
             return 0.0;
         }
 
         let font_size = self.text_size.unwrap_or(Pixels(14.0)).0;
-        self.cursor_position_cosmic(&self.value, cursor_position, font_size)
+        let font = self.font.unwrap_or_else(|| renderer.default_font());
+        self.cursor_position_cosmic(&self.value, cursor_position, font_size, font)
     }
 
     /// Determine cursor position from X coordinate using cosmic-text for accuracy.
     ///
     /// This replaces the old method that divided by an approximated character width,
     /// which was completely wrong for variable-width fonts and Unicode text.
-    fn cursor_position_from_x(&self, x: f32, _renderer: &Renderer) -> usize {
+    fn cursor_position_from_x(&self, x: f32, renderer: &Renderer) -> usize {
         if self.value.is_empty() || x <= 0.0 {
             return 0;
         }
 
-        self.cursor_position_from_x_cosmic(x)
+        let font = self.font.unwrap_or_else(|| renderer.default_font());
+        self.cursor_position_from_x_cosmic(x, font)
     }
 
     /// Accurately calculate cursor X position using cosmic-text.
     ///
     /// This method uses proper text shaping to handle complex scripts, RTL text,
-    /// emoji, and variable-width fonts correctly.
-    fn cursor_position_cosmic(&self, text: &str, cursor_position: usize, font_size: f32) -> f32 {
+    /// emoji, and variable-width fonts correctly. `cursor_position` is a byte
+    /// offset like everywhere else; glyphs are walked by character. `font` is
+    /// the one the text is drawn in, so the caret lines up with the glyphs.
+    fn cursor_position_cosmic(
+        &self,
+        text: &str,
+        cursor_position: usize,
+        font_size: f32,
+        font: iced::Font,
+    ) -> f32 {
         if cursor_position == 0 || text.is_empty() {
             return 0.0;
         }
+        let cursor_position = byte_to_char(text, cursor_position);
 
-        let font_system = get_font_system();
-        let mut font_system = font_system.lock().unwrap();
-        let metrics = Metrics::new(font_size, font_size * 1.2);
-        let mut buffer = Buffer::new(&mut font_system, metrics);
-
-        let attrs = Attrs::new();
-        buffer.set_text(&mut font_system, text, &attrs, Shaping::Advanced);
-        buffer.shape_until_scroll(&mut font_system, true);
+        let Some(buffer) = shape_text(text, font_size, font) else {
+            return estimate_width(
+                &text.chars().take(cursor_position).collect::<String>(),
+                font_size,
+            );
+        };
 
         let mut char_index = 0;
         let mut x_position = 0.0;
@@ -825,17 +1011,14 @@ where
     /// Accurately determine cursor position from X coordinate using cosmic-text.
     ///
     /// This method properly handles glyph boundaries and multi-character glyphs,
-    /// providing accurate cursor positioning for all text types.
-    fn cursor_position_from_x_cosmic(&self, x: f32) -> usize {
-        let font_system = get_font_system();
-        let mut font_system = font_system.lock().unwrap();
+    /// providing accurate cursor positioning for all text types. Returns a
+    /// byte offset into the value.
+    fn cursor_position_from_x_cosmic(&self, x: f32, font: iced::Font) -> usize {
         let font_size = self.text_size.unwrap_or(Pixels(14.0)).0;
-        let metrics = Metrics::new(font_size, font_size * 1.2);
-        let mut buffer = Buffer::new(&mut font_system, metrics);
-
-        let attrs = Attrs::new();
-        buffer.set_text(&mut font_system, &self.value, &attrs, Shaping::Advanced);
-        buffer.shape_until_scroll(&mut font_system, true);
+        let Some(buffer) = shape_text(&self.value, font_size, font) else {
+            let estimated = (x / estimate_width("0", font_size)).round() as usize;
+            return char_to_byte(&self.value, estimated);
+        };
 
         let mut char_index = 0;
         let mut best_position = 0;
@@ -862,26 +1045,233 @@ where
                 if x >= glyph.x && x <= glyph.x + glyph.w && char_count > 1 {
                     let progress = (x - glyph.x) / glyph.w;
                     let chars_into_glyph = (progress * char_count as f32).round() as usize;
-                    return char_index + chars_into_glyph.min(char_count);
+                    return char_to_byte(
+                        &self.value,
+                        char_index + chars_into_glyph.min(char_count),
+                    );
                 }
 
                 char_index += char_count;
             }
         }
 
-        best_position.min(self.value.chars().count())
+        char_to_byte(&self.value, best_position)
     }
 }
 
+/// Largest character boundary at or before `position`
+fn floor_boundary(value: &str, position: usize) -> usize {
+    let mut position = position.min(value.len());
+    while !value.is_char_boundary(position) {
+        position -= 1;
+    }
+    position
+}
+
+/// Produces a [`Task`] that focuses the [`TextInputDropdown`] with the given [`Id`]
+pub fn focus<T>(id: impl Into<Id>) -> Task<T> {
+    task::effect(Action::widget(operation::focusable::focus(id.into())))
+}
+
+/// Height of the open list, capped so long lists scroll
+fn list_height(items: usize, item_height: f32) -> f32 {
+    (items as f32 * item_height).min(200.0)
+}
+
+/// Next item to highlight moving down or up, wrapping at the ends
+fn step_option(current: Option<usize>, len: usize, down: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    Some(match (current, down) {
+        (None, true) => 0,
+        (None, false) => len - 1,
+        (Some(index), true) => (index + 1) % len,
+        (Some(index), false) => (index + len - 1) % len,
+    })
+}
+
+/// Scroll offset that keeps item `index` fully inside the visible list
+fn scroll_into_view(offset: f32, index: usize, item_height: f32, visible: f32) -> f32 {
+    let top = index as f32 * item_height;
+    let bottom = top + item_height;
+    if top < offset {
+        top
+    } else if bottom > offset + visible {
+        (bottom - visible).max(0.0)
+    } else {
+        offset
+    }
+}
+
+/// Number of characters before byte offset `position`
+fn byte_to_char(value: &str, position: usize) -> usize {
+    value[..floor_boundary(value, position)].chars().count()
+}
+
+/// Byte offset of the `chars`th character, or the end of `value`
+fn char_to_byte(value: &str, chars: usize) -> usize {
+    value
+        .char_indices()
+        .nth(chars)
+        .map_or(value.len(), |(i, _)| i)
+}
+
+/// Insert `text` at `cursor`, returning the cursor after it
+fn insert_at(value: &mut String, cursor: usize, text: &str) -> usize {
+    let cursor = floor_boundary(value, cursor);
+    value.insert_str(cursor, text);
+    cursor + text.len()
+}
+
+/// Remove the character before `cursor` (Backspace), returning the new cursor
+fn delete_before(value: &mut String, cursor: usize) -> Option<usize> {
+    let cursor = floor_boundary(value, cursor);
+    let prev = prev_boundary(value, cursor);
+    (prev < cursor).then(|| {
+        value.replace_range(prev..cursor, "");
+        prev
+    })
+}
+
+/// Remove the character after `cursor` (Delete), returning the new cursor
+fn delete_after(value: &mut String, cursor: usize) -> Option<usize> {
+    let cursor = floor_boundary(value, cursor);
+    let next = next_boundary(value, cursor);
+    (cursor < next).then(|| {
+        value.replace_range(cursor..next, "");
+        cursor
+    })
+}
+
+/// Start of the character before `position`
+fn prev_boundary(value: &str, position: usize) -> usize {
+    value[..floor_boundary(value, position)]
+        .char_indices()
+        .next_back()
+        .map_or(0, |(i, _)| i)
+}
+
+/// End of the character after `position`
+fn next_boundary(value: &str, position: usize) -> usize {
+    let position = floor_boundary(value, position);
+    value[position..]
+        .chars()
+        .next()
+        .map_or(position, |c| position + c.len_utf8())
+}
+
+/// Start of the word before `position`, skipping any whitespace first
+fn prev_word(value: &str, position: usize) -> usize {
+    let before = value[..floor_boundary(value, position)].trim_end();
+    before
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map_or(0, |(i, c)| i + c.len_utf8())
+}
+
+/// End of the word after `position`, skipping any whitespace first
+fn next_word(value: &str, position: usize) -> usize {
+    let position = floor_boundary(value, position);
+    let after = &value[position..];
+    let start = position + after.len() - after.trim_start().len();
+    value[start..]
+        .find(char::is_whitespace)
+        .map_or(value.len(), |i| start + i)
+}
+
+/// The run of non-whitespace around `position`, so a double click selects a whole address
+fn word_at(value: &str, position: usize) -> Range<usize> {
+    let position = floor_boundary(value, position);
+    let is_space = |c: char| c.is_ascii_whitespace();
+    let start = value[..position].rfind(is_space).map_or(0, |i| i + 1);
+    let end = value[position..]
+        .find(is_space)
+        .map_or(value.len(), |i| position + i);
+    start..end
+}
+
+/// Insert clipboard text at `cursor`, returning the cursor after it
+///
+/// Line breaks and other control characters are dropped so a copied line
+/// with a trailing newline pastes as just the address.
+fn insert_pasted(value: &mut String, cursor: usize, pasted: &str) -> usize {
+    let pasted: String = pasted.chars().filter(|c| !c.is_control()).collect();
+    insert_at(value, cursor, &pasted)
+}
+
+/// Border of an invalid value unless [`TextInputDropdown::error_color`] is set
+const DEFAULT_ERROR_COLOR: Color = Color::from_rgb(0.8, 0.2, 0.2);
+
+/// Characters of `text` that `allowed` passes, or all of them without a filter
+fn filter_chars(text: &str, allowed: Option<fn(char) -> bool>) -> String {
+    match allowed {
+        Some(allowed) => text.chars().filter(|c| allowed(*c)).collect(),
+        None => text.to_string(),
+    }
+}
+
+/// Characters that can appear in an IPv4 or IPv6 address, for
+/// [`TextInputDropdown::input_filter`]
+pub fn is_ip_char(c: char) -> bool {
+    c.is_ascii_hexdigit() || c == '.' || c == ':'
+}
+
 // Global font system for cosmic-text - shared across all dropdown instances for performance
-static GLOBAL_FONT_SYSTEM: OnceLock<Arc<Mutex<FontSystem>>> = OnceLock::new();
+static GLOBAL_FONT_SYSTEM: OnceLock<Option<Arc<Mutex<FontSystem>>>> = OnceLock::new();
 
 /// Get the shared font system instance for cosmic-text operations.
 ///
 /// Using a global font system improves performance by avoiding repeated
 /// font loading and initialization across multiple dropdown instances.
-fn get_font_system() -> &'static Arc<Mutex<FontSystem>> {
-    GLOBAL_FONT_SYSTEM.get_or_init(|| Arc::new(Mutex::new(FontSystem::new())))
+/// Returns `None` if the font system failed to initialize.
+fn get_font_system() -> Option<&'static Arc<Mutex<FontSystem>>> {
+    GLOBAL_FONT_SYSTEM
+        .get_or_init(|| {
+            std::panic::catch_unwind(FontSystem::new)
+                .map(|font_system| Arc::new(Mutex::new(font_system)))
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Shape `text` in `font`'s family with the shared font system.
+///
+/// A poisoned mutex is recovered rather than propagated, and a panic during
+/// shaping is caught so one bad string can't take down every later cursor
+/// calculation. Returns `None` when shaping is unavailable.
+fn shape_text(text: &str, font_size: f32, font: iced::Font) -> Option<Buffer> {
+    let font_system = get_font_system()?;
+    let mut font_system = font_system.lock().unwrap_or_else(|e| e.into_inner());
+
+    std::panic::catch_unwind(AssertUnwindSafe(|| {
+        let metrics = Metrics::new(font_size, font_size * 1.2);
+        let mut buffer = Buffer::new(&mut font_system, metrics);
+
+        let attrs = Attrs::new().family(family(font.family));
+        buffer.set_text(&mut font_system, text, &attrs, Shaping::Advanced);
+        buffer.shape_until_scroll(&mut font_system, true);
+        buffer
+    }))
+    .ok()
+}
+
+/// The cosmic-text family matching an iced one
+fn family(family: iced::font::Family) -> Family<'static> {
+    match family {
+        iced::font::Family::Name(name) => Family::Name(name),
+        iced::font::Family::Serif => Family::Serif,
+        iced::font::Family::SansSerif => Family::SansSerif,
+        iced::font::Family::Cursive => Family::Cursive,
+        iced::font::Family::Fantasy => Family::Fantasy,
+        iced::font::Family::Monospace => Family::Monospace,
+    }
+}
+
+/// Rough text width used when cosmic-text shaping is unavailable.
+fn estimate_width(text: &str, font_size: f32) -> f32 {
+    text.chars().count() as f32 * font_size * 0.6
 }
 
 impl<'a, T, L, Message, Theme, Renderer> From<TextInputDropdown<'a, T, L, Message, Theme, Renderer>>
@@ -890,10 +1280,177 @@ where
     T: ToString + PartialEq + Clone + 'a,
     L: Borrow<[T]> + 'a + std::fmt::Debug,
     Theme: Catalog + iced::widget::text_input::Catalog + iced::widget::button::Catalog + 'a,
-    Renderer: text::Renderer + 'a,
+    Renderer: text::Renderer<Font = iced::Font> + 'a,
     Message: Clone + 'a,
 {
     fn from(dropdown: TextInputDropdown<'a, T, L, Message, Theme, Renderer>) -> Self {
         iced::Element::new(dropdown)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_pasted() {
+        let mut value = String::from("192.168.");
+        let cursor = insert_pasted(&mut value, 8, "1.20\r\n");
+        assert_eq!(value, "192.168.1.20");
+        assert_eq!(cursor, value.len());
+
+        // A stale cursor past the end pastes at the end
+        let mut value = String::from("10.");
+        assert_eq!(insert_pasted(&mut value, 99, "0.0.1"), 8);
+        assert_eq!(value, "10.0.0.1");
+    }
+
+    #[test]
+    fn test_shaping_uses_the_font_family() {
+        let width = |text, font| {
+            let buffer = shape_text(text, 14.0, font)?;
+            buffer.layout_runs().map(|run| run.line_w).reduce(f32::max)
+        };
+        // Without a font system or installed fonts there's nothing to compare
+        let (Some(narrow), Some(wide)) = (
+            width("iii", iced::Font::MONOSPACE),
+            width("WWW", iced::Font::MONOSPACE),
+        ) else {
+            return;
+        };
+        assert_eq!(narrow, wide);
+        assert_ne!(
+            width("iii", iced::Font::DEFAULT),
+            width("WWW", iced::Font::DEFAULT)
+        );
+    }
+
+    #[test]
+    fn test_input_filter() {
+        assert_eq!(
+            filter_chars("192.168.1.1x", Some(is_ip_char)),
+            "192.168.1.1"
+        );
+        assert_eq!(filter_chars("fe80::1 ", Some(is_ip_char)), "fe80::1");
+        assert_eq!(filter_chars("Office", None), "Office");
+        assert!(filter_chars("g", Some(is_ip_char)).is_empty());
+    }
+
+    #[test]
+    fn test_selection_editing() {
+        let mut value = String::from("192.168.1.1");
+        let mut state = State::new();
+        state.cursor_position = 8;
+        // Shift+Left twice selects "8."
+        state.move_cursor(7, true);
+        state.move_cursor(6, true);
+        assert_eq!(state.selection_range(), Some(6..8));
+        assert_eq!(state.selected_text(&value), Some("8."));
+
+        assert_eq!(state.delete_selection(&mut value).as_deref(), Some("8."));
+        assert_eq!(value, "192.161.1");
+        assert_eq!(state.cursor_position, 6);
+        assert_eq!(state.selection_range(), None);
+        assert_eq!(state.delete_selection(&mut value), None);
+
+        // Moving without shift drops the selection
+        state.move_cursor(0, true);
+        state.move_cursor(3, false);
+        assert_eq!(state.selection_range(), None);
+    }
+
+    #[test]
+    fn test_word_navigation() {
+        let value = "ping  10.0.0.1 now";
+        assert_eq!(prev_word(value, value.len()), 15);
+        assert_eq!(prev_word(value, 15), 6);
+        assert_eq!(prev_word(value, 8), 6);
+        assert_eq!(prev_word(value, 3), 0);
+        assert_eq!(next_word(value, 0), 4);
+        assert_eq!(next_word(value, 4), 14);
+        assert_eq!(next_word(value, 15), value.len());
+        assert_eq!(next_word(value, value.len()), value.len());
+    }
+
+    #[test]
+    fn test_char_boundaries() {
+        // "é" and "→" are multi-byte
+        let value = "é→a";
+        assert_eq!(next_boundary(value, 0), 2);
+        assert_eq!(next_boundary(value, 2), 5);
+        assert_eq!(next_boundary(value, 6), 6);
+        assert_eq!(prev_boundary(value, 5), 2);
+        assert_eq!(prev_boundary(value, 0), 0);
+        // A cursor stuck inside a character snaps back to its start
+        assert_eq!(floor_boundary(value, 3), 2);
+        assert_eq!(prev_word("é→ a", 7), 6);
+        assert_eq!(prev_word("é→ a", 6), 0);
+    }
+
+    #[test]
+    fn test_edit_around_emoji() {
+        // 🐒 is four bytes
+        let mut value = String::from("a🐒b");
+        let mut cursor = char_to_byte(&value, 2);
+        assert_eq!(cursor, 5);
+
+        cursor = insert_at(&mut value, cursor, "é");
+        assert_eq!(value, "a🐒éb");
+        assert_eq!(byte_to_char(&value, cursor), 3);
+
+        // Backspace twice removes é then the whole emoji
+        cursor = delete_before(&mut value, cursor).unwrap();
+        cursor = delete_before(&mut value, cursor).unwrap();
+        assert_eq!((value.as_str(), cursor), ("ab", 1));
+
+        // A cursor left inside the emoji by a stale position snaps to its start
+        let mut value = String::from("🐒🐒");
+        assert_eq!(delete_after(&mut value, 2), Some(0));
+        assert_eq!(value, "🐒");
+        assert_eq!(delete_after(&mut value, 4), None);
+        assert_eq!(delete_before(&mut value, 0), None);
+        assert_eq!(char_to_byte(&value, 9), value.len());
+    }
+
+    #[test]
+    fn test_step_option_wraps() {
+        assert_eq!(step_option(None, 3, true), Some(0));
+        assert_eq!(step_option(None, 3, false), Some(2));
+        assert_eq!(step_option(Some(2), 3, true), Some(0));
+        assert_eq!(step_option(Some(0), 3, false), Some(2));
+        assert_eq!(step_option(Some(1), 3, true), Some(2));
+        assert_eq!(step_option(None, 0, true), None);
+    }
+
+    #[test]
+    fn test_scroll_into_view() {
+        // Five 40px items visible at a time
+        assert_eq!(scroll_into_view(0.0, 2, 40.0, 200.0), 0.0);
+        assert_eq!(scroll_into_view(0.0, 7, 40.0, 200.0), 120.0);
+        assert_eq!(scroll_into_view(120.0, 1, 40.0, 200.0), 40.0);
+        // Wrapping back to the top resets the offset
+        assert_eq!(scroll_into_view(1000.0, 0, 40.0, 200.0), 0.0);
+    }
+
+    #[test]
+    fn test_unfocus_closes_list() {
+        let mut state = State::new();
+        Focusable::focus(&mut state);
+        state.is_open = true;
+        state.selection = Some(0);
+        state.cursor_position = 3;
+        assert!(Focusable::is_focused(&state));
+
+        state.unfocus();
+        assert!(!state.is_focused && !state.is_open);
+        assert_eq!(state.selection_range(), None);
+    }
+
+    #[test]
+    fn test_word_at() {
+        let value = "ping 10.0.0.1 now";
+        assert_eq!(&value[word_at(value, 8)], "10.0.0.1");
+        assert_eq!(&value[word_at(value, 0)], "ping");
+        assert_eq!(&value[word_at(value, value.len())], "now");
+    }
+}
@@ -45,6 +45,9 @@ pub struct LabelWithHint {
     width: Length,
     text_size: f32,
     padding: Padding,
+    hint_position: tooltip::Position,
+    hint_icon: char,
+    hint_icon_color: Option<Color>,
     text_color: Option<Color>,
     theme: ThemeProvider,
 }
@@ -61,6 +64,9 @@ impl LabelWithHint {
             hint_text: hint_text.into(),
             width: Length::Shrink,
             text_size: 14.0,
+            hint_icon: '?',
+            hint_icon_color: None,
+            hint_position: tooltip::Position::Left,
             padding: Padding::new(0.0),
             text_color: None,
             theme: ThemeProvider::default(),
@@ -85,6 +91,24 @@ impl LabelWithHint {
         self
     }
 
+    /// Sets the symbol in the help icon, e.g. 'i' for info or '!' for a caution
+    pub fn hint_icon(mut self, icon: char) -> Self {
+        self.hint_icon = icon;
+        self
+    }
+
+    /// Sets the help icon's circle color instead of the theme's primary color
+    pub fn hint_icon_color(mut self, color: Color) -> Self {
+        self.hint_icon_color = Some(color);
+        self
+    }
+
+    /// Sets which side of the help icon the tooltip appears on (default left)
+    pub fn hint_position(mut self, position: tooltip::Position) -> Self {
+        self.hint_position = position;
+        self
+    }
+
     /// Sets the text color for the label
     pub fn color(mut self, color: Color) -> Self {
         self.text_color = Some(color);
@@ -125,22 +149,30 @@ impl LabelWithHint {
         if !self.hint_text.is_empty() {
             let text_size = self.text_size;
             let hint_text = self.hint_text.clone();
+            let (icon_color, icon_border) = match self.hint_icon_color {
+                Some(color) => (color, color),
+                None => (colors.primary_color(), colors.primary_light()),
+            };
 
             // Create a simple help icon using container with NetMonkey theming
-            let help_icon = container(text("?").size(text_size * 0.8).color(Color::WHITE))
-                .width(Length::Fixed(text_size))
-                .height(Length::Fixed(text_size))
-                .padding(Padding::new(text_size * 0.1))
-                .style(move |_theme: &Theme| container::Style {
-                    background: Some(iced::Background::Color(colors.primary_color())),
-                    border: iced::Border {
-                        color: colors.primary_light(),
-                        width: 1.0,
-                        radius: (text_size / 2.0).into(),
-                    },
-                    text_color: Some(Color::WHITE),
-                    shadow: iced::Shadow::default(),
-                });
+            let help_icon = container(
+                text(self.hint_icon.to_string())
+                    .size(text_size * 0.8)
+                    .color(Color::WHITE),
+            )
+            .width(Length::Fixed(text_size))
+            .height(Length::Fixed(text_size))
+            .padding(Padding::new(text_size * 0.1))
+            .style(move |_theme: &Theme| container::Style {
+                background: Some(iced::Background::Color(icon_color)),
+                border: iced::Border {
+                    color: icon_border,
+                    width: 1.0,
+                    radius: (text_size / 2.0).into(),
+                },
+                text_color: Some(Color::WHITE),
+                shadow: iced::Shadow::default(),
+            });
 
             // Wrap help icon with tooltip using NetMonkey theming
             let help_icon_with_tooltip = tooltip(
@@ -161,7 +193,7 @@ impl LabelWithHint {
                             blur_radius: 8.0,
                         },
                     }),
-                tooltip::Position::Left,
+                self.hint_position,
             );
 
             // Use Fill width for label and Shrink for icon to push icon to the right
@@ -3,16 +3,22 @@
 //! This crate provides reusable UI components for the Net Monkey application,
 //! built with the Iced GUI framework.
 
+pub mod context_menu;
 pub mod dropdown;
 pub mod label_with_hint;
+pub mod number_input;
+pub mod range_input;
 pub mod selection_overlay;
 pub mod subnet_slider;
 pub mod text_input_with_hint;
 
 // Re-export commonly used components for convenience
-pub use dropdown::TextInputDropdown;
+pub use context_menu::ContextMenu;
+pub use dropdown::{TextInputDropdown, is_ip_char};
 pub use label_with_hint::LabelWithHint;
 pub use label_with_hint::{label_with_hint, themed_label_with_hint};
+pub use number_input::NumberInput;
+pub use range_input::{RangeInput, parse_range};
 pub use selection_overlay::MultiselectOverlay;
 pub use subnet_slider::SubnetSlider;
 pub use text_input_with_hint::TextInputWithHint;
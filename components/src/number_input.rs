@@ -0,0 +1,140 @@
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use iced::widget::{button, row, text, text_input};
+use iced::{Alignment, Element, Length, Renderer, Theme};
+use net_monkey_theme::ThemeProvider;
+use net_monkey_theme::helpers::{self, ButtonVariant};
+
+/// A numeric text input with - and + stepper buttons.
+///
+/// The steppers always stay within `range`. Typed values are passed on as
+/// entered so partially typed numbers aren't fought over, and the field is
+/// outlined in the danger color while the value is out of range.
+///
+/// # Examples
+/// ```rust,ignore
+/// let input = NumberInput::new(config.retries, 0..=10, Message::RetriesChanged)
+///     .step(1)
+///     .text_size(18.0)
+///     .theme(theme_provider);
+/// ```
+pub struct NumberInput<'a, Message> {
+    value: u64,
+    range: RangeInclusive<u64>,
+    step: u64,
+    on_change: Rc<dyn Fn(u64) -> Message + 'a>,
+    width: Length,
+    text_size: f32,
+    theme: ThemeProvider,
+}
+
+impl<'a, Message> NumberInput<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    /// Creates a new NumberInput component
+    ///
+    /// # Arguments
+    /// * `value` - Current value
+    /// * `range` - Valid values, enforced by the stepper buttons
+    /// * `on_change` - Called with the new value when stepped or typed
+    pub fn new<F>(value: u64, range: RangeInclusive<u64>, on_change: F) -> Self
+    where
+        F: Fn(u64) -> Message + 'a,
+    {
+        Self {
+            value,
+            range,
+            step: 1,
+            on_change: Rc::new(on_change),
+            width: Length::Fill,
+            text_size: 14.0,
+            theme: ThemeProvider::default(),
+        }
+    }
+
+    /// Sets the amount the stepper buttons change the value by
+    pub fn step(mut self, step: u64) -> Self {
+        self.step = step.max(1);
+        self
+    }
+
+    /// Sets the width of the component
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the text size for the input and buttons
+    pub fn text_size(mut self, size: f32) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    /// Sets the NetMonkey theme for the component
+    pub fn theme(mut self, theme: ThemeProvider) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Whether the current value lies within the range
+    pub fn is_valid(&self) -> bool {
+        self.range.contains(&self.value)
+    }
+
+    /// Converts the component into an Element
+    pub fn into_element(self) -> Element<'a, Message, Theme, Renderer> {
+        let (min, max) = (*self.range.start(), *self.range.end());
+        let clamped = self.value.clamp(min, max);
+        let invalid = !self.is_valid();
+        let danger = self.theme.colors().danger_color();
+
+        let decrement =
+            (clamped > min).then(|| (self.on_change)(clamped.saturating_sub(self.step).max(min)));
+        let increment =
+            (clamped < max).then(|| (self.on_change)(clamped.saturating_add(self.step).min(max)));
+
+        let on_change = self.on_change.clone();
+        let current = self.value;
+        let input = text_input("", &self.value.to_string())
+            .on_input(move |s| on_change(s.trim().parse().unwrap_or(current)))
+            .size(self.text_size)
+            .width(Length::Fill)
+            .style(move |theme, status| {
+                let mut style = text_input::default(theme, status);
+                if invalid {
+                    style.border.color = danger;
+                }
+                style
+            });
+
+        let stepper = |label: &'static str, msg: Option<Message>| {
+            button(text(label).size(self.text_size).center())
+                .style(helpers::themed_button(
+                    ButtonVariant::Secondary,
+                    &self.theme,
+                ))
+                .width(Length::Fixed(self.text_size * 2.0))
+                .on_press_maybe(msg)
+        };
+
+        row![stepper("-", decrement), input, stepper("+", increment)]
+            .spacing(4)
+            .align_y(Alignment::Center)
+            .width(self.width)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid() {
+        assert!(NumberInput::new(5, 1..=10, |v| v).is_valid());
+        assert!(!NumberInput::new(0, 1..=10, |v| v).is_valid());
+        assert!(!NumberInput::new(11, 1..=10, |v| v).is_valid());
+    }
+}
@@ -0,0 +1,191 @@
+use std::net::IpAddr;
+
+use iced::{Element, Length, Renderer, Theme};
+use net_monkey_core::{Ipv4Subnet, Ipv6Subnet};
+use net_monkey_theme::ThemeProvider;
+
+use crate::TextInputWithHint;
+
+type OnChange<'a, Message> = Box<dyn Fn(String, Option<(IpAddr, IpAddr)>) -> Message + 'a>;
+
+/// A text input for an address range, written as `start-end`, a CIDR block
+/// or a single address.
+///
+/// Every edit is passed on together with the range it resolves to, or `None`
+/// while it doesn't parse. Invalid text is flagged the way
+/// [`TextInputWithHint::error`] does, with the reason in the tooltip.
+///
+/// # Examples
+/// ```rust,ignore
+/// let input = RangeInput::new(&app.range_text, |text, range| {
+///     Message::RangeChanged(text, range)
+/// })
+/// .text_size(18.0)
+/// .theme(theme_provider);
+/// ```
+pub struct RangeInput<'a, Message> {
+    value: String,
+    on_change: OnChange<'a, Message>,
+    width: Length,
+    text_size: f32,
+    theme: ThemeProvider,
+}
+
+impl<'a, Message> RangeInput<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    /// Creates a new RangeInput component
+    ///
+    /// # Arguments
+    /// * `value` - Current text
+    /// * `on_change` - Called with the edited text and the first and last
+    ///   address it covers, if it's valid
+    pub fn new<F>(value: impl Into<String>, on_change: F) -> Self
+    where
+        F: Fn(String, Option<(IpAddr, IpAddr)>) -> Message + 'a,
+    {
+        Self {
+            value: value.into(),
+            on_change: Box::new(on_change),
+            width: Length::Fill,
+            text_size: 14.0,
+            theme: ThemeProvider::default(),
+        }
+    }
+
+    /// Sets the width of the component
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the text size for the input and help icon
+    pub fn text_size(mut self, size: f32) -> Self {
+        self.text_size = size;
+        self
+    }
+
+    /// Sets the NetMonkey theme for the component
+    pub fn theme(mut self, theme: ThemeProvider) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The range the current text resolves to
+    pub fn range(&self) -> Result<(IpAddr, IpAddr), String> {
+        parse_range(&self.value)
+    }
+
+    /// Converts the component into an Element
+    pub fn into_element(self) -> Element<'a, Message, Theme, Renderer> {
+        // Nothing typed yet isn't worth flagging
+        let error = match self.value.trim().is_empty() {
+            true => None,
+            false => self.range().err(),
+        };
+        let on_change = self.on_change;
+
+        TextInputWithHint::new(
+            self.value,
+            "192.168.1.10-192.168.1.50",
+            "A range like 192.168.1.10-192.168.1.50,\na CIDR block like 10.0.0.0/24, or one address",
+            move |text| {
+                let range = parse_range(&text).ok();
+                on_change(text, range)
+            },
+        )
+        .error(error)
+        .width(self.width)
+        .text_size(self.text_size)
+        .theme(self.theme)
+        .into_element()
+    }
+}
+
+/// First and last address of `start-end`, a CIDR block or a single address
+///
+/// A CIDR block covers its usable hosts, so an IPv4 /24 runs from .1 to
+/// .254. Fails with a reason suitable for showing next to the input.
+pub fn parse_range(text: &str) -> Result<(IpAddr, IpAddr), String> {
+    let text = text.trim();
+    if let Some((start, end)) = text.split_once('-') {
+        let (start, end) = (parse_ip(start)?, parse_ip(end)?);
+        if start.is_ipv4() != end.is_ipv4() {
+            return Err(String::from("Start and end must both be IPv4 or both IPv6"));
+        }
+        if start > end {
+            return Err(format!("{start} comes after {end}"));
+        }
+        return Ok((start, end));
+    }
+
+    let Some((ip, prefix)) = text.split_once('/') else {
+        let ip = parse_ip(text)?;
+        return Ok((ip, ip));
+    };
+    let ip = parse_ip(ip)?;
+    let max_prefix = match ip {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    };
+    let prefix = prefix
+        .trim()
+        .parse::<u8>()
+        .ok()
+        .filter(|prefix| *prefix <= max_prefix)
+        .ok_or_else(|| format!("Prefix must be 0-{max_prefix}"))?;
+    Ok(match ip {
+        IpAddr::V4(ip) => {
+            let (first, last) = Ipv4Subnet::containing(ip, prefix).host_range();
+            (first.into(), last.into())
+        }
+        IpAddr::V6(ip) => {
+            let (first, last) = Ipv6Subnet::containing(ip, prefix).host_range();
+            (first.into(), last.into())
+        }
+    })
+}
+
+fn parse_ip(text: &str) -> Result<IpAddr, String> {
+    let text = text.trim();
+    text.parse()
+        .map_err(|_| format!("\"{text}\" isn't an IP address"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(text: &str) -> IpAddr {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(
+            parse_range("192.168.1.10 - 192.168.1.50"),
+            Ok((ip("192.168.1.10"), ip("192.168.1.50")))
+        );
+        assert_eq!(
+            parse_range("10.0.0.77/24"),
+            Ok((ip("10.0.0.1"), ip("10.0.0.254")))
+        );
+        assert_eq!(
+            parse_range("fd00::/120"),
+            Ok((ip("fd00::1"), ip("fd00::ff")))
+        );
+        assert_eq!(
+            parse_range("10.0.0.5"),
+            Ok((ip("10.0.0.5"), ip("10.0.0.5")))
+        );
+
+        assert!(parse_range("192.168.1.50-192.168.1.10").is_err());
+        assert!(parse_range("192.168.1.1-fd00::1").is_err());
+        assert!(parse_range("10.0.0.0/33").is_err());
+        assert_eq!(
+            parse_range("192.168.1.300"),
+            Err(String::from("\"192.168.1.300\" isn't an IP address"))
+        );
+    }
+}
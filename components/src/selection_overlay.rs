@@ -1,4 +1,5 @@
 use iced::Pixels;
+use iced::overlay::menu;
 use iced::widget::pick_list::Catalog;
 use iced_core::clipboard::Clipboard;
 use iced_core::event::{self, Event};
@@ -9,6 +10,15 @@ use iced_core::renderer;
 use iced_core::text::{self};
 use iced_core::{Border, Color, Padding, Rectangle, Size, Vector};
 
+/// The dropdown list of a [`TextInputDropdown`](crate::TextInputDropdown)
+///
+/// Lists taller than `bounds` scroll with the mouse wheel. The hovered item
+/// and scroll offset live in the owning widget's state so they survive the
+/// overlay being rebuilt every frame.
+///
+/// By default a click selects an item and closes the list. With `on_toggle`
+/// set each row gets a check box showing whether it's in `selected`, and
+/// clicks toggle items while the list stays open.
 pub struct MultiselectOverlay<'a, T, Message, Theme, Renderer>
 where
     T: ToString + PartialEq + Clone,
@@ -17,6 +27,10 @@ where
 {
     pub items: Vec<T>,
     pub on_select: &'a dyn Fn(T) -> Message,
+    pub on_toggle: Option<&'a dyn Fn(T) -> Message>,
+    pub selected: &'a [T],
+    /// Items drawn faded, still selectable
+    pub dimmed: &'a [T],
     pub bounds: Rectangle,
     pub item_height: f32,
     pub text_size: Pixels,
@@ -25,7 +39,31 @@ where
     pub text_shaping: text::Shaping,
     pub font: Option<Renderer::Font>,
     pub class: <Theme as Catalog>::Class<'static>,
-    pub hovered_option: Option<usize>,
+    pub hovered_option: &'a mut Option<usize>,
+    pub scroll_offset: &'a mut f32,
+    pub is_open: &'a mut bool,
+}
+
+impl<T, Message, Theme, Renderer> MultiselectOverlay<'_, T, Message, Theme, Renderer>
+where
+    T: ToString + PartialEq + Clone,
+    Theme: Catalog + iced::widget::text_input::Catalog + iced::widget::button::Catalog,
+    Renderer: text::Renderer,
+{
+    /// Furthest the list can scroll before its last item reaches the bottom
+    fn max_scroll(&self, bounds: Rectangle) -> f32 {
+        max_scroll(self.items.len(), self.item_height, bounds.height)
+    }
+
+    /// Index of the item under `y`, accounting for the scroll offset
+    fn index_at(&self, bounds: Rectangle, y: f32) -> Option<usize> {
+        let index = ((y - bounds.y + *self.scroll_offset) / self.item_height) as usize;
+        (index < self.items.len()).then_some(index)
+    }
+}
+
+fn max_scroll(items: usize, item_height: f32, visible_height: f32) -> f32 {
+    (items as f32 * item_height - visible_height).max(0.0)
 }
 
 impl<'a, T, Message, Theme, Renderer> overlay::Overlay<Message, Theme, Renderer>
@@ -54,6 +92,9 @@ where
 
         let pick_list_style =
             <Theme as Catalog>::style(theme, &self.class, iced::widget::pick_list::Status::Active);
+        // Hovered rows use the same highlight as a pick list's menu
+        let menu_style =
+            <Theme as menu::Catalog>::style(theme, &<Theme as Catalog>::default_menu());
 
         // Draw dropdown background with shadow
         renderer.fill_quad(
@@ -73,72 +114,130 @@ where
             pick_list_style.background,
         );
 
-        // Draw dropdown items
-        for (index, item) in self.items.iter().enumerate() {
-            let item_y = bounds.y + (index as f32 * self.item_height);
-            let item_bounds = Rectangle {
-                x: bounds.x,
-                y: item_y,
-                width: bounds.width,
-                height: self.item_height,
-            };
-
-            // Check if we're past the visible area
-            if item_y + self.item_height > bounds.y + bounds.height {
-                break;
+        // Draw the visible dropdown items, clipping any partly scrolled out
+        let offset = self.scroll_offset.clamp(0.0, self.max_scroll(bounds));
+        renderer.with_layer(bounds, |renderer| {
+            for (index, item) in self.items.iter().enumerate() {
+                let item_y = bounds.y + (index as f32 * self.item_height) - offset;
+                let item_bounds = Rectangle {
+                    x: bounds.x,
+                    y: item_y,
+                    width: bounds.width,
+                    height: self.item_height,
+                };
+
+                if item_y + self.item_height < bounds.y {
+                    continue;
+                }
+                if item_y > bounds.y + bounds.height {
+                    break;
+                }
+
+                // Highlight hovered item
+                let is_hovered = *self.hovered_option == Some(index);
+                if is_hovered {
+                    renderer.fill_quad(
+                        renderer::Quad {
+                            bounds: item_bounds,
+                            border: Border::default(),
+                            shadow: iced_core::Shadow::default(),
+                        },
+                        menu_style.selected_background,
+                    );
+                }
+
+                let mut text_color = match is_hovered {
+                    true => menu_style.selected_text_color,
+                    false => pick_list_style.text_color,
+                };
+                if self.dimmed.contains(item) {
+                    text_color.a *= 0.5;
+                }
+
+                // Check box for multi-select rows, filled when selected
+                let mut text_x = item_bounds.x + self.padding.left;
+                if self.on_toggle.is_some() {
+                    let size = (self.text_size.0).min(item_bounds.height - 4.0);
+                    let check_bounds = Rectangle {
+                        x: text_x,
+                        y: item_bounds.center_y() - size / 2.0,
+                        width: size,
+                        height: size,
+                    };
+                    renderer.fill_quad(
+                        renderer::Quad {
+                            bounds: check_bounds,
+                            border: Border {
+                                color: text_color,
+                                width: 1.0,
+                                radius: iced::border::Radius::new(2.0),
+                            },
+                            shadow: iced_core::Shadow::default(),
+                        },
+                        iced_core::Background::Color(Color::TRANSPARENT),
+                    );
+                    if self.selected.contains(item) {
+                        renderer.fill_quad(
+                            renderer::Quad {
+                                bounds: check_bounds.shrink(3.0),
+                                border: Border {
+                                    radius: iced::border::Radius::new(1.0),
+                                    ..Border::default()
+                                },
+                                shadow: iced_core::Shadow::default(),
+                            },
+                            iced_core::Background::Color(text_color),
+                        );
+                    }
+                    text_x += size + 6.0;
+                }
+
+                // Draw item text
+                renderer.fill_text(
+                    text::Text {
+                        content: item.to_string(),
+                        size: self.text_size,
+                        line_height: self.text_line_height,
+                        font,
+                        bounds: item_bounds.size(),
+                        horizontal_alignment: iced::alignment::Horizontal::Left,
+                        vertical_alignment: iced::alignment::Vertical::Center,
+                        shaping: self.text_shaping,
+                        wrapping: text::Wrapping::None,
+                    },
+                    iced_core::Point::new(text_x, item_bounds.y)
+                        + Vector {
+                            x: 0.0,
+                            y: item_bounds.height / 2.0,
+                        },
+                    text_color,
+                    item_bounds,
+                );
             }
 
-            // Highlight hovered item
-            let is_hovered = self.hovered_option == Some(index);
-            if is_hovered {
+            // Scrollbar thumb showing which part of the list is visible
+            let max_scroll = self.max_scroll(bounds);
+            if max_scroll > 0.0 {
+                let content_height = bounds.height + max_scroll;
+                let thumb_height = bounds.height * bounds.height / content_height;
                 renderer.fill_quad(
                     renderer::Quad {
-                        bounds: item_bounds,
-                        border: Border::default(),
+                        bounds: Rectangle {
+                            x: bounds.x + bounds.width - 6.0,
+                            y: bounds.y + offset / max_scroll * (bounds.height - thumb_height),
+                            width: 4.0,
+                            height: thumb_height,
+                        },
+                        border: Border {
+                            radius: iced::border::Radius::new(2.0),
+                            ..Border::default()
+                        },
                         shadow: iced_core::Shadow::default(),
                     },
-                    {
-                        let item_bg_color = match pick_list_style.background {
-                            iced_core::Background::Color(color) => Color {
-                                r: color.r * 0.98,
-                                g: color.g * 0.98,
-                                b: color.b * 1.05,
-                                a: color.a,
-                            },
-                            _ => Color::from_rgb(0.9, 0.95, 1.0),
-                        };
-                        iced_core::Background::Color(item_bg_color)
-                    },
+                    iced_core::Background::Color(pick_list_style.text_color.scale_alpha(0.4)),
                 );
             }
-
-            let text_color = match is_hovered {
-                true => Color::BLACK,
-                false => pick_list_style.text_color,
-            };
-
-            // Draw item text
-            renderer.fill_text(
-                text::Text {
-                    content: item.to_string(),
-                    size: self.text_size,
-                    line_height: self.text_line_height,
-                    font,
-                    bounds: item_bounds.size(),
-                    horizontal_alignment: iced::alignment::Horizontal::Left,
-                    vertical_alignment: iced::alignment::Vertical::Center,
-                    shaping: self.text_shaping,
-                    wrapping: text::Wrapping::None,
-                },
-                iced_core::Point::new(item_bounds.x + self.padding.left, item_bounds.y)
-                    + Vector {
-                        x: 0.0,
-                        y: item_bounds.height / 2.0,
-                    },
-                text_color,
-                item_bounds,
-            );
-        }
+        });
     }
 
     fn on_event(
@@ -155,31 +254,46 @@ where
                 let bounds = layout.bounds();
                 if let Some(cursor_position) = cursor.position()
                     && bounds.contains(cursor_position)
+                    && let Some(clicked_index) = self.index_at(bounds, cursor_position.y)
                 {
-                    let clicked_index =
-                        ((cursor_position.y - bounds.y) / self.item_height) as usize;
-
-                    if clicked_index < self.items.len() {
-                        let selected_item = self.items[clicked_index].clone();
-                        shell.publish((self.on_select)(selected_item));
-                        return event::Status::Captured;
+                    let item = self.items[clicked_index].clone();
+                    match self.on_toggle {
+                        Some(on_toggle) => shell.publish(on_toggle(item)),
+                        None => {
+                            shell.publish((self.on_select)(item));
+                            *self.is_open = false;
+                        }
                     }
+                    return event::Status::Captured;
                 }
             }
             Event::Mouse(mouse::Event::CursorMoved { .. }) => {
                 let bounds = layout.bounds();
                 if let Some(cursor_position) = cursor.position() {
                     if bounds.contains(cursor_position) {
-                        let hovered_index =
-                            ((cursor_position.y - bounds.y) / self.item_height) as usize;
-
-                        if hovered_index < self.items.len() {
-                            self.hovered_option = Some(hovered_index);
+                        *self.hovered_option = self.index_at(bounds, cursor_position.y);
+                        if self.hovered_option.is_some() {
                             return event::Status::Captured;
                         }
                     }
                 } else {
-                    self.hovered_option = None;
+                    *self.hovered_option = None;
+                }
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                let bounds = layout.bounds();
+                if cursor.is_over(bounds) {
+                    let delta_y = match delta {
+                        mouse::ScrollDelta::Lines { y, .. } => y * self.item_height,
+                        mouse::ScrollDelta::Pixels { y, .. } => y,
+                    };
+                    *self.scroll_offset =
+                        (*self.scroll_offset - delta_y).clamp(0.0, self.max_scroll(bounds));
+                    // Keep the highlight under the cursor as the list moves
+                    if let Some(cursor_position) = cursor.position() {
+                        *self.hovered_option = self.index_at(bounds, cursor_position.y);
+                    }
+                    return event::Status::Captured;
                 }
             }
             _ => {}
@@ -202,3 +316,17 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_scroll() {
+        // 30 adapters at 40px in a 200px list
+        assert_eq!(max_scroll(30, 40.0, 200.0), 1000.0);
+        // Short lists don't scroll
+        assert_eq!(max_scroll(3, 40.0, 200.0), 0.0);
+        assert_eq!(max_scroll(0, 40.0, 0.0), 0.0);
+    }
+}
@@ -1,10 +1,15 @@
-use iced::mouse;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
 use iced::widget::canvas::{self, Canvas, Geometry, Path, Stroke, Text};
+use iced::widget::{column, text};
 use iced::{Color, Element, Length, Point, Rectangle, Renderer, Size};
+use iced::{keyboard, mouse};
+use net_monkey_core::{Ipv4Subnet, Ipv6Subnet};
 
 /// A custom subnet slider that looks like a progress bar with text overlay.
 /// The left side shows dotted decimal notation, right side shows CIDR notation,
-/// and the slider appears as a draggable blue filled area.
+/// and the slider appears as a draggable blue filled area. In IPv6 mode the
+/// slider spans /1 to /128 and the left side shows the mask as hex groups.
 ///
 /// Features:
 /// - Rounded corners and themed styling matching other components
@@ -28,9 +33,35 @@ use iced::{Color, Element, Length, Point, Rectangle, Renderer, Size};
 /// let slider = subnet_slider(16, |value| Message::SubnetChanged(value))
 ///     .text_size(18.0)    // Larger text
 ///     .height(50.0)       // Taller slider
+///     .show_host_count(true) // "254 hosts" in the filled bar
 ///     .width(Length::Fixed(400.0));
 /// ```
 ///
+/// ## IPv6 Prefixes
+/// ```rust,ignore
+/// // Shows "ffff:ffff:ffff:ffff::" on the left and "/64" on the right
+/// let slider = SubnetSlider::new(64, Message::PrefixChanged).ipv6(true);
+/// ```
+///
+/// ## Snapping to Common Prefixes
+/// ```rust,ignore
+/// // Holding Shift while dragging only lands on these prefixes
+/// let slider = SubnetSlider::new(24, Message::SubnetChanged).snap_prefixes(vec![8, 16, 24]);
+/// ```
+///
+/// ## Warning About Huge Ranges
+/// ```rust,ignore
+/// // The fill shades from primary toward danger below /22
+/// let slider = SubnetSlider::new(16, Message::SubnetChanged).warn_below_prefix(22);
+/// ```
+///
+/// ## With a Host Readout
+/// ```rust,ignore
+/// // Shows "host 137 of 254 · network 192.168.1.0 · broadcast 192.168.1.255" below
+/// let slider = SubnetSlider::new(24, Message::SubnetChanged)
+///     .host(Ipv4Addr::new(192, 168, 1, 137));
+/// ```
+///
 /// ## Visual Layout
 /// ```
 /// ⬜[255.255.255.0######         24]⬜
@@ -45,6 +76,11 @@ pub struct SubnetSlider<Message> {
     width: Length,
     height: f32,
     text_size: f32,
+    host: Option<Ipv4Addr>,
+    show_host_count: bool,
+    max_prefix: u8,
+    warn_below: Option<u8>,
+    snap_prefixes: Option<Vec<u8>>,
 }
 
 impl<Message> SubnetSlider<Message> {
@@ -54,14 +90,58 @@ impl<Message> SubnetSlider<Message> {
         F: Fn(u8) -> Message + 'static,
     {
         Self {
-            value: value.clamp(1, 32),
+            value,
             on_change: Box::new(on_change),
             width: Length::Fill,
             height: 40.0,
             text_size: 14.0,
+            host: None,
+            show_host_count: false,
+            max_prefix: 32,
+            warn_below: None,
+            snap_prefixes: None,
         }
     }
 
+    /// Span IPv6 prefixes (/1 to /128) instead of IPv4 ones
+    pub fn ipv6(mut self, ipv6: bool) -> Self {
+        self.max_prefix = if ipv6 { 128 } else { 32 };
+        self
+    }
+
+    /// The current prefix clamped to the slider's range
+    fn prefix(&self) -> u8 {
+        self.value.clamp(1, self.max_prefix)
+    }
+
+    /// Show the number of usable hosts in the middle of the filled bar
+    pub fn show_host_count(mut self, show: bool) -> Self {
+        self.show_host_count = show;
+        self
+    }
+
+    /// Shade the fill toward the theme's danger color for prefixes shorter
+    /// than `prefix`, as a warning before scanning a huge range
+    pub fn warn_below_prefix(mut self, prefix: u8) -> Self {
+        self.warn_below = Some(prefix);
+        self
+    }
+
+    /// Prefixes a drag snaps to while Shift is held
+    ///
+    /// Defaults to [`DEFAULT_SNAP_PREFIXES`], or [`DEFAULT_IPV6_SNAP_PREFIXES`]
+    /// in IPv6 mode.
+    pub fn snap_prefixes(mut self, prefixes: Vec<u8>) -> Self {
+        self.snap_prefixes = Some(prefixes);
+        self
+    }
+
+    /// Show where `host` sits in the selected subnet below the slider
+    pub fn host(mut self, host: Ipv4Addr) -> Self {
+        self.host = Some(host);
+        self
+    }
+
     /// Sets the width of the slider
     #[allow(dead_code)]
     pub fn width(mut self, width: Length) -> Self {
@@ -99,10 +179,61 @@ impl<Message> SubnetSlider<Message> {
         )
     }
 
+    /// Converts an IPv6 prefix length to its mask in hex groups
+    fn to_hex_mask(prefix: u8) -> String {
+        let prefix = prefix.clamp(1, 128);
+        Ipv6Addr::from(u128::MAX << (128 - u32::from(prefix))).to_string()
+    }
+
+    /// The mask shown on the left for this slider's address family
+    fn mask_label(prefix: u8, max_prefix: u8) -> String {
+        match max_prefix {
+            128 => Self::to_hex_mask(prefix),
+            _ => Self::to_dotted_decimal(prefix),
+        }
+    }
+
+    /// Usable hosts in a subnet of this size, e.g. "254 hosts" for a /24
+    ///
+    /// IPv6 counts too long to read are shown as a power of two.
+    fn host_count_label(prefix: u8, max_prefix: u8) -> String {
+        let count = match max_prefix {
+            128 => {
+                let prefix = prefix.clamp(1, 128);
+                if prefix < 108 {
+                    return format!("~2^{} hosts", 128 - prefix);
+                }
+                Ipv6Subnet::containing(Ipv6Addr::UNSPECIFIED, prefix).host_count()
+            }
+            _ => Ipv4Subnet::containing(Ipv4Addr::UNSPECIFIED, prefix.clamp(1, 32)).host_count()
+                as u128,
+        };
+        match count {
+            1 => String::from("1 host"),
+            count => format!("{count} hosts"),
+        }
+    }
+
+    /// Describes the host's position and the subnet boundaries around it
+    fn host_readout(host: Ipv4Addr, cidr: u8) -> String {
+        let subnet = Ipv4Subnet::containing(host, cidr);
+        let position = match subnet.offset_of(host) {
+            Some(0) if cidr < 31 => String::from("network address"),
+            _ if host == subnet.broadcast && cidr < 31 => String::from("broadcast address"),
+            Some(offset) if cidr < 31 => format!("host {offset} of {}", subnet.host_count()),
+            Some(offset) => format!("host {} of {}", offset + 1, subnet.host_count()),
+            None => unreachable!("a subnet always contains the address it was built from"),
+        };
+        format!(
+            "{host} is {position} · network {} · broadcast {}",
+            subnet.network, subnet.broadcast
+        )
+    }
+
     /// Calculates the fill percentage based on the current value
     #[allow(dead_code)]
     fn fill_percentage(&self) -> f32 {
-        (self.value as f32 - 1.0) / 31.0
+        fill_percentage(self.prefix(), self.max_prefix)
     }
 
     /// Convert to Element using Canvas
@@ -110,26 +241,112 @@ impl<Message> SubnetSlider<Message> {
     where
         Message: 'static + Clone,
     {
-        Canvas::new(SubnetSliderCanvas {
-            value: self.value,
+        let prefix = self.prefix();
+        let snap_prefixes = self.snap_prefixes.unwrap_or_else(|| match self.max_prefix {
+            128 => DEFAULT_IPV6_SNAP_PREFIXES.to_vec(),
+            _ => DEFAULT_SNAP_PREFIXES.to_vec(),
+        });
+        // A host readout only makes sense for IPv4 prefixes
+        let readout = self
+            .host
+            .filter(|_| self.max_prefix == 32)
+            .map(|host| text(Self::host_readout(host, prefix)).size(self.text_size * 0.75));
+        let slider = Canvas::new(SubnetSliderCanvas {
+            value: prefix,
+            max_prefix: self.max_prefix,
             on_change: self.on_change,
             text_size: self.text_size,
+            show_host_count: self.show_host_count,
+            warn_below: self.warn_below,
+            snap_prefixes,
         })
         .width(self.width)
-        .height(self.height)
-        .into()
+        .height(self.height);
+
+        match readout {
+            Some(readout) => column![slider, readout].spacing(4).width(self.width).into(),
+            None => slider.into(),
+        }
     }
 }
 
+/// Position of `prefix` along a slider spanning /1 to `/max_prefix`
+fn fill_percentage(prefix: u8, max_prefix: u8) -> f32 {
+    (prefix as f32 - 1.0) / (max_prefix as f32 - 1.0)
+}
+
+/// IPv4 prefixes a Shift-drag snaps to unless [`SubnetSlider::snap_prefixes`] is set
+pub const DEFAULT_SNAP_PREFIXES: [u8; 8] = [8, 16, 24, 25, 26, 27, 28, 30];
+
+/// IPv6 counterpart of [`DEFAULT_SNAP_PREFIXES`]
+pub const DEFAULT_IPV6_SNAP_PREFIXES: [u8; 6] = [32, 48, 56, 64, 112, 120];
+
+/// The entry of `snap` closest to `prefix`, or `prefix` itself if none fit
+/// the slider
+fn snap_prefix(prefix: u8, snap: &[u8], max_prefix: u8) -> u8 {
+    snap.iter()
+        .copied()
+        .filter(|candidate| (1..=max_prefix).contains(candidate))
+        .min_by_key(|candidate| candidate.abs_diff(prefix))
+        .unwrap_or(prefix)
+}
+
+/// Prefixes below the warning threshold before the fill is fully the danger
+/// color, each one doubling the range
+const WARN_SPAN: u8 = 8;
+
+/// How far `prefix` has gone past `warn_below`, from 0.0 at the threshold to
+/// 1.0 once it's [`WARN_SPAN`] prefixes shorter
+fn warning_level(prefix: u8, warn_below: Option<u8>) -> f32 {
+    match warn_below {
+        Some(warn_below) if prefix < warn_below => {
+            (f32::from(warn_below - prefix) / f32::from(WARN_SPAN)).min(1.0)
+        }
+        _ => 0.0,
+    }
+}
+
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    let lerp = |a: f32, b: f32| a * (1.0 - t) + b * t;
+    Color::from_rgba(
+        lerp(from.r, to.r),
+        lerp(from.g, to.g),
+        lerp(from.b, to.b),
+        lerp(from.a, to.a),
+    )
+}
+
+/// Prefix under a cursor `relative_x` of the way along the slider
+fn prefix_at(relative_x: f32, max_prefix: u8) -> u8 {
+    let prefix = 1.0 + relative_x.clamp(0.0, 1.0) * (max_prefix as f32 - 1.0);
+    (prefix.round() as u8).clamp(1, max_prefix)
+}
+
 struct SubnetSliderCanvas<Message> {
     value: u8,
+    max_prefix: u8,
     on_change: Box<dyn Fn(u8) -> Message>,
     text_size: f32,
+    show_host_count: bool,
+    warn_below: Option<u8>,
+    snap_prefixes: Vec<u8>,
+}
+
+impl<Message> SubnetSliderCanvas<Message> {
+    /// Prefix under the cursor, snapped to a common one while Shift is held
+    fn prefix_at(&self, state: &SubnetSliderState, relative_x: f32) -> u8 {
+        let prefix = prefix_at(relative_x, self.max_prefix);
+        match state.modifiers.shift() {
+            true => snap_prefix(prefix, &self.snap_prefixes, self.max_prefix),
+            false => prefix,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct SubnetSliderState {
     is_dragging: bool,
+    modifiers: keyboard::Modifiers,
 }
 
 impl<Message> canvas::Program<Message> for SubnetSliderCanvas<Message>
@@ -148,9 +365,12 @@ where
     ) -> Vec<Geometry> {
         let mut frame = canvas::Frame::new(renderer, bounds.size());
 
-        let dotted_decimal = SubnetSlider::<Message>::to_dotted_decimal(self.value);
-        let cidr_notation = format!("{}", self.value);
-        let fill_percentage = (self.value as f32 - 1.0) / 31.0;
+        let mask = SubnetSlider::<Message>::mask_label(self.value, self.max_prefix);
+        let cidr_notation = match self.max_prefix {
+            128 => format!("/{}", self.value),
+            _ => format!("{}", self.value),
+        };
+        let fill_percentage = fill_percentage(self.value, self.max_prefix);
 
         // Draw white outline background (1 pixel larger)
         let corner_radius = 4.0;
@@ -182,7 +402,13 @@ where
             Stroke::default().with_color(border_color).with_width(1.0),
         );
 
-        // Draw blue filled portion (progress) with rounded corners (inset by 1 pixel)
+        // Draw filled portion (progress) with rounded corners (inset by 1 pixel),
+        // shading toward danger for ranges too big to scan comfortably
+        let fill_color = mix(
+            theme.palette().primary,
+            theme.palette().danger,
+            warning_level(self.value, self.warn_below),
+        );
         let fill_width = (bounds.width - 2.0) * fill_percentage;
         if fill_width > 1.0 {
             let corner_radius = 4.0;
@@ -193,12 +419,12 @@ where
                     (corner_radius - 1.0_f32).max(0.0).into(),
                 );
             });
-            frame.fill(&fill_rect, theme.palette().primary);
+            frame.fill(&fill_rect, fill_color);
         }
 
-        // Draw left text (dotted decimal) - adjust for white outline
+        // Draw left text (dotted decimal or hex mask) - adjust for white outline
         frame.fill_text(Text {
-            content: dotted_decimal,
+            content: mask,
             position: Point::new(9.0, bounds.height / 2.0),
             color: theme.palette().text,
             size: iced::Pixels(self.text_size),
@@ -222,6 +448,21 @@ where
             shaping: Default::default(),
         });
 
+        // Draw host count centered in the filled portion
+        if self.show_host_count {
+            frame.fill_text(Text {
+                content: SubnetSlider::<Message>::host_count_label(self.value, self.max_prefix),
+                position: Point::new(1.0 + fill_width / 2.0, bounds.height / 2.0),
+                color: theme.palette().text,
+                size: iced::Pixels(self.text_size * 0.75),
+                font: Default::default(),
+                horizontal_alignment: iced::alignment::Horizontal::Center,
+                vertical_alignment: iced::alignment::Vertical::Center,
+                line_height: Default::default(),
+                shaping: Default::default(),
+            });
+        }
+
         vec![frame.into_geometry()]
     }
 
@@ -236,9 +477,7 @@ where
             canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
                 if let Some(cursor_position) = cursor.position_in(bounds) {
                     state.is_dragging = true;
-                    let relative_x = cursor_position.x / bounds.width;
-                    let new_value = (1.0_f32 + relative_x.clamp(0.0, 1.0) * 31.0).round() as u8;
-                    let new_value = new_value.clamp(1, 32);
+                    let new_value = self.prefix_at(state, cursor_position.x / bounds.width);
 
                     return (
                         canvas::event::Status::Captured,
@@ -246,6 +485,9 @@ where
                     );
                 }
             }
+            canvas::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.modifiers = modifiers;
+            }
             canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
                 state.is_dragging = false;
                 return (canvas::event::Status::Captured, None);
@@ -254,9 +496,7 @@ where
                 if state.is_dragging
                     && let Some(cursor_position) = cursor.position_in(bounds)
                 {
-                    let relative_x = cursor_position.x / bounds.width;
-                    let new_value = (1.0_f32 + relative_x.clamp(0.0, 1.0) * 31.0).round() as u8;
-                    let new_value = new_value.clamp(1, 32);
+                    let new_value = self.prefix_at(state, cursor_position.x / bounds.width);
 
                     return (
                         canvas::event::Status::Captured,
@@ -310,4 +550,76 @@ mod tests {
         let slider = SubnetSlider::<()>::new(32, |_| ());
         assert_eq!(slider.fill_percentage(), 1.0);
     }
+
+    #[test]
+    fn test_host_count_label() {
+        assert_eq!(SubnetSlider::<()>::host_count_label(24, 32), "254 hosts");
+        assert_eq!(SubnetSlider::<()>::host_count_label(16, 32), "65534 hosts");
+        assert_eq!(SubnetSlider::<()>::host_count_label(31, 32), "2 hosts");
+        assert_eq!(SubnetSlider::<()>::host_count_label(32, 32), "1 host");
+        assert_eq!(SubnetSlider::<()>::host_count_label(64, 128), "~2^64 hosts");
+        assert_eq!(SubnetSlider::<()>::host_count_label(120, 128), "255 hosts");
+        assert_eq!(SubnetSlider::<()>::host_count_label(128, 128), "1 host");
+    }
+
+    #[test]
+    fn test_ipv6_mode() {
+        assert_eq!(SubnetSlider::<()>::to_hex_mask(64), "ffff:ffff:ffff:ffff::");
+        assert_eq!(SubnetSlider::<()>::to_hex_mask(1), "8000::");
+        assert_eq!(SubnetSlider::<()>::mask_label(24, 32), "255.255.255.0");
+
+        let slider = SubnetSlider::<()>::new(64, |_| ()).ipv6(true);
+        assert_eq!(slider.prefix(), 64);
+        assert!((slider.fill_percentage() - 63.0 / 127.0).abs() < 0.001);
+        // Leaving IPv6 mode clamps back into the IPv4 range
+        assert_eq!(slider.ipv6(false).prefix(), 32);
+
+        assert_eq!(prefix_at(0.0, 128), 1);
+        assert_eq!(prefix_at(1.0, 128), 128);
+        assert_eq!(prefix_at(1.0, 32), 32);
+    }
+
+    #[test]
+    fn test_snap_prefix() {
+        assert_eq!(snap_prefix(13, &DEFAULT_SNAP_PREFIXES, 32), 16);
+        assert_eq!(snap_prefix(21, &DEFAULT_SNAP_PREFIXES, 32), 24);
+        assert_eq!(snap_prefix(29, &DEFAULT_SNAP_PREFIXES, 32), 28);
+        assert_eq!(snap_prefix(32, &DEFAULT_SNAP_PREFIXES, 32), 30);
+        assert_eq!(snap_prefix(62, &DEFAULT_IPV6_SNAP_PREFIXES, 128), 64);
+        // Nothing in range to snap to leaves the prefix alone
+        assert_eq!(snap_prefix(20, &[48, 64], 32), 20);
+    }
+
+    #[test]
+    fn test_warning_level() {
+        assert_eq!(warning_level(16, None), 0.0);
+        assert_eq!(warning_level(22, Some(22)), 0.0);
+        assert_eq!(warning_level(24, Some(22)), 0.0);
+        assert_eq!(warning_level(18, Some(22)), 0.5);
+        assert_eq!(warning_level(8, Some(22)), 1.0);
+
+        let (primary, danger) = (
+            Color::from_rgb(0.0, 0.0, 1.0),
+            Color::from_rgb(1.0, 0.0, 0.0),
+        );
+        assert_eq!(mix(primary, danger, 0.0), primary);
+        assert_eq!(mix(primary, danger, 1.0), danger);
+    }
+
+    #[test]
+    fn test_host_readout() {
+        let host = Ipv4Addr::new(192, 168, 1, 137);
+        assert_eq!(
+            SubnetSlider::<()>::host_readout(host, 24),
+            "192.168.1.137 is host 137 of 254 · network 192.168.1.0 · broadcast 192.168.1.255"
+        );
+        assert_eq!(
+            SubnetSlider::<()>::host_readout(host, 28),
+            "192.168.1.137 is host 9 of 14 · network 192.168.1.128 · broadcast 192.168.1.143"
+        );
+        assert!(
+            SubnetSlider::<()>::host_readout(Ipv4Addr::new(10, 0, 0, 255), 24)
+                .starts_with("10.0.0.255 is broadcast address")
+        );
+    }
 }
@@ -49,6 +49,10 @@ pub struct TextInputWithHint<'a, Message> {
     width: Length,
     text_size: f32,
     padding: Padding,
+    hint_position: tooltip::Position,
+    hint_icon: char,
+    hint_icon_color: Option<Color>,
+    error: Option<String>,
     theme: ThemeProvider,
 }
 
@@ -79,7 +83,11 @@ where
             on_input: Box::new(on_input),
             width: Length::Fill,
             text_size: 14.0,
+            hint_icon: '?',
+            hint_icon_color: None,
+            hint_position: tooltip::Position::Right,
             padding: Padding::new(8.0),
+            error: None,
             theme: ThemeProvider::default(),
         }
     }
@@ -102,6 +110,31 @@ where
         self
     }
 
+    /// Sets the symbol in the help icon, e.g. 'i' for info or '!' for a caution
+    pub fn hint_icon(mut self, icon: char) -> Self {
+        self.hint_icon = icon;
+        self
+    }
+
+    /// Sets the help icon's circle color instead of the theme's primary color
+    pub fn hint_icon_color(mut self, color: Color) -> Self {
+        self.hint_icon_color = Some(color);
+        self
+    }
+
+    /// Sets which side of the help icon the tooltip appears on (default right)
+    pub fn hint_position(mut self, position: tooltip::Position) -> Self {
+        self.hint_position = position;
+        self
+    }
+
+    /// Flags the value as invalid: the border and help icon turn the danger
+    /// color and the tooltip shows `error` instead of the hint
+    pub fn error(mut self, error: Option<String>) -> Self {
+        self.error = error;
+        self
+    }
+
     /// Sets the NetMonkey theme for the component
     ///
     /// This applies the appropriate color scheme including:
@@ -118,8 +151,17 @@ where
     }
 
     /// Converts the component into an Element
-    pub fn into_element(self) -> Element<'a, Message, Theme, Renderer> {
+    pub fn into_element(mut self) -> Element<'a, Message, Theme, Renderer> {
         let colors = self.theme.colors();
+        let border_color = match self.error.take() {
+            Some(error) => {
+                self.hint_text = error;
+                self.hint_icon = '!';
+                self.hint_icon_color = Some(colors.danger_color());
+                colors.danger_color()
+            }
+            None => colors.border_color(),
+        };
 
         let input = text_input(&self.placeholder, &self.value)
             .on_input(self.on_input)
@@ -129,22 +171,30 @@ where
         if !self.hint_text.is_empty() {
             let text_size = self.text_size;
             let hint_text = self.hint_text.clone();
+            let (icon_color, icon_border) = match self.hint_icon_color {
+                Some(color) => (color, color),
+                None => (colors.primary_color(), colors.primary_light()),
+            };
 
             // Create a simple help icon using container with NetMonkey theming
-            let help_icon = container(text("?").size(text_size * 0.8).color(Color::WHITE))
-                .width(Length::Fixed(text_size))
-                .height(Length::Fixed(text_size))
-                .padding(Padding::new(text_size * 0.1))
-                .style(move |_theme: &Theme| container::Style {
-                    background: Some(iced::Background::Color(colors.primary_color())),
-                    border: iced::Border {
-                        color: colors.primary_light(),
-                        width: 1.0,
-                        radius: (text_size / 2.0).into(),
-                    },
-                    text_color: Some(Color::WHITE),
-                    shadow: iced::Shadow::default(),
-                });
+            let help_icon = container(
+                text(self.hint_icon.to_string())
+                    .size(text_size * 0.8)
+                    .color(Color::WHITE),
+            )
+            .width(Length::Fixed(text_size))
+            .height(Length::Fixed(text_size))
+            .padding(Padding::new(text_size * 0.1))
+            .style(move |_theme: &Theme| container::Style {
+                background: Some(iced::Background::Color(icon_color)),
+                border: iced::Border {
+                    color: icon_border,
+                    width: 1.0,
+                    radius: (text_size / 2.0).into(),
+                },
+                text_color: Some(Color::WHITE),
+                shadow: iced::Shadow::default(),
+            });
 
             // Wrap help icon with tooltip using NetMonkey theming
             let help_icon_with_tooltip = tooltip(
@@ -165,7 +215,7 @@ where
                             blur_radius: 8.0,
                         },
                     }),
-                tooltip::Position::Right,
+                self.hint_position,
             );
 
             let content = row![input, help_icon_with_tooltip].spacing(8);
@@ -176,7 +226,7 @@ where
                 .style(move |_theme: &Theme| container::Style {
                     background: Some(iced::Background::Color(colors.background_color())),
                     border: iced::Border {
-                        color: colors.border_color(),
+                        color: border_color,
                         width: 1.0,
                         radius: 4.0.into(),
                     },
@@ -192,7 +242,7 @@ where
                 .style(move |_theme: &Theme| container::Style {
                     background: Some(iced::Background::Color(colors.background_color())),
                     border: iced::Border {
-                        color: colors.border_color(),
+                        color: border_color,
                         width: 1.0,
                         radius: 4.0.into(),
                     },
@@ -1,12 +1,22 @@
-use std::{fmt::Display, net::IpAddr};
+use std::{collections::HashMap, fmt::Display, net::IpAddr, time::Duration};
 
-use if_addrs::get_if_addrs;
+use futures::Stream;
+use if_addrs::{IfAddr, get_if_addrs};
+
+/// How often [`adapter_changes`] is usually polled
+pub const ADAPTER_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NetworkAdapter {
     pub name: String,
     pub ip_address: String,
     pub mac_address: String,
+    /// Prefix length of the adapter's subnet
+    pub prefix: u8,
+    /// Whether the link is up, assumed so where the OS can't tell us
+    pub is_up: bool,
+    /// Link speed in Mb/s, where the OS exposes it
+    pub speed_mbps: Option<u32>,
 }
 impl Default for NetworkAdapter {
     fn default() -> Self {
@@ -14,12 +24,20 @@ impl Default for NetworkAdapter {
             name: String::from("default"),
             ip_address: String::from("192.168.1.1"),
             mac_address: String::from(""),
+            prefix: 24,
+            is_up: true,
+            speed_mbps: None,
         }
     }
 }
 impl Display for NetworkAdapter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}] {}", self.ip_address, self.name)
+        write!(f, "[{}] {}", self.ip_address, self.name)?;
+        match (self.is_up, self.speed_mbps) {
+            (false, _) => write!(f, " (down)"),
+            (true, Some(speed)) => write!(f, " ({speed} Mb/s)"),
+            (true, None) => Ok(()),
+        }
     }
 }
 pub fn get_network_adapters() -> Vec<NetworkAdapter> {
@@ -29,6 +47,9 @@ pub fn get_network_adapters() -> Vec<NetworkAdapter> {
             if interfaces.is_empty() {
                 println!("No interfaces found")
             }
+            // PowerShell is slow to start, so ask about every adapter at once
+            #[cfg(target_os = "windows")]
+            let windows_adapters = get_adapters_windows();
             for interface in interfaces {
                 if !interface.is_loopback() {
                     let ip_address = match interface.ip() {
@@ -36,14 +57,35 @@ pub fn get_network_adapters() -> Vec<NetworkAdapter> {
                         IpAddr::V6(ipv6) => ipv6.to_string(),
                     };
 
-                    let mac_address = get_mac_address_for_interface(&interface.name);
+                    let prefix = match &interface.addr {
+                        IfAddr::V4(v4) => u32::from(v4.netmask).count_ones() as u8,
+                        IfAddr::V6(v6) => u128::from(v6.netmask).count_ones() as u8,
+                    };
+
+                    #[cfg(target_os = "windows")]
+                    let (mac_address, (is_up, speed_mbps)) =
+                        match windows_adapters.get(&interface.name.to_ascii_uppercase()) {
+                            Some(found) if !found.mac_address.is_empty() => {
+                                (found.mac_address.clone(), (found.is_up, found.speed_mbps))
+                            }
+                            found => (
+                                get_mac_address_windows(&interface.name),
+                                found.map_or((true, None), |found| (found.is_up, found.speed_mbps)),
+                            ),
+                        };
+                    #[cfg(not(target_os = "windows"))]
+                    let (mac_address, (is_up, speed_mbps)) = (
+                        get_mac_address_for_interface(&interface.name),
+                        get_link_status_for_interface(&interface.name),
+                    );
                     adapters.push(NetworkAdapter {
                         name: interface.name.clone(),
                         ip_address,
                         mac_address,
+                        prefix,
+                        is_up,
+                        speed_mbps,
                     });
-                } else {
-                    println!("Skipping loopback adapter {}", interface.ip())
                 }
             }
         }
@@ -55,11 +97,45 @@ pub fn get_network_adapters() -> Vec<NetworkAdapter> {
     adapters
 }
 
-fn get_mac_address_for_interface(interface_name: &str) -> String {
-    #[cfg(target_os = "windows")]
-    {
-        get_mac_address_windows(interface_name)
+/// Adapters as they change, checked every `interval`
+///
+/// Nothing is emitted until the list differs from the one found when the
+/// stream was first polled, such as when a cable is plugged in or Wi-Fi drops.
+pub fn adapter_changes(interval: Duration) -> impl Stream<Item = Vec<NetworkAdapter>> {
+    futures::stream::unfold(
+        None,
+        move |mut known: Option<Vec<NetworkAdapter>>| async move {
+            loop {
+                if known.is_some() {
+                    tokio::time::sleep(interval).await;
+                }
+                // Some platforms shell out to find MAC addresses, so keep off the async threads
+                let adapters = tokio::task::spawn_blocking(get_network_adapters)
+                    .await
+                    .unwrap_or_default();
+                if let Some(changed) = adapter_change(&mut known, adapters) {
+                    return Some((changed, known));
+                }
+            }
+        },
+    )
+}
+
+/// `adapters` if they differ from the `known` list, which they then replace
+///
+/// The first list seen only becomes the baseline.
+fn adapter_change(
+    known: &mut Option<Vec<NetworkAdapter>>,
+    adapters: Vec<NetworkAdapter>,
+) -> Option<Vec<NetworkAdapter>> {
+    match known.replace(adapters.clone()) {
+        Some(previous) if previous != adapters => Some(adapters),
+        _ => None,
     }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn get_mac_address_for_interface(interface_name: &str) -> String {
     #[cfg(target_os = "linux")]
     {
         get_mac_address_linux(interface_name)
@@ -68,32 +144,129 @@ fn get_mac_address_for_interface(interface_name: &str) -> String {
     {
         get_mac_address_macos(interface_name)
     }
-    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
     {
         "Unsupported OS".to_string()
     }
 }
 
+/// Whether the interface is up and its speed in Mb/s
+#[cfg(not(target_os = "windows"))]
+fn get_link_status_for_interface(interface_name: &str) -> (bool, Option<u32>) {
+    #[cfg(target_os = "linux")]
+    {
+        get_link_status_linux(interface_name)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        get_link_status_macos(interface_name)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = interface_name;
+        (true, None)
+    }
+}
+
+/// Link status from the contents of sysfs `operstate` and `speed`
+///
+/// Virtual interfaces often report an `unknown` operstate while passing
+/// traffic, and the speed reads as -1 or fails when the link is down.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_linux_link(operstate: &str, speed: Option<&str>) -> (bool, Option<u32>) {
+    let is_up = matches!(operstate.trim(), "up" | "unknown");
+    let speed = speed.and_then(|s| s.trim().parse().ok()).filter(|_| is_up);
+    (is_up, speed)
+}
+
+#[cfg(target_os = "linux")]
+fn get_link_status_linux(interface_name: &str) -> (bool, Option<u32>) {
+    use std::fs;
+
+    let read = |file: &str| fs::read_to_string(format!("/sys/class/net/{interface_name}/{file}"));
+    match read("operstate") {
+        Ok(operstate) => parse_linux_link(&operstate, read("speed").ok().as_deref()),
+        Err(_) => (true, None),
+    }
+}
+
+/// An adapter as listed by `Get-NetAdapter`
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WindowsAdapter {
+    mac_address: String,
+    is_up: bool,
+    speed_mbps: Option<u32>,
+}
+
+/// Adapters by upper case interface GUID, from `Get-NetAdapter` lines of
+/// `guid|status|receive speed|mac`
+///
+/// Status can be several words, such as "Not Present", hence the separator.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn parse_windows_adapters(output: &str) -> HashMap<String, WindowsAdapter> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().split('|');
+            let guid = fields.next().filter(|guid| !guid.is_empty())?;
+            let status = fields.next()?;
+            // Link speed is reported in bits per second
+            let speed_mbps = fields
+                .next()
+                .and_then(|bps| bps.parse::<u64>().ok())
+                .map(|bps| (bps / 1_000_000) as u32)
+                .filter(|&mbps| mbps > 0);
+            let adapter = WindowsAdapter {
+                mac_address: fields.next().unwrap_or_default().to_string(),
+                is_up: status == "Up",
+                speed_mbps,
+            };
+            Some((guid.to_ascii_uppercase(), adapter))
+        })
+        .collect()
+}
+
+/// Every adapter's MAC address and link status from a single PowerShell call
 #[cfg(target_os = "windows")]
-fn get_mac_address_windows(interface_name: &str) -> String {
+fn get_adapters_windows() -> HashMap<String, WindowsAdapter> {
     use std::process::Command;
 
-    let ps_script = format!(
-        "Get-NetAdapter | Where-Object {{ $_.InterfaceGuid -eq '{interface_name}' }} | Select-Object -ExpandProperty MacAddress",
-    );
+    let ps_script = "Get-NetAdapter | ForEach-Object { \"$($_.InterfaceGuid)|$($_.Status)|$($_.ReceiveLinkSpeed)|$($_.MacAddress)\" }";
 
-    let output = Command::new("powershell")
-        .args(["-Command", &ps_script])
-        .output();
+    match Command::new("powershell")
+        .args(["-Command", ps_script])
+        .output()
+    {
+        Ok(output) => parse_windows_adapters(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => HashMap::new(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_link_status_macos(interface_name: &str) -> (bool, Option<u32>) {
+    use std::process::Command;
+
+    let output = Command::new("ifconfig").arg(interface_name).output();
 
     if let Ok(output) = output {
         let output_str = String::from_utf8_lossy(&output.stdout);
-        let mac = output_str.trim();
-        if !mac.is_empty() && mac != "MacAddress" {
-            return mac.to_string();
+        for line in output_str.lines() {
+            if let Some(status) = line.trim().strip_prefix("status:") {
+                return (status.trim() == "active", None);
+            }
         }
     }
 
+    // Interfaces without a status line, such as tunnels, are up while configured
+    (true, None)
+}
+
+/// MAC address from WMI, for adapters `Get-NetAdapter` didn't list
+#[cfg(target_os = "windows")]
+fn get_mac_address_windows(interface_name: &str) -> String {
+    use std::process::Command;
+
     let wmi_query = format!(
         "wmic path win32_networkadapter where \"GUID='{interface_name}' and NetEnabled=true\" get MACAddress /format:list",
     );
@@ -148,3 +321,75 @@ fn get_mac_address_macos(interface_name: &str) -> String {
         Err(_) => "Error".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_linux_link() {
+        assert_eq!(parse_linux_link("up\n", Some("1000\n")), (true, Some(1000)));
+        // Wi-Fi and virtual interfaces don't report a speed
+        assert_eq!(parse_linux_link("unknown\n", Some("-1\n")), (true, None));
+        assert_eq!(parse_linux_link("up", None), (true, None));
+        // An unplugged port can still report its last negotiated speed
+        assert_eq!(parse_linux_link("down\n", Some("1000\n")), (false, None));
+    }
+
+    #[test]
+    fn test_parse_windows_adapters() {
+        let output = "{a1}|Up|1000000000|00-15-5D-01-02-03\r\n\
+                      {B2}|Not Present|0|\r\n\
+                      {C3}|Disconnected|100000000|00-15-5D-04-05-06\r\n";
+        let adapters = parse_windows_adapters(output);
+        assert_eq!(adapters.len(), 3);
+        assert_eq!(
+            adapters["{A1}"],
+            WindowsAdapter {
+                mac_address: String::from("00-15-5D-01-02-03"),
+                is_up: true,
+                speed_mbps: Some(1000),
+            }
+        );
+        assert!(!adapters["{B2}"].is_up);
+        assert_eq!(adapters["{B2}"].speed_mbps, None);
+        assert!(adapters["{B2}"].mac_address.is_empty());
+        assert!(!adapters["{C3}"].is_up);
+    }
+
+    #[test]
+    fn test_adapter_change_only_on_difference() {
+        let eth0 = NetworkAdapter {
+            name: String::from("eth0"),
+            ..Default::default()
+        };
+        let unplugged = NetworkAdapter {
+            is_up: false,
+            ..eth0.clone()
+        };
+        let mut known = None;
+
+        // The first poll is the baseline, not a change
+        assert_eq!(adapter_change(&mut known, vec![eth0.clone()]), None);
+        assert_eq!(adapter_change(&mut known, vec![eth0.clone()]), None);
+        assert_eq!(
+            adapter_change(&mut known, vec![unplugged.clone()]),
+            Some(vec![unplugged.clone()])
+        );
+        assert_eq!(adapter_change(&mut known, vec![unplugged]), None);
+        assert_eq!(adapter_change(&mut known, Vec::new()), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_display_shows_link() {
+        let mut adapter = NetworkAdapter {
+            name: String::from("eth0"),
+            ip_address: String::from("10.0.0.2"),
+            speed_mbps: Some(1000),
+            ..Default::default()
+        };
+        assert_eq!(adapter.to_string(), "[10.0.0.2] eth0 (1000 Mb/s)");
+        adapter.is_up = false;
+        assert_eq!(adapter.to_string(), "[10.0.0.2] eth0 (down)");
+    }
+}
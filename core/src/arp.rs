@@ -0,0 +1,145 @@
+//! MAC addresses of hosts on the local segment, read from the system ARP table
+//!
+//! Only hosts reachable without a router show up in the ARP table, so routed
+//! addresses and IPv6 hosts (which use neighbour discovery instead) get no MAC.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr};
+
+/// MAC address of `ip` as `AA:BB:CC:DD:EE:FF`, if the system has an ARP entry for it
+pub async fn lookup_mac(ip: IpAddr) -> Option<String> {
+    let IpAddr::V4(ip) = ip else {
+        return None;
+    };
+    tokio::task::spawn_blocking(move || arp_table().remove(&ip))
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Read the whole ARP table, `/proc/net/arp` on Linux and `arp -a` elsewhere
+fn arp_table() -> HashMap<Ipv4Addr, String> {
+    #[cfg(target_os = "linux")]
+    let table = std::fs::read_to_string("/proc/net/arp").ok();
+    #[cfg(not(target_os = "linux"))]
+    let table = std::process::Command::new("arp")
+        .arg("-a")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned());
+    table
+        .map(|table| parse_arp_table(&table))
+        .unwrap_or_default()
+}
+
+/// Parse ARP table text into IP to MAC pairs
+///
+/// Handles `/proc/net/arp`, BSD/macOS `arp -a` (`? (10.0.0.1) at 0:1a:...`)
+/// and Windows `arp -a` (`10.0.0.1  00-1a-...  dynamic`). Incomplete and
+/// broadcast entries are skipped.
+pub fn parse_arp_table(table: &str) -> HashMap<Ipv4Addr, String> {
+    table
+        .lines()
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let ip = tokens
+                .clone()
+                .find_map(|token| token.trim_matches(['(', ')']).parse().ok())?;
+            let mac = tokens.find_map(normalize_mac)?;
+            let unusable = mac == "00:00:00:00:00:00" || mac == "FF:FF:FF:FF:FF:FF";
+            (!unusable).then_some((ip, mac))
+        })
+        .collect()
+}
+
+/// `0:1a:2B:3c:4d:5e` or `00-1a-2b-3c-4d-5e` as `00:1A:2B:3C:4D:5E`
+fn normalize_mac(token: &str) -> Option<String> {
+    let octets: Vec<u8> = token
+        .split([':', '-'])
+        .map(|octet| match octet.len() {
+            1 | 2 => u8::from_str_radix(octet, 16).ok(),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    (octets.len() == 6).then(|| {
+        octets
+            .iter()
+            .map(|octet| format!("{octet:02X}"))
+            .collect::<Vec<_>>()
+            .join(":")
+    })
+}
+
+/// A small sample of OUI prefixes common on home and lab networks
+const OUI_VENDORS: &[(&str, &str)] = &[
+    ("00:03:93", "Apple"),
+    ("00:04:4B", "NVIDIA"),
+    ("00:05:69", "VMware"),
+    ("00:0C:29", "VMware"),
+    ("00:0D:B9", "PC Engines"),
+    ("00:11:32", "Synology"),
+    ("00:14:22", "Dell"),
+    ("00:15:5D", "Microsoft Hyper-V"),
+    ("00:16:3E", "Xen"),
+    ("00:17:88", "Philips Hue"),
+    ("00:1C:42", "Parallels"),
+    ("00:25:90", "Supermicro"),
+    ("00:50:56", "VMware"),
+    ("00:E0:4C", "Realtek"),
+    ("08:00:27", "VirtualBox"),
+    ("52:54:00", "QEMU/KVM"),
+    ("B8:27:EB", "Raspberry Pi"),
+    ("DC:A6:32", "Raspberry Pi"),
+    ("E4:5F:01", "Raspberry Pi"),
+];
+
+/// Vendor of a MAC address from its OUI prefix, when it's in the bundled table
+pub fn vendor(mac: &str) -> Option<&'static str> {
+    let prefix = normalize_mac(mac)?;
+    OUI_VENDORS
+        .iter()
+        .find(|(oui, _)| prefix.starts_with(oui))
+        .map(|(_, vendor)| *vendor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_net_arp() {
+        let table = "\
+IP address       HW type     Flags       HW address            Mask     Device
+192.168.1.1      0x1         0x2         b8:27:eb:12:34:56     *        eth0
+192.168.1.9      0x1         0x0         00:00:00:00:00:00     *        eth0
+";
+        let parsed = parse_arp_table(table);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[&Ipv4Addr::new(192, 168, 1, 1)], "B8:27:EB:12:34:56");
+    }
+
+    #[test]
+    fn test_parse_arp_a() {
+        let bsd = "? (10.0.0.1) at 0:c:29:a:b:c on en0 ifscope [ethernet]\n\
+                   ? (10.0.0.7) at (incomplete) on en0 ifscope [ethernet]";
+        let parsed = parse_arp_table(bsd);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[&Ipv4Addr::new(10, 0, 0, 1)], "00:0C:29:0A:0B:0C");
+
+        let windows = "Interface: 10.0.0.5 --- 0x4\n\
+                       \x20 Internet Address      Physical Address      Type\n\
+                       \x20 10.0.0.1              52-54-00-ab-cd-ef     dynamic\n\
+                       \x20 10.0.0.255            ff-ff-ff-ff-ff-ff     static";
+        let parsed = parse_arp_table(windows);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[&Ipv4Addr::new(10, 0, 0, 1)], "52:54:00:AB:CD:EF");
+    }
+
+    #[test]
+    fn test_vendor() {
+        assert_eq!(vendor("b8:27:eb:12:34:56"), Some("Raspberry Pi"));
+        assert_eq!(vendor("08-00-27-00-00-01"), Some("VirtualBox"));
+        assert_eq!(vendor("12:34:56:78:9A:BC"), None);
+        assert_eq!(vendor("not a mac"), None);
+    }
+}
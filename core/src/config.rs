@@ -0,0 +1,222 @@
+//! Tunable scan parameters
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Order in which hosts in a range are probed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScanOrder {
+    /// Lowest address first
+    #[default]
+    Sequential,
+    /// Shuffled, to spread load across a subnet and look less like a sweep
+    Random,
+}
+
+/// How a scan decides whether a host is up
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProbeMethod {
+    /// ICMP echo, which needs a raw socket and so often elevated privileges
+    #[default]
+    Icmp,
+    /// A completed TCP connection to one of the scanned ports, or to
+    /// [`FALLBACK_PORTS`](crate::scanner::FALLBACK_PORTS) when none are set.
+    /// Works without privileges but misses hosts with no open ports.
+    TcpConnect,
+}
+
+/// Knobs controlling how a scan probes hosts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScanConfig {
+    /// Maximum pings in flight at once
+    pub max_concurrent: usize,
+    /// How long to wait for each echo reply, or TCP connect; dead hosts wait
+    /// this long for every attempt, so keep it short on a LAN
+    pub timeout_ms: u64,
+    /// Extra attempts for hosts that didn't reply
+    pub retries: u8,
+    /// Echo requests sent to each host, averaged into its reported ping
+    pub pings_per_host: u8,
+    /// Pause between starting consecutive probes
+    pub packet_delay_ms: u64,
+    /// Most pings and TCP connects sent per second across the whole scan,
+    /// however many hosts are in flight, so a sweep doesn't burst past an
+    /// IDS. `None` sends them as fast as `max_concurrent` allows
+    pub max_pps: Option<u32>,
+    /// Bytes of payload in each echo request
+    pub payload_size: usize,
+    pub order: ScanOrder,
+    pub probe: ProbeMethod,
+    /// Report hosts that gave no answer as `Down` rather than leaving them
+    /// out, so a sweep accounts for every address
+    pub report_down: bool,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 128,
+            timeout_ms: 1000,
+            retries: 0,
+            pings_per_host: 1,
+            packet_delay_ms: 0,
+            max_pps: None,
+            payload_size: 0,
+            order: ScanOrder::Sequential,
+            probe: ProbeMethod::Icmp,
+            report_down: false,
+        }
+    }
+}
+
+impl ScanConfig {
+    pub const MAX_CONCURRENT: RangeInclusive<usize> = 1..=2048;
+    pub const TIMEOUT_MS: RangeInclusive<u64> = 50..=60_000;
+    pub const RETRIES: RangeInclusive<u8> = 0..=10;
+    pub const PINGS_PER_HOST: RangeInclusive<u8> = 1..=10;
+    pub const PACKET_DELAY_MS: RangeInclusive<u64> = 0..=10_000;
+    pub const MAX_PPS: RangeInclusive<u32> = 1..=100_000;
+    /// Largest payload that fits an unfragmented IPv4 echo on a 1500 byte MTU
+    pub const PAYLOAD_SIZE: RangeInclusive<usize> = 0..=1472;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = max_concurrent;
+        self
+    }
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_ms = timeout.as_millis() as u64;
+        self
+    }
+    pub fn retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+    pub fn pings_per_host(mut self, pings_per_host: u8) -> Self {
+        self.pings_per_host = pings_per_host;
+        self
+    }
+    pub fn packet_delay(mut self, delay: Duration) -> Self {
+        self.packet_delay_ms = delay.as_millis() as u64;
+        self
+    }
+    pub fn max_pps(mut self, max_pps: Option<u32>) -> Self {
+        self.max_pps = max_pps;
+        self
+    }
+    pub fn payload_size(mut self, payload_size: usize) -> Self {
+        self.payload_size = payload_size;
+        self
+    }
+    pub fn order(mut self, order: ScanOrder) -> Self {
+        self.order = order;
+        self
+    }
+    pub fn probe(mut self, probe: ProbeMethod) -> Self {
+        self.probe = probe;
+        self
+    }
+    pub fn report_down(mut self, report_down: bool) -> Self {
+        self.report_down = report_down;
+        self
+    }
+
+    /// Per-host reply timeout
+    pub fn timeout_duration(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    /// Delay between starting consecutive probes
+    pub fn packet_delay_duration(&self) -> Duration {
+        Duration::from_millis(self.packet_delay_ms)
+    }
+
+    /// Shortest gap between consecutive packets under `max_pps`
+    pub fn probe_interval(&self) -> Option<Duration> {
+        self.max_pps
+            .map(|pps| Duration::from_secs(1) / clamp(pps, &Self::MAX_PPS))
+    }
+
+    /// Copy with every field clamped into its supported range
+    pub fn validated(&self) -> Self {
+        Self {
+            max_concurrent: clamp(self.max_concurrent, &Self::MAX_CONCURRENT),
+            timeout_ms: clamp(self.timeout_ms, &Self::TIMEOUT_MS),
+            retries: clamp(self.retries, &Self::RETRIES),
+            pings_per_host: clamp(self.pings_per_host, &Self::PINGS_PER_HOST),
+            packet_delay_ms: clamp(self.packet_delay_ms, &Self::PACKET_DELAY_MS),
+            max_pps: self.max_pps.map(|pps| clamp(pps, &Self::MAX_PPS)),
+            payload_size: clamp(self.payload_size, &Self::PAYLOAD_SIZE),
+            order: self.order,
+            probe: self.probe,
+            report_down: self.report_down,
+        }
+    }
+
+    /// Zeroed echo payload of the configured size
+    pub fn payload(&self) -> Vec<u8> {
+        vec![0; self.payload_size]
+    }
+
+    /// Put `hosts` in the configured probe order
+    pub fn order_hosts<T>(&self, mut hosts: Vec<T>) -> Vec<T> {
+        if self.order == ScanOrder::Random {
+            fastrand::shuffle(&mut hosts);
+        }
+        hosts
+    }
+}
+
+fn clamp<T: Ord + Copy>(value: T, range: &RangeInclusive<T>) -> T {
+    value.clamp(*range.start(), *range.end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validated_clamps_ranges() {
+        let config = ScanConfig::new()
+            .max_concurrent(0)
+            .timeout(Duration::from_secs(600))
+            .retries(50)
+            .pings_per_host(0)
+            .payload_size(9000)
+            .max_pps(Some(0))
+            .validated();
+        assert_eq!(config.max_concurrent, 1);
+        assert_eq!(config.timeout_ms, 60_000);
+        assert_eq!(config.retries, 10);
+        assert_eq!(config.pings_per_host, 1);
+        assert_eq!(config.payload_size, 1472);
+        assert_eq!(config.max_pps, Some(1));
+
+        let config = ScanConfig::new().max_concurrent(5000).validated();
+        assert_eq!(config.max_concurrent, 2048);
+        assert_eq!(ScanConfig::default().validated(), ScanConfig::default());
+
+        assert_eq!(ScanConfig::default().probe_interval(), None);
+        assert_eq!(
+            ScanConfig::new().max_pps(Some(50)).probe_interval(),
+            Some(Duration::from_millis(20))
+        );
+    }
+
+    #[test]
+    fn test_order_hosts_keeps_every_host() {
+        let hosts: Vec<u32> = (0..256).collect();
+        assert_eq!(ScanConfig::new().order_hosts(hosts.clone()), hosts);
+
+        let mut shuffled = ScanConfig::new()
+            .order(ScanOrder::Random)
+            .order_hosts(hosts.clone());
+        shuffled.sort_unstable();
+        assert_eq!(shuffled, hosts);
+    }
+}
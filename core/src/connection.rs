@@ -0,0 +1,347 @@
+//! Client sockets behind the TCP and UDP tabs
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::time::Instant;
+
+/// How long to wait for a TCP connection to be accepted
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a sent datagram waits for a reply before `NoReply` is reported
+pub const REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Lifecycle and traffic of a client connection
+#[derive(Debug, Clone)]
+pub enum ConnectionEvent {
+    /// The connection is open and packets can be sent through the handle
+    Opened(ConnectionHandle),
+    /// A packet was written to the socket
+    Sent(Vec<u8>),
+    /// Data arrived from the peer
+    Received(Vec<u8>),
+    Error(String),
+    /// Nothing came back within [`REPLY_TIMEOUT`] of a datagram being sent
+    NoReply,
+    /// Always the last event, after the peer hung up, an error or every handle was dropped
+    Closed,
+}
+
+/// How packets typed into a client tab map to bytes on the wire
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PacketEncoding {
+    /// Text sent as-is, received bytes decoded lossily as UTF-8
+    #[default]
+    Ascii,
+    /// Whitespace separated hex bytes such as `de ad be ef`, received bytes shown as a hex dump
+    Hex,
+}
+
+impl PacketEncoding {
+    /// Bytes to send for `input`, or why it can't be sent
+    pub fn encode(&self, input: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Ascii => Ok(input.as_bytes().to_vec()),
+            Self::Hex => input
+                .split_whitespace()
+                .map(|byte| {
+                    let digits = byte.strip_prefix("0x").unwrap_or(byte);
+                    match digits.len() {
+                        1 | 2 => u8::from_str_radix(digits, 16).ok(),
+                        _ => None,
+                    }
+                    .ok_or_else(|| format!("{byte} isn't a hex byte"))
+                })
+                .collect(),
+        }
+    }
+
+    /// Display `bytes` for the connection history
+    pub fn render(&self, bytes: &[u8]) -> String {
+        match self {
+            Self::Ascii => String::from_utf8_lossy(bytes).into_owned(),
+            Self::Hex => hex_dump(bytes),
+        }
+    }
+}
+
+/// Offset, hex and printable ASCII columns, 16 bytes to a line
+fn hex_dump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(line, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02x}")).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| match b.is_ascii_graphic() || b == b' ' {
+                    true => b as char,
+                    false => '.',
+                })
+                .collect();
+            format!("{:04x}  {:<47}  {ascii}", line * 16, hex.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Queues packets for an open connection
+///
+/// Clones share the connection, which closes once every handle is dropped.
+#[derive(Debug, Clone)]
+pub struct ConnectionHandle {
+    packets: UnboundedSender<Vec<u8>>,
+}
+
+impl ConnectionHandle {
+    /// Queue `packet` to be sent, `false` if the connection has already closed
+    pub fn send(&self, packet: impl Into<Vec<u8>>) -> bool {
+        self.packets.send(packet.into()).is_ok()
+    }
+}
+
+/// Connect to `addr` over TCP and report what happens on the connection
+///
+/// The first event is `Opened`, or an `Error` if the connection couldn't be
+/// made within [`CONNECT_TIMEOUT`]. Each packet sent through the handle is
+/// echoed back as `Sent` once written.
+///
+/// # Example
+/// ```rust,no_run
+/// use net_monkey_core::{ConnectionEvent, create_tcp_client};
+///
+/// # async fn run() {
+/// let mut events = create_tcp_client("192.168.1.1:80".parse().unwrap()).await;
+/// while let Some(event) = events.recv().await {
+///     match event {
+///         ConnectionEvent::Opened(handle) => {
+///             handle.send("GET / HTTP/1.0\r\n\r\n");
+///         }
+///         ConnectionEvent::Received(data) => println!("{}", String::from_utf8_lossy(&data)),
+///         ConnectionEvent::Closed => break,
+///         _ => {}
+///     }
+/// }
+/// # }
+/// ```
+pub async fn create_tcp_client(addr: SocketAddr) -> UnboundedReceiver<ConnectionEvent> {
+    let (events, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        match tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(addr)).await {
+            Ok(Ok(stream)) => run_tcp(stream, &events).await,
+            Ok(Err(e)) => {
+                let _ = events.send(ConnectionEvent::Error(format!("Failed to connect: {e}")));
+            }
+            Err(_) => {
+                let _ = events.send(ConnectionEvent::Error(format!(
+                    "No answer from {addr} within {CONNECT_TIMEOUT:?}"
+                )));
+            }
+        }
+        let _ = events.send(ConnectionEvent::Closed);
+    });
+    rx
+}
+
+/// Bind a UDP socket aimed at `addr` and report datagrams sent and received
+///
+/// Events follow [`create_tcp_client`], except that UDP has no connection to
+/// lose: send and receive errors (such as an ICMP port unreachable) are
+/// reported and the socket stays open, and a datagram that gets no answer
+/// within [`REPLY_TIMEOUT`] is reported as `NoReply`.
+pub async fn create_udp_client(addr: SocketAddr) -> UnboundedReceiver<ConnectionEvent> {
+    let (events, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let local = match addr {
+            SocketAddr::V4(_) => SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)),
+            SocketAddr::V6(_) => SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0)),
+        };
+        let socket = match UdpSocket::bind(local).await {
+            Ok(socket) => socket,
+            Err(e) => {
+                let _ = events.send(ConnectionEvent::Error(format!("Failed to bind: {e}")));
+                let _ = events.send(ConnectionEvent::Closed);
+                return;
+            }
+        };
+        // Connecting filters out datagrams from anyone but `addr`
+        match socket.connect(addr).await {
+            Ok(()) => run_udp(socket, &events).await,
+            Err(e) => {
+                let _ = events.send(ConnectionEvent::Error(format!("Failed to connect: {e}")));
+            }
+        }
+        let _ = events.send(ConnectionEvent::Closed);
+    });
+    rx
+}
+
+/// Send queued datagrams and report replies until every handle is dropped
+async fn run_udp(socket: UdpSocket, events: &UnboundedSender<ConnectionEvent>) {
+    let (packets, mut outgoing) = mpsc::unbounded_channel::<Vec<u8>>();
+    let _ = events.send(ConnectionEvent::Opened(ConnectionHandle { packets }));
+    // Largest possible UDP payload
+    let mut buf = vec![0; 65_507];
+    let mut reply_deadline: Option<Instant> = None;
+    loop {
+        let waiting = async {
+            match reply_deadline {
+                Some(deadline) => tokio::time::sleep_until(deadline).await,
+                None => std::future::pending().await,
+            }
+        };
+        let event = tokio::select! {
+            packet = outgoing.recv() => match packet {
+                Some(packet) => match socket.send(&packet).await {
+                    Ok(_) => {
+                        reply_deadline.get_or_insert(Instant::now() + REPLY_TIMEOUT);
+                        ConnectionEvent::Sent(packet)
+                    }
+                    Err(e) => ConnectionEvent::Error(format!("Failed to send: {e}")),
+                },
+                None => return,
+            },
+            received = socket.recv(&mut buf) => {
+                reply_deadline = None;
+                match received {
+                    Ok(n) => ConnectionEvent::Received(buf[..n].to_vec()),
+                    Err(e) => ConnectionEvent::Error(format!("Failed to receive: {e}")),
+                }
+            },
+            () = waiting => {
+                reply_deadline = None;
+                ConnectionEvent::NoReply
+            },
+        };
+        if events.send(event).is_err() {
+            return;
+        }
+    }
+}
+
+/// Shuttle packets and replies until either side closes
+async fn run_tcp(stream: TcpStream, events: &UnboundedSender<ConnectionEvent>) {
+    let (packets, mut outgoing) = mpsc::unbounded_channel::<Vec<u8>>();
+    let _ = events.send(ConnectionEvent::Opened(ConnectionHandle { packets }));
+    let (mut reader, mut writer) = stream.into_split();
+    let mut buf = vec![0; 4096];
+    loop {
+        let event = tokio::select! {
+            packet = outgoing.recv() => match packet {
+                Some(packet) => match writer.write_all(&packet).await {
+                    Ok(()) => ConnectionEvent::Sent(packet),
+                    Err(e) => ConnectionEvent::Error(format!("Failed to send: {e}")),
+                },
+                None => return,
+            },
+            read = reader.read(&mut buf) => match read {
+                Ok(0) => return,
+                Ok(n) => ConnectionEvent::Received(buf[..n].to_vec()),
+                Err(e) => ConnectionEvent::Error(format!("Failed to receive: {e}")),
+            },
+        };
+        let failed = matches!(event, ConnectionEvent::Error(_));
+        if events.send(event).is_err() || failed {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tcp_client_round_trip() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut events = create_tcp_client(addr).await;
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        let Some(ConnectionEvent::Opened(handle)) = events.recv().await else {
+            panic!("the connection opens first");
+        };
+        assert!(handle.send("ping"));
+        assert!(matches!(events.recv().await, Some(ConnectionEvent::Sent(p)) if p == b"ping"));
+
+        let mut buf = [0; 4];
+        server.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+        server.write_all(b"pong").await.unwrap();
+        assert!(matches!(events.recv().await, Some(ConnectionEvent::Received(p)) if p == b"pong"));
+
+        // Dropping the last handle closes the connection
+        drop(handle);
+        assert!(matches!(events.recv().await, Some(ConnectionEvent::Closed)));
+    }
+
+    #[tokio::test]
+    async fn test_udp_client_round_trip_and_timeout() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut events = create_udp_client(server.local_addr().unwrap()).await;
+        let Some(ConnectionEvent::Opened(handle)) = events.recv().await else {
+            panic!("the socket opens first");
+        };
+
+        handle.send("ping");
+        assert!(matches!(events.recv().await, Some(ConnectionEvent::Sent(p)) if p == b"ping"));
+        let mut buf = [0; 16];
+        let (n, client) = server.recv_from(&mut buf).await.unwrap();
+        assert_eq!(&buf[..n], b"ping");
+        server.send_to(b"pong", client).await.unwrap();
+        assert!(matches!(events.recv().await, Some(ConnectionEvent::Received(p)) if p == b"pong"));
+
+        // The server stays quiet this time
+        handle.send("anyone?");
+        assert!(matches!(
+            events.recv().await,
+            Some(ConnectionEvent::Sent(_))
+        ));
+        assert!(matches!(
+            events.recv().await,
+            Some(ConnectionEvent::NoReply)
+        ));
+
+        drop(handle);
+        assert!(matches!(events.recv().await, Some(ConnectionEvent::Closed)));
+    }
+
+    #[test]
+    fn test_packet_encoding() {
+        assert_eq!(PacketEncoding::Ascii.encode("hi"), Ok(b"hi".to_vec()));
+        assert_eq!(
+            PacketEncoding::Hex.encode(" de AD\tbe 0xef 7 "),
+            Ok(vec![0xde, 0xad, 0xbe, 0xef, 0x07])
+        );
+        assert!(PacketEncoding::Hex.encode("de adbe").is_err());
+        assert!(PacketEncoding::Hex.encode("zz").is_err());
+
+        assert_eq!(PacketEncoding::Ascii.render(b"hi\xff"), "hi\u{fffd}");
+        assert_eq!(
+            PacketEncoding::Hex.render(b"GET /\r\n"),
+            format!("0000  {:<47}  GET /..", "47 45 54 20 2f 0d 0a")
+        );
+        let dump = PacketEncoding::Hex.render(&[0; 17]);
+        assert_eq!(dump.lines().count(), 2);
+        assert!(dump.lines().nth(1).unwrap().starts_with("0010  00 "));
+    }
+
+    #[tokio::test]
+    async fn test_tcp_client_refused() {
+        // Bind then drop to get a port that's almost certainly closed
+        let addr = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut events = create_tcp_client(addr).await;
+        assert!(matches!(
+            events.recv().await,
+            Some(ConnectionEvent::Error(_))
+        ));
+        assert!(matches!(events.recv().await, Some(ConnectionEvent::Closed)));
+    }
+}
@@ -0,0 +1,70 @@
+//! OS family hints derived from the TTL of ICMP replies
+//!
+//! Hosts start replies at a fixed TTL that differs between OS families, and
+//! each router on the way decrements it. Rounding the observed TTL up to the
+//! nearest common initial value gives a cheap, unreliable guess.
+
+use serde::{Deserialize, Serialize};
+
+/// Likely OS family of a host, guessed from its initial TTL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OsGuess {
+    /// Initial TTL of 64: Linux, macOS, BSD and most Unix-likes
+    Unix,
+    /// Initial TTL of 128: Windows
+    Windows,
+    /// Initial TTL of 255: routers, switches and other network gear
+    NetworkDevice,
+}
+
+impl OsGuess {
+    /// Guess the OS family from the TTL observed on a reply
+    ///
+    /// | Observed TTL | Initial TTL | Guess           |
+    /// |--------------|-------------|-----------------|
+    /// | 0            | -           | `None`          |
+    /// | 1..=64       | 64          | `Unix`          |
+    /// | 65..=128     | 128         | `Windows`       |
+    /// | 129..=255    | 255         | `NetworkDevice` |
+    pub fn from_ttl(ttl: u8) -> Option<Self> {
+        match ttl {
+            0 => None,
+            1..=64 => Some(Self::Unix),
+            65..=128 => Some(Self::Windows),
+            129..=255 => Some(Self::NetworkDevice),
+        }
+    }
+
+    /// Short label for display
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Unix => "Linux/Unix",
+            Self::Windows => "Windows",
+            Self::NetworkDevice => "Network device",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ttl_boundaries() {
+        assert_eq!(OsGuess::from_ttl(0), None);
+        assert_eq!(OsGuess::from_ttl(1), Some(OsGuess::Unix));
+        assert_eq!(OsGuess::from_ttl(64), Some(OsGuess::Unix));
+        assert_eq!(OsGuess::from_ttl(65), Some(OsGuess::Windows));
+        assert_eq!(OsGuess::from_ttl(128), Some(OsGuess::Windows));
+        assert_eq!(OsGuess::from_ttl(129), Some(OsGuess::NetworkDevice));
+        assert_eq!(OsGuess::from_ttl(255), Some(OsGuess::NetworkDevice));
+    }
+
+    #[test]
+    fn test_from_ttl_after_hops() {
+        // A Windows host three routers away
+        assert_eq!(OsGuess::from_ttl(125), Some(OsGuess::Windows));
+        // A Linux host on the local segment
+        assert_eq!(OsGuess::from_ttl(63), Some(OsGuess::Unix));
+    }
+}
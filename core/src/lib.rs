@@ -4,12 +4,36 @@
 //! including network adapter discovery, IP scanning, and related utilities.
 
 pub mod adaptor;
+pub mod arp;
+pub mod config;
+pub mod connection;
+pub mod fingerprint;
+pub mod metrics;
+pub mod processor;
+pub mod results;
 pub mod scanner;
+pub mod stats;
+pub mod subnet;
 pub mod tasks;
 
 // Re-export commonly used types for convenience
-pub use adaptor::{NetworkAdapter, get_network_adapters};
-pub use tasks::{Task, TaskMessage, TaskState};
+pub use adaptor::{ADAPTER_POLL_INTERVAL, NetworkAdapter, adapter_changes, get_network_adapters};
+pub use config::{ProbeMethod, ScanConfig, ScanOrder};
+pub use connection::{
+    ConnectionEvent, ConnectionHandle, PacketEncoding, create_tcp_client, create_udp_client,
+};
+pub use fingerprint::OsGuess;
+pub use metrics::to_prometheus;
+pub use processor::{BoxedProcessor, NoopProcessor, ResultProcessor};
+pub use results::{load_scan_results, save_scan_results};
+pub use stats::LatencyStats;
+pub use subnet::{Ipv4Subnet, Ipv6Subnet};
+pub use tasks::{ProgressReporter, Task, TaskMessage, TaskState, TaskStatus};
 
 // Re-export scanner functionality
-pub use scanner::{ScanMessage, ScannedIp, create_network_scanner, scan_network_async};
+pub use scanner::{
+    CancelToken, FALLBACK_PORTS, HostState, MIN_SCAN_PREFIX, ProbeError, ScanMessage, ScanRange,
+    ScanTarget, ScannedIp, create_multi_adapter_scanner, create_network_scanner,
+    create_network_scanner_with, create_range_scanner, ipv4_hosts, probe_ports, scan_network_async,
+    scan_single_host, scan_stream,
+};
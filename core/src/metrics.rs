@@ -0,0 +1,136 @@
+//! Prometheus text-format snapshot of scan results, for node_exporter's textfile collector
+
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::scanner::ScannedIp;
+
+/// Render `results` as Prometheus text exposition format
+///
+/// Every host gets a `net_monkey_host_up` sample (1 when reachable) and each
+/// open port a `net_monkey_port_open` sample. Replying hosts also report their
+/// round-trip time. `scraped_at` is written as a comment at the top.
+///
+/// ```text
+/// # Scraped at 1718000000 (unix seconds)
+/// # HELP net_monkey_host_up Whether the host responded to the scan
+/// # TYPE net_monkey_host_up gauge
+/// net_monkey_host_up{ip="192.168.1.10"} 1
+/// ```
+pub fn to_prometheus(results: &[ScannedIp], scraped_at: SystemTime) -> String {
+    let mut out = String::new();
+    let secs = scraped_at
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let _ = writeln!(out, "# Scraped at {secs} (unix seconds)");
+
+    header(
+        &mut out,
+        "net_monkey_host_up",
+        "Whether the host responded to the scan",
+    );
+    for host in results {
+        let up = host.state.is_alive() as u8;
+        let _ = writeln!(out, "net_monkey_host_up{{{}}} {up}", host_labels(host));
+    }
+
+    header(
+        &mut out,
+        "net_monkey_port_open",
+        "Ports that accepted a connection",
+    );
+    for host in results {
+        for port in &host.ports {
+            let _ = writeln!(
+                out,
+                "net_monkey_port_open{{{},port=\"{port}\"}} 1",
+                host_labels(host)
+            );
+        }
+    }
+
+    header(
+        &mut out,
+        "net_monkey_ping_seconds",
+        "Round-trip time of the host's echo reply",
+    );
+    for host in results.iter().filter(|host| host.alive) {
+        let seconds = host.ping as f64 / 1000.0;
+        let _ = writeln!(
+            out,
+            "net_monkey_ping_seconds{{{}}} {seconds}",
+            host_labels(host)
+        );
+    }
+    out
+}
+
+fn header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} gauge");
+}
+
+fn host_labels(host: &ScannedIp) -> String {
+    let mut labels = format!("ip=\"{}\"", escape_label(&host.ip.to_string()));
+    if let Some(interface) = &host.interface {
+        let _ = write!(labels, ",interface=\"{}\"", escape_label(interface));
+    }
+    labels
+}
+
+/// Escape a label value as the text format requires
+pub fn escape_label(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::Duration;
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("eth0"), "eth0");
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    #[test]
+    fn test_prometheus_snapshot() {
+        let up = ScannedIp::new(
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)),
+            true,
+            12,
+            vec![22, 80],
+        )
+        .with_interface("Wi-Fi \"home\"");
+        let down = ScannedIp::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 11)), false, 0, vec![]);
+        let at = UNIX_EPOCH + Duration::from_secs(1_718_000_000);
+
+        let text = to_prometheus(&[up, down], at);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "# Scraped at 1718000000 (unix seconds)");
+        assert!(lines.contains(
+            &"net_monkey_host_up{ip=\"192.168.1.10\",interface=\"Wi-Fi \\\"home\\\"\"} 1"
+        ));
+        assert!(lines.contains(&"net_monkey_host_up{ip=\"192.168.1.11\"} 0"));
+        assert!(lines.contains(
+            &"net_monkey_port_open{ip=\"192.168.1.10\",interface=\"Wi-Fi \\\"home\\\"\",port=\"80\"} 1"
+        ));
+        assert!(lines.contains(
+            &"net_monkey_ping_seconds{ip=\"192.168.1.10\",interface=\"Wi-Fi \\\"home\\\"\"} 0.012"
+        ));
+        assert!(!text.contains("net_monkey_ping_seconds{ip=\"192.168.1.11\""));
+        assert!(text.ends_with('\n'));
+    }
+}
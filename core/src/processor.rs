@@ -0,0 +1,86 @@
+//! Hook for post-processing scan results before they leave the scanner
+//!
+//! Embedders can implement [`ResultProcessor`] to annotate results (e.g. tag
+//! hosts from an asset database) or drop ones they aren't interested in.
+//!
+//! # Ordering
+//! * Each result is processed exactly once, on the scanning task, before it is
+//!   sent to the receiver.
+//! * Results are processed in the order their probes finish, not in address
+//!   order, so processors must not rely on seeing hosts sequentially.
+//! * [`ScanMessage::Complete`](crate::ScanMessage::Complete) is only sent after
+//!   every result has been processed.
+
+use crate::ScannedIp;
+use std::sync::Arc;
+
+/// Post-processes each scan result before it is emitted
+pub trait ResultProcessor: Send + Sync {
+    /// Modify a result in place, e.g. by adding tags
+    fn process(&self, scanned_ip: &mut ScannedIp);
+
+    /// Whether a processed result should be emitted at all
+    fn keep(&self, _scanned_ip: &ScannedIp) -> bool {
+        true
+    }
+}
+
+/// Default processor that leaves results untouched
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopProcessor;
+
+impl ResultProcessor for NoopProcessor {
+    fn process(&self, _scanned_ip: &mut ScannedIp) {}
+}
+
+/// Shared handle to a processor, cheap to clone into each probe
+pub type BoxedProcessor = Arc<dyn ResultProcessor>;
+
+/// Run `processor` over a result, returning it only if it should be emitted
+pub fn apply(processor: &dyn ResultProcessor, mut scanned_ip: ScannedIp) -> Option<ScannedIp> {
+    processor.process(&mut scanned_ip);
+    processor.keep(&scanned_ip).then_some(scanned_ip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    /// Tags a single known host and drops hosts with no open ports
+    struct TagKnownIp(IpAddr);
+
+    impl ResultProcessor for TagKnownIp {
+        fn process(&self, scanned_ip: &mut ScannedIp) {
+            if scanned_ip.ip == self.0 {
+                scanned_ip.tags.push(String::from("router"));
+            }
+        }
+        fn keep(&self, scanned_ip: &ScannedIp) -> bool {
+            !scanned_ip.ports.is_empty()
+        }
+    }
+
+    #[test]
+    fn test_processor_tags_known_ip() {
+        let router = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1));
+        let other = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+        let processor = TagKnownIp(router);
+
+        let tagged = apply(&processor, ScannedIp::new(router, true, 1, vec![80])).unwrap();
+        assert_eq!(tagged.tags, vec![String::from("router")]);
+
+        let untagged = apply(&processor, ScannedIp::new(other, true, 1, vec![80])).unwrap();
+        assert!(untagged.tags.is_empty());
+
+        assert!(apply(&processor, ScannedIp::new(other, true, 1, Vec::new())).is_none());
+    }
+
+    #[test]
+    fn test_noop_processor_keeps_result() {
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let result = apply(&NoopProcessor, ScannedIp::new(ip, true, 3, Vec::new())).unwrap();
+        assert_eq!(result.ip, ip);
+        assert!(result.tags.is_empty());
+    }
+}
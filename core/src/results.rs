@@ -0,0 +1,54 @@
+//! Keeping the last scan's results on disk between runs
+
+use std::path::Path;
+
+use crate::scanner::ScannedIp;
+
+/// Write `results` to `path` as JSON, creating its directory if needed
+pub fn save_scan_results(results: &[ScannedIp], path: impl AsRef<Path>) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string(results)?)?;
+    Ok(())
+}
+
+/// Results saved by [`save_scan_results`], or none if the file is missing or unreadable
+pub fn load_scan_results(path: impl AsRef<Path>) -> Vec<ScannedIp> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::*;
+
+    #[test]
+    fn test_results_round_trip() {
+        let dir = std::env::temp_dir().join(format!("net_monkey_results_{}", std::process::id()));
+        let path = dir.join("scan_results.json");
+        assert!(load_scan_results(&path).is_empty());
+
+        let results = vec![
+            ScannedIp::new(IpAddr::from([192, 168, 1, 1]), true, 3, vec![80, 443]),
+            ScannedIp::new(IpAddr::from([192, 168, 1, 2]), false, 0, vec![]),
+        ];
+        save_scan_results(&results, &path).unwrap();
+        let loaded = load_scan_results(&path);
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].ip, results[0].ip);
+        assert_eq!(loaded[0].ports, vec![80, 443]);
+        assert!(!loaded[1].alive);
+
+        // A corrupt cache is ignored rather than failing startup
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_scan_results(&path).is_empty());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
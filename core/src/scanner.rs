@@ -1,6 +1,107 @@
+use crate::NetworkAdapter;
+use crate::config::{ProbeMethod, ScanConfig};
+use crate::fingerprint::OsGuess;
+use crate::processor::{self, BoxedProcessor, NoopProcessor};
+use crate::stats::LatencyStats;
+use crate::subnet::{Ipv4Subnet, Ipv6Subnet};
+use futures::{FutureExt, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::net::{IpAddr, Ipv4Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+/// Reachability of a host, combining ICMP and TCP evidence
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HostState {
+    /// The host answered ICMP echo requests
+    Up,
+    /// Nothing answered
+    #[default]
+    Down,
+    /// ICMP went unanswered but a TCP port accepted, so the host is up behind a filter
+    Filtered,
+}
+
+impl HostState {
+    /// Combine probe outcomes into a host state
+    ///
+    /// | ICMP reply | TCP port open | State      |
+    /// |------------|---------------|------------|
+    /// | yes        | yes           | `Up`       |
+    /// | yes        | no            | `Up`       |
+    /// | no         | yes           | `Filtered` |
+    /// | no         | no            | `Down`     |
+    pub fn from_probes(icmp_reply: bool, tcp_open: bool) -> Self {
+        match (icmp_reply, tcp_open) {
+            (true, _) => Self::Up,
+            (false, true) => Self::Filtered,
+            (false, false) => Self::Down,
+        }
+    }
+
+    /// Whether the host should be treated as reachable
+    pub fn is_alive(&self) -> bool {
+        !matches!(self, Self::Down)
+    }
+
+    /// Short label for display
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Filtered => "filtered",
+        }
+    }
+}
+
+/// Why a host gave no answer
+///
+/// Ordered from least to most telling, so the `max` of several failures is
+/// the one worth reporting.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProbeError {
+    /// Nothing answered in time, the usual reason for an unused address
+    Timeout,
+    Other(String),
+    /// No route to the host or its network
+    Unreachable,
+    /// The OS refused the socket or send, raw ICMP usually needs admin rights
+    PermissionDenied,
+}
+
+impl ProbeError {
+    pub fn from_io(error: &std::io::Error) -> Self {
+        use std::io::ErrorKind;
+        match error.kind() {
+            ErrorKind::TimedOut => Self::Timeout,
+            ErrorKind::PermissionDenied => Self::PermissionDenied,
+            ErrorKind::NetworkUnreachable | ErrorKind::HostUnreachable => Self::Unreachable,
+            _ => Self::Other(error.to_string()),
+        }
+    }
+
+    fn from_ping(error: &surge_ping::SurgeError) -> Self {
+        match error {
+            surge_ping::SurgeError::Timeout { .. } => Self::Timeout,
+            surge_ping::SurgeError::IOError(error) => Self::from_io(error),
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout => write!(f, "timed out"),
+            Self::Other(reason) => write!(f, "{reason}"),
+            Self::Unreachable => write!(f, "network unreachable"),
+            Self::PermissionDenied => write!(f, "permission denied"),
+        }
+    }
+}
 
 /// Result of scanning a single IP address
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,19 +110,85 @@ pub struct ScannedIp {
     pub ip: IpAddr,
     pub ping: u128,
     pub ports: Vec<u16>,
+    #[serde(default)]
+    pub state: HostState,
+    /// Raw round-trip time of every reply received
+    #[serde(default)]
+    pub samples: Vec<Duration>,
+    /// TTL of the ICMP reply, when the platform reports it
+    #[serde(default)]
+    pub ttl: Option<u8>,
+    /// OS family guessed from `ttl`
+    #[serde(default)]
+    pub os_hint: Option<OsGuess>,
+    /// Local interface the host was found from, for multi-adapter scans
+    #[serde(default)]
+    pub interface: Option<String>,
+    /// Free-form annotations added by a [`ResultProcessor`](crate::ResultProcessor)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Hardware address from the ARP table, only known for hosts on the local segment
+    #[serde(default)]
+    pub mac: Option<String>,
 }
 
 impl ScannedIp {
     /// Create a new ScannedIp result
+    ///
+    /// `alive` is whether the host answered ICMP; the host state is derived
+    /// from that together with whether any of `ports` accepted a connection.
     pub fn new(ip: IpAddr, alive: bool, ping: u128, ports: Vec<u16>) -> Self {
+        let state = HostState::from_probes(alive, !ports.is_empty());
         Self {
-            alive,
+            alive: state.is_alive(),
             ip,
             ping,
             ports,
+            state,
+            samples: Vec::new(),
+            ttl: None,
+            os_hint: None,
+            interface: None,
+            tags: Vec::new(),
+            mac: None,
         }
     }
 
+    /// Record the raw round-trip times behind `ping`
+    pub fn with_samples(mut self, samples: Vec<Duration>) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Percentile summary of the ping samples, if there are any
+    pub fn latency_stats(&self) -> Option<LatencyStats> {
+        LatencyStats::from_samples(&self.samples)
+    }
+
+    /// Record the reply TTL and the OS guess it implies
+    pub fn with_ttl(mut self, ttl: Option<u8>) -> Self {
+        self.ttl = ttl;
+        self.os_hint = ttl.and_then(OsGuess::from_ttl);
+        self
+    }
+
+    /// Record the host's hardware address
+    pub fn with_mac(mut self, mac: Option<String>) -> Self {
+        self.mac = mac;
+        self
+    }
+
+    /// Network card vendor, looked up from the MAC address prefix
+    pub fn vendor(&self) -> Option<&'static str> {
+        crate::arp::vendor(self.mac.as_deref()?)
+    }
+
+    /// Tag the result with the local interface it was found from
+    pub fn with_interface(mut self, interface: impl Into<String>) -> Self {
+        self.interface = Some(interface.into());
+        self
+    }
+
     /// Convert ports vector to display string
     pub fn ports_to_string(&self) -> String {
         match self.ports.is_empty() {
@@ -36,31 +203,169 @@ impl ScannedIp {
     }
 }
 
+/// Narrowest prefix a single IPv4 scan will sweep; a /16 is 65,534 hosts
+pub const MIN_SCAN_PREFIX: u8 = 16;
+
+/// Most addresses probed in an IPv6 scan or an explicit span, matching an
+/// IPv4 /16
+pub const MAX_IPV6_HOSTS: usize = 65_534;
+
+/// The addresses a single-range scan sweeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanRange {
+    V4(Ipv4Subnet),
+    /// Only the first [`MAX_IPV6_HOSTS`] addresses are probed
+    V6(Ipv6Subnet),
+    /// Every address from `start` to `end` inclusive, both of one family
+    Span {
+        start: IpAddr,
+        end: IpAddr,
+    },
+}
+
+impl ScanRange {
+    /// The range a scan of `base`/`prefix` covers
+    ///
+    /// IPv4 prefixes wider than [`MIN_SCAN_PREFIX`] are narrowed to it, and
+    /// IPv6 scans stop after [`MAX_IPV6_HOSTS`], with a warning either way
+    /// rather than sweeping millions of addresses.
+    pub fn new(base: IpAddr, prefix: u8) -> Self {
+        match base {
+            IpAddr::V4(base) => {
+                if prefix < MIN_SCAN_PREFIX {
+                    eprintln!(
+                        "{base}/{prefix} is too large to sweep, scanning {base}/{MIN_SCAN_PREFIX} instead"
+                    );
+                }
+                Self::V4(Ipv4Subnet::containing(base, prefix.max(MIN_SCAN_PREFIX)))
+            }
+            IpAddr::V6(base) => {
+                let subnet = Ipv6Subnet::containing(base, prefix);
+                if subnet.host_count() > MAX_IPV6_HOSTS as u128 {
+                    eprintln!(
+                        "{base}/{prefix} is too large to sweep, scanning its first {MAX_IPV6_HOSTS} addresses"
+                    );
+                }
+                Self::V6(subnet)
+            }
+        }
+    }
+
+    /// The addresses from `start` to `end` inclusive
+    ///
+    /// Fails if they're of different families or `start` comes after `end`.
+    /// Spans longer than [`MAX_IPV6_HOSTS`] stop after that many addresses,
+    /// with a warning.
+    pub fn between(start: IpAddr, end: IpAddr) -> Result<Self, String> {
+        if start.is_ipv4() != end.is_ipv4() {
+            return Err(format!("{start} and {end} aren't both IPv4 or both IPv6"));
+        }
+        if start > end {
+            return Err(format!("{start} comes after {end}"));
+        }
+        let span = Self::Span { start, end };
+        if span_len(start, end) > MAX_IPV6_HOSTS as u128 {
+            eprintln!(
+                "{start}-{end} is too large to sweep, scanning its first {MAX_IPV6_HOSTS} addresses"
+            );
+        }
+        Ok(span)
+    }
+
+    /// Number of addresses that will be probed
+    pub fn host_count(&self) -> usize {
+        match self {
+            Self::V4(subnet) => subnet.host_count() as usize,
+            Self::V6(subnet) => subnet.host_count().min(MAX_IPV6_HOSTS as u128) as usize,
+            Self::Span { start, end } => {
+                span_len(*start, *end).min(MAX_IPV6_HOSTS as u128) as usize
+            }
+        }
+    }
+
+    /// ICMP socket settings for pinging this range's address family
+    fn socket_config(&self) -> surge_ping::Config {
+        let kind = match self {
+            Self::V4(_)
+            | Self::Span {
+                start: IpAddr::V4(_),
+                ..
+            } => surge_ping::ICMP::V4,
+            Self::V6(_) | Self::Span { .. } => surge_ping::ICMP::V6,
+        };
+        surge_ping::Config::builder().kind(kind).build()
+    }
+
+    /// Addresses to probe, lowest first
+    pub fn hosts(&self) -> Box<dyn Iterator<Item = IpAddr> + Send> {
+        match *self {
+            Self::V4(subnet) => Box::new(subnet.hosts().map(IpAddr::V4)),
+            Self::V6(subnet) => Box::new(subnet.hosts(MAX_IPV6_HOSTS).map(IpAddr::V6)),
+            Self::Span { start, .. } => {
+                let first = ip_to_u128(start);
+                let addrs = (0..self.host_count() as u128).map(move |n| first + n);
+                match start {
+                    IpAddr::V4(_) => Box::new(addrs.map(|n| IpAddr::V4(Ipv4Addr::from(n as u32)))),
+                    IpAddr::V6(_) => Box::new(addrs.map(|n| IpAddr::V6(Ipv6Addr::from(n)))),
+                }
+            }
+        }
+    }
+}
+
+fn ip_to_u128(ip: IpAddr) -> u128 {
+    match ip {
+        IpAddr::V4(ip) => u32::from(ip) as u128,
+        IpAddr::V6(ip) => u128::from(ip),
+    }
+}
+
+/// Addresses from `start` to `end` inclusive, saturating for all of IPv6
+fn span_len(start: IpAddr, end: IpAddr) -> u128 {
+    (ip_to_u128(end) - ip_to_u128(start)).saturating_add(1)
+}
+
 /// Scan a network range for alive hosts
 ///
-/// This function performs ping scans on IP addresses in the range 192.168.1.0 to 192.168.1.255
-/// and calls the provided callback for each successful ping result and when scanning completes.
+/// This function pings every host in the subnet of `base` with the given CIDR
+/// `prefix` (see [`ScanRange::new`] for limits) and calls the provided callback
+/// for each successful ping result and when scanning completes.
 ///
 /// # Arguments
-/// * `result_callback` - Called for each successful ping with ScannedIp result
-/// * `complete_callback` - Called when scanning is complete
+/// * `base` - Any address in the subnet to scan, e.g. the configured starting IP
+/// * `prefix` - CIDR prefix length of the subnet
+/// * `ports` - TCP ports probed on every host that replies
+/// * `config` - Concurrency, pings per host and whether hosts are pinged or
+///   connected to, see [`ScanConfig`]
+/// * `result_callback` - Called with each host found
+/// * `complete_callback` - Called when scanning is complete, unless the scan
+///   couldn't start and an error is returned instead
 ///
 /// # Example
 /// ```rust,no_run
-/// use net_monkey_core::scan_network_async;
-///
-/// tokio::spawn(async {
-///     scan_network_async(
-///         |scanned_ip| {
-///             println!("Found host: {:?}", scanned_ip);
-///         },
-///         || {
-///             println!("Scan complete!");
-///         }
-///     ).await;
-/// });
+/// use net_monkey_core::{ProbeMethod, ScanConfig, scan_network_async};
+///
+/// # async fn sweep() -> Result<(), Box<dyn std::error::Error>> {
+/// scan_network_async(
+///     "192.168.1.1".parse().unwrap(),
+///     24,
+///     vec![22, 80, 443],
+///     ScanConfig::new().pings_per_host(3).probe(ProbeMethod::TcpConnect),
+///     |scanned_ip| {
+///         println!("Found host: {:?}", scanned_ip);
+///     },
+///     || {
+///         println!("Scan complete!");
+///     }
+/// ).await?;
+/// # Ok(())
+/// # }
 /// ```
 pub async fn scan_network_async<F, G>(
+    base: IpAddr,
+    prefix: u8,
+    ports: Vec<u16>,
+    config: ScanConfig,
     result_callback: F,
     complete_callback: G,
 ) -> Result<(), Box<dyn std::error::Error>>
@@ -68,111 +373,695 @@ where
     F: Fn(ScannedIp) + Send + Sync + 'static,
     G: Fn() + Send + Sync + 'static,
 {
-    let client = surge_ping::Client::new(&surge_ping::Config::default())?;
-
-    let mut ping_futures = Vec::new();
-    for n in 0..=255 {
-        let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, n));
-        let client = client.clone();
-        let result_callback = &result_callback;
-
-        let ping_future = async move {
-            let mut pinger = client.pinger(ip, surge_ping::PingIdentifier(0)).await;
-            match pinger
-                .timeout(Duration::from_millis(5000)) // 5 second timeout
-                .ping((n as u16).into(), &[])
-                .await
-            {
-                Ok((_, duration)) => {
-                    println!("Ping successful for {ip}: {duration:?}");
-                    let scanned_ip = ScannedIp::new(ip, true, duration.as_millis(), Vec::new());
-                    result_callback(scanned_ip);
-                }
-                Err(_) => {
-                    println!("Ping failed for {ip}");
-                }
+    let range = ScanRange::new(base, prefix);
+    let mut failed = None;
+    scan_stream(range, ports, config, Arc::new(NoopProcessor))
+        .for_each(|message| {
+            match message {
+                ScanMessage::Result(scanned_ip) => result_callback(scanned_ip),
+                // Without an address the scan couldn't start at all
+                ScanMessage::Error { ip: None, error } => failed = Some(error),
+                _ => {}
             }
-        };
-        ping_futures.push(ping_future);
+            futures::future::ready(())
+        })
+        .await;
+    if let Some(error) = failed {
+        return Err(error.to_string().into());
     }
 
-    // Wait for all pings to complete
-    futures::future::join_all(ping_futures).await;
-
     // Signal completion
     complete_callback();
 
     Ok(())
 }
 
+/// Sweep `range` as a stream of [`ScanMessage`]s, for driving a scan
+/// without a channel or a GUI
+///
+/// Yields a `Result` or `Error` and then a `Progress` as each host resolves,
+/// at most `config.max_concurrent` at a time with no more than
+/// `config.max_pps` packets a second between them, and finally `Complete`. Hosts are probed in
+/// `config.order`, `config.packet_delay_ms` apart. If the ICMP socket can't
+/// be opened it yields an `Error` with no `ip` and then `Complete`. Nothing runs until the stream is polled, and dropping it
+/// stops the scan. `processor` runs over each result like in
+/// [`create_network_scanner_with`].
+///
+/// # Example
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use net_monkey_core::{NoopProcessor, ScanConfig, ScanMessage, ScanRange, scan_stream};
+/// use std::sync::Arc;
+///
+/// # async fn sweep() {
+/// let range = ScanRange::new("192.168.1.1".parse().unwrap(), 24);
+/// let scan = scan_stream(range, vec![22, 80], ScanConfig::default(), Arc::new(NoopProcessor));
+/// let mut scan = std::pin::pin!(scan);
+/// while let Some(message) = scan.next().await {
+///     if let ScanMessage::Result(scanned_ip) = message {
+///         println!("Found: {}", scanned_ip.ip);
+///     }
+/// }
+/// # }
+/// ```
+pub fn scan_stream(
+    range: ScanRange,
+    ports: Vec<u16>,
+    config: ScanConfig,
+    processor: BoxedProcessor,
+) -> impl Stream<Item = ScanMessage> + Send + 'static {
+    let config = config.validated();
+    let prober = match Prober::new(&config, &range.socket_config()) {
+        Ok(prober) => prober,
+        Err(e) => {
+            eprintln!("Failed to open ICMP socket: {e}");
+            let error = ProbeError::from_io(&e);
+            let failed = [
+                ScanMessage::Error { ip: None, error },
+                ScanMessage::Complete,
+            ];
+            return futures::stream::iter(failed).left_stream();
+        }
+    };
+    let total = range.host_count();
+    let max_concurrent = config.max_concurrent;
+    let hosts = paced_hosts(range.hosts(), &config);
+    let limiter = RateLimiter::new(&config);
+    let probe = Arc::new((prober, limiter, ports, config));
+
+    // Bounded so a large range doesn't open a socket per host at once
+    hosts
+        .map(move |(n, host)| {
+            let probe = probe.clone();
+            async move {
+                let (prober, limiter, ports, config) = &*probe;
+                let probed = scan_host(prober, limiter, host, n as u16, ports, config).await;
+                (host, probed)
+            }
+        })
+        .buffer_unordered(max_concurrent)
+        .enumerate()
+        .flat_map(move |(done, (ip, probed))| {
+            let outcome = match probed {
+                Ok(scanned_ip) => {
+                    processor::apply(processor.as_ref(), scanned_ip).map(ScanMessage::Result)
+                }
+                Err(error) => Some(ScanMessage::Error {
+                    ip: Some(ip),
+                    error,
+                }),
+            };
+            let progress = ScanMessage::Progress {
+                scanned: done + 1,
+                total,
+            };
+            futures::stream::iter(outcome.into_iter().chain([progress]))
+        })
+        .chain(futures::stream::once(futures::future::ready(
+            ScanMessage::Complete,
+        )))
+        .right_stream()
+}
+
 /// Create a tokio channel-based network scanner
 ///
 /// This function returns a channel receiver that yields scan results as they come in.
 /// It's designed to work with async streaming systems like Iced subscriptions.
 ///
+/// # Arguments
+/// * `base` - Any address in the subnet to scan
+/// * `prefix` - CIDR prefix length of the subnet
+/// * `ports` - TCP ports probed on every host that replies
+///
 /// # Returns
 /// * `tokio::sync::mpsc::UnboundedReceiver<ScanMessage>` - Channel receiver for scan results
+/// * [`CancelToken`] - Cancel to stop the scan early
 ///
 /// # Example
 /// ```rust,no_run
-/// use net_monkey_core::create_network_scanner;
+/// use net_monkey_core::{ScanMessage, create_network_scanner};
 ///
-/// let mut rx = create_network_scanner().await;
+/// # async fn sweep() {
+/// let (mut rx, cancel) =
+///     create_network_scanner("192.168.1.1".parse().unwrap(), 24, vec![80, 443]).await;
 /// while let Some(message) = rx.recv().await {
 ///     match message {
 ///         ScanMessage::Result(scanned_ip) => {
 ///             println!("Found: {:?}", scanned_ip);
 ///         }
+///         ScanMessage::Progress { scanned, total } => {
+///             println!("{scanned} of {total} hosts probed");
+///         }
+///         ScanMessage::Error { ip, error } => {
+///             println!("No answer from {ip:?}: {error}");
+///         }
 ///         ScanMessage::Complete => {
 ///             println!("Scan finished");
 ///             break;
 ///         }
 ///     }
 /// }
+/// # cancel.cancel();
+/// # }
 /// ```
 pub async fn create_network_scanner(
+    base: IpAddr,
+    prefix: u8,
+    ports: Vec<u16>,
+) -> (UnboundedReceiver<ScanMessage>, CancelToken) {
+    create_network_scanner_with(
+        base,
+        prefix,
+        ports,
+        ScanConfig::default(),
+        Arc::new(NoopProcessor),
+    )
+    .await
+}
+
+/// Create a channel-based network scanner using `config` that runs
+/// `processor` over each result before sending it
+///
+/// At most `config.max_concurrent` hosts are probed at once, and `Complete`
+/// is sent after the last of them resolves. See [`crate::processor`] for
+/// ordering guarantees.
+pub async fn create_network_scanner_with(
+    base: IpAddr,
+    prefix: u8,
+    ports: Vec<u16>,
+    config: ScanConfig,
+    processor: BoxedProcessor,
+) -> (UnboundedReceiver<ScanMessage>, CancelToken) {
+    create_range_scanner(ScanRange::new(base, prefix), ports, config, processor).await
+}
+
+/// Create a channel-based scanner sweeping an explicit `range`, such as a
+/// [`ScanRange::between`] span, otherwise like [`create_network_scanner_with`]
+pub async fn create_range_scanner(
+    range: ScanRange,
+    ports: Vec<u16>,
+    config: ScanConfig,
+    processor: BoxedProcessor,
+) -> (UnboundedReceiver<ScanMessage>, CancelToken) {
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let cancel = CancelToken::new();
+    let token = cancel.clone();
+
+    // Dropping the stream on cancellation drops any pings still in flight
+    tokio::spawn(async move {
+        let mut finished = false;
+        let forward = scan_stream(range, ports, config, processor).for_each(|message| {
+            finished = matches!(message, ScanMessage::Complete);
+            let _ = tx.send(message);
+            futures::future::ready(())
+        });
+        token.run_until_cancelled(forward).await;
+        // A cancelled scan stops short of its own `Complete`
+        if !finished {
+            let _ = tx.send(ScanMessage::Complete);
+        }
+    });
+
+    (rx, cancel)
+}
+
+/// Ping and port-scan a single host the way a sweep does, without sweeping
+/// the rest of its subnet
+///
+/// A host that doesn't answer is still returned, marked `Filtered` or `Down`
+/// depending on whether any of `ports` is open, so a stale result can be
+/// replaced. Fails only if the ICMP socket can't be opened.
+///
+/// # Example
+/// ```rust,no_run
+/// use net_monkey_core::{ScanConfig, scan_single_host};
+///
+/// # async fn rescan() -> Result<(), String> {
+/// let host = scan_single_host("192.168.1.20".parse().unwrap(), &[22], ScanConfig::default()).await?;
+/// println!("{} is {}", host.ip, host.state.label());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn scan_single_host(
     ip: IpAddr,
-    mask: u8,
-) -> tokio::sync::mpsc::UnboundedReceiver<ScanMessage> {
+    ports: &[u16],
+    config: ScanConfig,
+) -> Result<ScannedIp, String> {
+    let prefix = match ip {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    };
+    let config = config.validated().report_down(true);
+    let prober = Prober::new(&config, &ScanRange::new(ip, prefix).socket_config())
+        .map_err(|e| format!("Can't open ICMP socket: {e}"))?;
+    scan_host(&prober, &RateLimiter::new(&config), ip, 0, ports, &config)
+        .await
+        .map_err(|e| format!("Can't probe {ip}: {e}"))
+}
+
+/// A subnet to sweep from a specific local adapter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanTarget {
+    /// Interface name that results are tagged with
+    pub interface: String,
+    /// Local address the pings are sent from
+    pub source: Ipv4Addr,
+    /// Prefix length of the subnet around `source`
+    pub prefix: u8,
+}
+
+impl ScanTarget {
+    /// Target the subnet an adapter sits on; only IPv4 adapters are supported
+    ///
+    /// Like [`ScanRange::new`], subnets wider than [`MIN_SCAN_PREFIX`] are
+    /// narrowed to it with a warning.
+    pub fn from_adapter(adapter: &NetworkAdapter) -> Option<Self> {
+        let source: Ipv4Addr = adapter.ip_address.parse().ok()?;
+        if adapter.prefix < MIN_SCAN_PREFIX {
+            eprintln!(
+                "{source}/{} on {} is too large to sweep, scanning {source}/{MIN_SCAN_PREFIX} instead",
+                adapter.prefix, adapter.name
+            );
+        }
+        Some(Self {
+            interface: adapter.name.clone(),
+            source,
+            prefix: adapter.prefix.max(MIN_SCAN_PREFIX),
+        })
+    }
+
+    /// Host addresses in the target subnet, never wider than [`MIN_SCAN_PREFIX`]
+    pub fn hosts(&self) -> impl Iterator<Item = Ipv4Addr> + use<> {
+        ipv4_hosts(self.source, self.prefix.max(MIN_SCAN_PREFIX))
+    }
+}
+
+/// Usable host addresses of the subnet containing `base`
+///
+/// Network and broadcast addresses are skipped, except for /31 and /32
+/// where every address is a host.
+pub fn ipv4_hosts(base: Ipv4Addr, prefix: u8) -> impl Iterator<Item = Ipv4Addr> {
+    Ipv4Subnet::containing(base, prefix).hosts()
+}
+
+/// Sweep several adapters' subnets concurrently, each from its own interface
+///
+/// Results are tagged with the interface they were found from and merged onto
+/// one channel. `config.max_concurrent` bounds the pings in flight across all
+/// targets combined, and a single `Complete` is sent once every target has
+/// finished or the returned [`CancelToken`] is cancelled.
+pub async fn create_multi_adapter_scanner(
+    targets: Vec<ScanTarget>,
+    ports: Vec<u16>,
+    config: ScanConfig,
+    processor: BoxedProcessor,
+) -> (UnboundedReceiver<ScanMessage>, CancelToken) {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let cancel = CancelToken::new();
+    let token = cancel.clone();
 
-    // Spawn the scanning task
     tokio::spawn(async move {
-        let client = surge_ping::Client::new(&surge_ping::Config::default()).unwrap();
+        let config = config.validated();
+        let max_concurrent = config.max_concurrent;
+        let permits = Arc::new(Semaphore::new(max_concurrent));
+        let limiter = RateLimiter::new(&config);
+        let total: usize = targets.iter().map(|target| target.hosts().count()).sum();
+        let scanned = AtomicUsize::new(0);
 
-        let mut ping_futures = Vec::new();
-        let ip_range = 0xffffffff_u32 >> mask;
-        for n in 0..=ip_range {
-            let ip = IpAddr::V4();
-            let client = client.clone();
+        let sweeps = targets.into_iter().map(|target| {
             let tx = tx.clone();
+            let permits = permits.clone();
+            let processor = processor.clone();
+            let (ports, config, token, scanned) = (&ports, &config, &token, &scanned);
+            let limiter = &limiter;
 
-            let ping_future = async move {
-                let mut pinger = client.pinger(ip, surge_ping::PingIdentifier(0)).await;
-                match pinger
-                    .timeout(Duration::from_millis(5000)) // 5 second timeout
-                    .ping((n as u16).into(), &[])
-                    .await
-                {
-                    Ok((_, duration)) => {
-                        println!("Ping successful for {ip}: {duration:?}");
-                        let scanned_ip = ScannedIp::new(ip, true, duration.as_millis(), Vec::new());
-                        let _ = tx.send(ScanMessage::Result(scanned_ip));
+            async move {
+                let socket_config = surge_ping::Config::builder()
+                    .bind(SocketAddr::new(IpAddr::V4(target.source), 0))
+                    .build();
+                let prober = match Prober::new(config, &socket_config) {
+                    Ok(prober) => prober,
+                    Err(e) => {
+                        eprintln!("Failed to open ICMP socket on {}: {e}", target.interface);
+                        let error = ProbeError::from_io(&e);
+                        let _ = tx.send(ScanMessage::Error { ip: None, error });
+                        return;
                     }
-                    Err(_) => {
-                        println!("Ping failed for {ip}");
-                    }
-                }
-            };
-            ping_futures.push(ping_future);
-        }
+                };
 
-        // Wait for all pings
-        futures::future::join_all(ping_futures).await;
+                paced_hosts(target.hosts(), config)
+                    .for_each_concurrent(max_concurrent, |(n, ip)| {
+                        let (prober, tx) = (&prober, &tx);
+                        let (permits, processor) = (&permits, &processor);
+                        let (interface, token) = (&target.interface, &token);
+                        async move {
+                            // The shared semaphore caps pings across every target
+                            let Ok(_permit) = permits.acquire().await else {
+                                return;
+                            };
+                            if token.is_cancelled() {
+                                return;
+                            }
+                            let ip_addr = IpAddr::V4(ip);
+                            let probed =
+                                scan_host(prober, limiter, ip_addr, n as u16, ports, config).await;
+                            match probed {
+                                _ if token.is_cancelled() => {}
+                                Ok(scanned_ip) => {
+                                    if let Some(scanned_ip) = processor::apply(
+                                        processor.as_ref(),
+                                        scanned_ip.with_interface(interface),
+                                    ) {
+                                        let _ = tx.send(ScanMessage::Result(scanned_ip));
+                                    }
+                                }
+                                Err(error) => {
+                                    let ip = Some(IpAddr::V4(ip));
+                                    let _ = tx.send(ScanMessage::Error { ip, error });
+                                }
+                            }
+                            let scanned = scanned.fetch_add(1, Ordering::Relaxed) + 1;
+                            let _ = tx.send(ScanMessage::Progress { scanned, total });
+                        }
+                    })
+                    .await;
+            }
+        });
+
+        token
+            .run_until_cancelled(futures::future::join_all(sweeps).map(|_| ()))
+            .await;
         let _ = tx.send(ScanMessage::Complete);
     });
 
-    rx
+    (rx, cancel)
+}
+
+/// `hosts` numbered for their ping sequence, in the configured
+/// [`ScanOrder`](crate::ScanOrder) and started `config.packet_delay_ms` apart
+fn paced_hosts<I, T>(hosts: I, config: &ScanConfig) -> impl Stream<Item = (usize, T)> + use<I, T>
+where
+    I: Iterator<Item = T>,
+    T: Send + 'static,
+{
+    let delay = config.packet_delay_duration();
+    let hosts = config.order_hosts(hosts.enumerate().collect());
+    futures::stream::iter(hosts).then(move |host| async move {
+        // Pace probe starts; the probes themselves still overlap
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        host
+    })
+}
+
+/// How long each port gets to accept a connection
+pub const PORT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Ports tried by [`ProbeMethod::TcpConnect`] when a scan has none configured
+pub const FALLBACK_PORTS: [u16; 3] = [80, 443, 22];
+
+/// Spaces out the pings and TCP connects of a scan to its
+/// [`ScanConfig::max_pps`], shared by every host and sweep in the scan so the
+/// cap holds across all of them
+///
+/// Each packet reserves the next free slot, so they go out no closer
+/// together than the interval however many hosts are in flight.
+struct RateLimiter {
+    interval: Option<Duration>,
+    next: std::sync::Mutex<Option<tokio::time::Instant>>,
+}
+
+impl RateLimiter {
+    fn new(config: &ScanConfig) -> Self {
+        Self {
+            interval: config.probe_interval(),
+            next: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn unlimited() -> Self {
+        Self::new(&ScanConfig::default())
+    }
+
+    /// Wait for this packet's turn to be sent
+    async fn wait(&self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let start = {
+            let mut next = self.next.lock().unwrap();
+            let now = tokio::time::Instant::now();
+            let start = next.map_or(now, |next| next.max(now));
+            *next = Some(start + interval);
+            start
+        };
+        tokio::time::sleep_until(start).await;
+    }
+}
+
+/// Checks hosts for life the way a scan's [`ProbeMethod`] asks, set up once
+/// per scan
+enum Prober {
+    Icmp(surge_ping::Client),
+    TcpConnect,
+}
+
+impl Prober {
+    /// Only ICMP opens a socket, using `socket`
+    fn new(config: &ScanConfig, socket: &surge_ping::Config) -> std::io::Result<Self> {
+        match config.probe {
+            ProbeMethod::Icmp => surge_ping::Client::new(socket).map(Self::Icmp),
+            ProbeMethod::TcpConnect => Ok(Self::TcpConnect),
+        }
+    }
+}
+
+/// Check a host is up and probe `ports` on it
+///
+/// A host that ignores ping but has a port open is still returned, as
+/// `Filtered`, and with `config.report_down` one with nothing open is
+/// returned as `Down`. Otherwise the failure is why it didn't answer.
+async fn scan_host(
+    prober: &Prober,
+    limiter: &RateLimiter,
+    ip: IpAddr,
+    seq: u16,
+    ports: &[u16],
+    config: &ScanConfig,
+) -> Result<ScannedIp, ProbeError> {
+    let open_ports = || paced_probe_ports(ip, ports, PORT_TIMEOUT, limiter);
+    let probed = match prober {
+        Prober::Icmp(client) => match ping_host(client, limiter, ip, seq, config).await {
+            Ok(mut scanned_ip) => {
+                scanned_ip.ports = open_ports().await;
+                Ok(scanned_ip)
+            }
+            Err(error) => Err((error, open_ports().await)),
+        },
+        // Connecting has already found that no port is open
+        Prober::TcpConnect => connect_host(limiter, ip, ports, config)
+            .await
+            .map_err(|error| (error, Vec::new())),
+    };
+    let scanned_ip = match probed {
+        Ok(scanned_ip) => scanned_ip,
+        Err((error, open)) if open.is_empty() && !config.report_down => return Err(error),
+        Err((_, open)) => ScannedIp::new(ip, false, 0, open),
+    };
+    // The probe has just populated the ARP entry for local hosts
+    Ok(scanned_ip.with_mac(crate::arp::lookup_mac(ip).await))
+}
+
+/// Connect to every one of `ports` (or [`FALLBACK_PORTS`]) at once,
+/// `config.pings_per_host` times, returning a result if the host answered
+/// any connection and otherwise the most telling failure
+///
+/// A refused connection is an answer too, the host is up with that port
+/// closed. The fastest answer of each round is a latency sample, and the
+/// reported ping is their mean like for ICMP. Only ports from `ports` that
+/// accepted are listed as open.
+async fn connect_host(
+    limiter: &RateLimiter,
+    ip: IpAddr,
+    ports: &[u16],
+    config: &ScanConfig,
+) -> Result<ScannedIp, ProbeError> {
+    let candidates = match ports.is_empty() {
+        true => &FALLBACK_PORTS[..],
+        false => ports,
+    };
+    let mut samples = Vec::new();
+    let mut accepted = Vec::new();
+    let mut failure = None;
+    for _ in 0..config.pings_per_host {
+        for _ in 0..=config.retries {
+            let (connects, errors): (Vec<_>, Vec<_>) =
+                timed_connects(limiter, ip, candidates, config.timeout_duration())
+                    .await
+                    .into_iter()
+                    .partition(Result::is_ok);
+            failure = failure.max(errors.into_iter().filter_map(Result::err).max());
+            let connects: Vec<_> = connects.into_iter().flatten().collect();
+            if let Some(fastest) = connects.iter().map(|(_, _, duration)| *duration).min() {
+                samples.push(fastest);
+                accepted.extend(
+                    connects
+                        .into_iter()
+                        .filter_map(|(port, open, _)| open.then_some(port)),
+                );
+                break;
+            }
+        }
+    }
+    let Some(stats) = LatencyStats::from_samples(&samples) else {
+        return Err(failure.unwrap_or(ProbeError::Timeout));
+    };
+    let open = ports
+        .iter()
+        .copied()
+        .filter(|port| accepted.contains(port))
+        .collect();
+    Ok(ScannedIp::new(ip, true, stats.mean.as_millis(), open).with_samples(samples))
+}
+
+/// Connect to each of `ports` within `timeout`, with whether each was
+/// accepted and how long the host took to answer, or why it didn't
+async fn timed_connects(
+    limiter: &RateLimiter,
+    ip: IpAddr,
+    ports: &[u16],
+    timeout: Duration,
+) -> Vec<Result<(u16, bool, Duration), ProbeError>> {
+    let connects = ports.iter().map(|&port| async move {
+        limiter.wait().await;
+        let started = tokio::time::Instant::now();
+        let connect = tokio::net::TcpStream::connect(SocketAddr::new(ip, port));
+        match tokio::time::timeout(timeout, connect).await {
+            Ok(Ok(_)) => Ok((port, true, started.elapsed())),
+            Ok(Err(e)) if e.kind() == std::io::ErrorKind::ConnectionRefused => {
+                Ok((port, false, started.elapsed()))
+            }
+            Ok(Err(e)) => Err(ProbeError::from_io(&e)),
+            Err(_) => Err(ProbeError::Timeout),
+        }
+    });
+    futures::future::join_all(connects).await
+}
+
+/// Try a TCP connection to each of `ports` at once, returning those that accepted
+///
+/// Ports that refuse, or don't answer within `timeout`, are left out. The
+/// result keeps the order of `ports`.
+pub async fn probe_ports(ip: IpAddr, ports: &[u16], timeout: Duration) -> Vec<u16> {
+    paced_probe_ports(ip, ports, timeout, &RateLimiter::unlimited()).await
+}
+
+/// [`probe_ports`] with each connect waiting its turn from `limiter`
+async fn paced_probe_ports(
+    ip: IpAddr,
+    ports: &[u16],
+    timeout: Duration,
+    limiter: &RateLimiter,
+) -> Vec<u16> {
+    let probes = ports.iter().map(|&port| async move {
+        limiter.wait().await;
+        let connect = tokio::net::TcpStream::connect(SocketAddr::new(ip, port));
+        matches!(tokio::time::timeout(timeout, connect).await, Ok(Ok(_))).then_some(port)
+    });
+    futures::future::join_all(probes)
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Ping a single host `config.pings_per_host` times, returning a result if
+/// any of them got a reply and otherwise the most telling failure
+///
+/// Each ping is retried per `config`. The reported ping is the mean of the
+/// replies, so a single slow reply doesn't decide how the host is shown.
+async fn ping_host(
+    client: &surge_ping::Client,
+    limiter: &RateLimiter,
+    ip: IpAddr,
+    seq: u16,
+    config: &ScanConfig,
+) -> Result<ScannedIp, ProbeError> {
+    let payload = config.payload();
+    let mut pinger = client.pinger(ip, surge_ping::PingIdentifier(0)).await;
+    pinger.timeout(config.timeout_duration());
+    let mut samples = Vec::new();
+    let mut ttl = None;
+    let mut failure = None;
+    for n in 0..config.pings_per_host as u16 {
+        for _ in 0..=config.retries {
+            limiter.wait().await;
+            match pinger.ping(seq.wrapping_add(n).into(), &payload).await {
+                Ok((packet, duration)) => {
+                    println!("Ping successful for {ip}: {duration:?}");
+                    samples.push(duration);
+                    ttl = ttl.or(reply_ttl(&packet));
+                    break;
+                }
+                Err(e) => failure = failure.max(Some(ProbeError::from_ping(&e))),
+            }
+        }
+    }
+    let Some(stats) = LatencyStats::from_samples(&samples) else {
+        return Err(failure.unwrap_or(ProbeError::Timeout));
+    };
+    Ok(ScannedIp::new(ip, true, stats.mean.as_millis(), Vec::new())
+        .with_samples(samples)
+        .with_ttl(ttl))
+}
+
+/// TTL of an echo reply; IPv6 replies don't expose their hop limit
+fn reply_ttl(packet: &surge_ping::IcmpPacket) -> Option<u8> {
+    match packet {
+        surge_ping::IcmpPacket::V4(packet) => packet.get_ttl(),
+        surge_ping::IcmpPacket::V6(_) => None,
+    }
+}
+
+/// Stops a running scan when cancelled
+///
+/// Clones share the same state, so the app can keep one while the scanner
+/// holds another. Once cancelled, no further results are sent and the scan
+/// finishes with a `Complete` message.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<tokio::sync::watch::Sender<bool>>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the scan to stop; calling it again has no effect
+    pub fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    /// Resolves once the token has been cancelled
+    pub async fn cancelled(&self) {
+        let mut rx = self.cancelled.subscribe();
+        let _ = rx.wait_for(|cancelled| *cancelled).await;
+    }
+
+    /// Run `sweep` until it finishes or the token is cancelled, dropping any
+    /// pings still in flight on cancellation
+    async fn run_until_cancelled(&self, sweep: impl Future<Output = ()>) {
+        tokio::select! {
+            _ = sweep => {}
+            _ = self.cancelled() => {}
+        }
+    }
 }
 
 /// Messages sent by the network scanner
@@ -180,6 +1069,344 @@ pub async fn create_network_scanner(
 pub enum ScanMessage {
     /// A scan result for a single IP
     Result(ScannedIp),
+    /// `scanned` of the `total` hosts in range have been probed, sent as each host resolves
+    Progress { scanned: usize, total: usize },
+    /// A host gave no answer, or with no `ip` the scan couldn't probe at all
+    Error {
+        ip: Option<IpAddr>,
+        error: ProbeError,
+    },
     /// Scanning is complete
     Complete,
 }
+
+impl ScanMessage {
+    /// Fraction of the range probed so far, 1.0 for an empty range
+    pub fn progress_ratio(scanned: usize, total: usize) -> f32 {
+        match total {
+            0 => 1.0,
+            _ => (scanned as f32 / total as f32).min(1.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_state_from_probes() {
+        assert_eq!(HostState::from_probes(true, true), HostState::Up);
+        assert_eq!(HostState::from_probes(true, false), HostState::Up);
+        assert_eq!(HostState::from_probes(false, true), HostState::Filtered);
+        assert_eq!(HostState::from_probes(false, false), HostState::Down);
+    }
+
+    #[test]
+    fn test_ipv4_hosts_ranges() {
+        let base = Ipv4Addr::new(192, 168, 1, 77);
+
+        let hosts: Vec<_> = ipv4_hosts(base, 24).collect();
+        assert_eq!(hosts.len(), 254);
+        assert_eq!(hosts[0], Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(hosts[253], Ipv4Addr::new(192, 168, 1, 254));
+
+        assert_eq!(ipv4_hosts(base, 25).count(), 126);
+        assert_eq!(ipv4_hosts(base, 31).count(), 2);
+        assert_eq!(ipv4_hosts(base, 32).collect::<Vec<_>>(), vec![base]);
+    }
+
+    #[test]
+    fn test_scan_range_limits() {
+        let base = IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3));
+        assert_eq!(ScanRange::new(base, 25).host_count(), 126);
+        assert_eq!(ScanRange::new(base, 16).host_count(), 65_534);
+
+        // Wider ranges are capped at /16 around the base address
+        let ScanRange::V4(capped) = ScanRange::new(base, 8) else {
+            panic!("an IPv4 base gives an IPv4 range");
+        };
+        assert_eq!(capped.prefix, MIN_SCAN_PREFIX);
+        assert_eq!(capped.network, Ipv4Addr::new(10, 1, 0, 0));
+
+        let v6 = ScanRange::new("fe80::1".parse().unwrap(), 64);
+        assert_eq!(v6.host_count(), MAX_IPV6_HOSTS);
+        assert_eq!(v6.hosts().count(), MAX_IPV6_HOSTS);
+        assert_eq!(v6.hosts().next(), Some("fe80::1".parse().unwrap()));
+        assert_eq!(
+            ScanRange::new("fe80::1".parse().unwrap(), 124).host_count(),
+            15
+        );
+    }
+
+    #[test]
+    fn test_scan_range_between() {
+        let start = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 250));
+        let end = IpAddr::V4(Ipv4Addr::new(192, 168, 2, 4));
+        let span = ScanRange::between(start, end).unwrap();
+        assert_eq!(span.host_count(), 11);
+        let hosts: Vec<_> = span.hosts().collect();
+        assert_eq!(hosts.first(), Some(&start));
+        assert_eq!(hosts.last(), Some(&end));
+
+        assert!(ScanRange::between(end, start).is_err());
+        assert!(ScanRange::between(start, "fe80::1".parse().unwrap()).is_err());
+
+        let whole = ScanRange::between("::".parse().unwrap(), Ipv6Addr::from(u128::MAX).into());
+        assert_eq!(whole.unwrap().hosts().count(), MAX_IPV6_HOSTS);
+    }
+
+    #[test]
+    fn test_progress_ratio() {
+        assert_eq!(ScanMessage::progress_ratio(0, 254), 0.0);
+        assert_eq!(ScanMessage::progress_ratio(63, 126), 0.5);
+        assert_eq!(ScanMessage::progress_ratio(126, 126), 1.0);
+        assert_eq!(ScanMessage::progress_ratio(0, 0), 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_token_is_shared() {
+        let token = CancelToken::new();
+        let scanner_side = token.clone();
+        assert!(!scanner_side.is_cancelled());
+
+        let sweep = scanner_side.run_until_cancelled(std::future::pending());
+        token.cancel();
+        // Would hang forever if cancellation didn't stop the sweep
+        tokio::time::timeout(Duration::from_secs(1), sweep)
+            .await
+            .unwrap();
+        assert!(scanner_side.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_probe_ports_finds_listener() {
+        let open = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let open_port = open.local_addr().unwrap().port();
+        // Bind then drop to get a port that's almost certainly closed
+        let closed_port = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let found = probe_ports(localhost, &[closed_port, open_port], PORT_TIMEOUT).await;
+        assert_eq!(found, vec![open_port]);
+        assert!(probe_ports(localhost, &[], PORT_TIMEOUT).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_connect_host_without_icmp() {
+        let open = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let open_port = open.local_addr().unwrap().port();
+        let closed_port = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let config = ScanConfig::new()
+            .probe(ProbeMethod::TcpConnect)
+            .pings_per_host(2)
+            .timeout(PORT_TIMEOUT);
+        let unlimited = RateLimiter::unlimited();
+        let host = connect_host(&unlimited, localhost, &[closed_port, open_port], &config)
+            .await
+            .unwrap();
+        assert_eq!(host.state, HostState::Up);
+        assert_eq!(host.ports, vec![open_port]);
+        assert_eq!(host.samples.len(), 2);
+
+        // Refusing is still an answer, so the host is up with nothing open
+        let host = connect_host(&unlimited, localhost, &[closed_port], &config)
+            .await
+            .unwrap();
+        assert_eq!(host.state, HostState::Up);
+        assert!(host.ports.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_paced_hosts_follow_order_and_delay() {
+        let subnet = Ipv4Subnet::containing(Ipv4Addr::new(10, 0, 0, 0), 24);
+        let sequential: Vec<_> = subnet.hosts().collect();
+        let shuffled: Vec<_> = paced_hosts(
+            subnet.hosts(),
+            &ScanConfig::new().order(crate::ScanOrder::Random),
+        )
+        .collect()
+        .await;
+        // Each host keeps the sequence number of its place in the range
+        assert!(shuffled.iter().all(|(n, ip)| sequential[*n] == *ip));
+        let shuffled: Vec<_> = shuffled.into_iter().map(|(_, ip)| ip).collect();
+        assert_ne!(shuffled, sequential);
+
+        let config = ScanConfig::new().packet_delay(Duration::from_millis(25));
+        let start = tokio::time::Instant::now();
+        let paced: Vec<_> = paced_hosts(sequential.into_iter().take(4), &config)
+            .collect()
+            .await;
+        assert_eq!(paced.len(), 4);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_probes() {
+        let limiter = RateLimiter::new(&ScanConfig::new().max_pps(Some(50)));
+        let start = tokio::time::Instant::now();
+        // Started together, yet each waits 20ms behind the one before
+        futures::future::join_all((0..6).map(|_| limiter.wait())).await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        // Without a cap nothing waits
+        let start = tokio::time::Instant::now();
+        RateLimiter::unlimited().wait().await;
+        assert!(start.elapsed() < Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_counts_every_connect() {
+        let open = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let open_port = open.local_addr().unwrap().port();
+        let config = ScanConfig::new()
+            .probe(ProbeMethod::TcpConnect)
+            .pings_per_host(2)
+            .max_pps(Some(20));
+
+        // Two rounds of three connects to one host is six packets, 50ms apart
+        let start = tokio::time::Instant::now();
+        let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let limiter = RateLimiter::new(&config);
+        connect_host(&limiter, localhost, &[open_port; 3], &config)
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(250));
+    }
+
+    #[tokio::test]
+    async fn test_scan_stream_over_tcp() {
+        let open = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let open_port = open.local_addr().unwrap().port();
+
+        let range = ScanRange::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 32);
+        let config = ScanConfig::new()
+            .probe(ProbeMethod::TcpConnect)
+            .timeout(PORT_TIMEOUT);
+        let messages: Vec<_> = scan_stream(range, vec![open_port], config, Arc::new(NoopProcessor))
+            .collect()
+            .await;
+        match messages.as_slice() {
+            [
+                ScanMessage::Result(host),
+                ScanMessage::Progress {
+                    scanned: 1,
+                    total: 1,
+                },
+                ScanMessage::Complete,
+            ] => assert_eq!(host.ports, vec![open_port]),
+            other => panic!("unexpected messages {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_probe_error_from_io() {
+        use std::io::{Error, ErrorKind};
+
+        let denied = ProbeError::from_io(&Error::from(ErrorKind::PermissionDenied));
+        assert_eq!(denied, ProbeError::PermissionDenied);
+        assert_eq!(
+            ProbeError::from_io(&Error::from(ErrorKind::NetworkUnreachable)),
+            ProbeError::Unreachable
+        );
+        assert_eq!(
+            ProbeError::from_io(&Error::from(ErrorKind::TimedOut)),
+            ProbeError::Timeout
+        );
+        // A timeout says the least about why a host was missed
+        let other = ProbeError::Other(String::from("connection refused"));
+        assert_eq!(ProbeError::Timeout.max(other.clone()), other);
+        assert_eq!(other.max(denied.clone()), denied);
+    }
+
+    #[tokio::test]
+    async fn test_sweep_reports_down_hosts() {
+        let sweep = |report_down| {
+            // TCP can't connect to a multicast address, so nothing answers
+            let range = ScanRange::new(IpAddr::V4(Ipv4Addr::new(224, 0, 0, 1)), 32);
+            let config = ScanConfig::new()
+                .probe(ProbeMethod::TcpConnect)
+                .timeout(PORT_TIMEOUT)
+                .report_down(report_down);
+            scan_stream(range, vec![9], config, Arc::new(NoopProcessor)).collect::<Vec<_>>()
+        };
+
+        // Left out with only the reason by default
+        let messages = sweep(false).await;
+        assert!(matches!(
+            messages[0],
+            ScanMessage::Error { ip: Some(_), .. }
+        ));
+
+        let messages = sweep(true).await;
+        match &messages[0] {
+            ScanMessage::Result(host) => {
+                assert_eq!(host.state, HostState::Down);
+                assert!(!host.alive);
+            }
+            other => panic!("unexpected message {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_scan_target_from_adapter() {
+        let adapter = NetworkAdapter {
+            name: String::from("eth1"),
+            ip_address: String::from("10.0.5.20"),
+            prefix: 30,
+            ..Default::default()
+        };
+        let target = ScanTarget::from_adapter(&adapter).unwrap();
+        assert_eq!(target.interface, "eth1");
+        assert_eq!(
+            target.hosts().collect::<Vec<_>>(),
+            vec![Ipv4Addr::new(10, 0, 5, 21), Ipv4Addr::new(10, 0, 5, 22)]
+        );
+
+        let v6 = NetworkAdapter {
+            ip_address: String::from("fe80::1"),
+            ..adapter
+        };
+        assert_eq!(ScanTarget::from_adapter(&v6), None);
+
+        // A /8 network is narrowed like any other sweep
+        let wide = NetworkAdapter {
+            ip_address: String::from("10.0.5.20"),
+            prefix: 8,
+            ..v6
+        };
+        let target = ScanTarget::from_adapter(&wide).unwrap();
+        assert_eq!(target.prefix, MIN_SCAN_PREFIX);
+        assert_eq!(target.hosts().count(), 65534);
+        let raw = ScanTarget {
+            prefix: 8,
+            ..target
+        };
+        assert!(raw.hosts().count() <= 65534);
+    }
+
+    #[test]
+    fn test_scanned_ip_state() {
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10));
+
+        let filtered = ScannedIp::new(ip, false, 0, vec![443]);
+        assert_eq!(filtered.state, HostState::Filtered);
+        assert!(filtered.alive);
+
+        let down = ScannedIp::new(ip, false, 0, Vec::new());
+        assert_eq!(down.state, HostState::Down);
+        assert!(!down.alive);
+    }
+}
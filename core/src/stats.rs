@@ -0,0 +1,83 @@
+//! Latency statistics over a host's ping samples
+
+use std::time::Duration;
+
+/// Percentile summary of a set of ping samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub min: Duration,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl LatencyStats {
+    /// Summarise `samples`, or `None` if there are none
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        Some(Self {
+            min: *sorted.first()?,
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+            p50: percentile_sorted(&sorted, 50.0)?,
+            p95: percentile_sorted(&sorted, 95.0)?,
+            p99: percentile_sorted(&sorted, 99.0)?,
+            max: *sorted.last()?,
+        })
+    }
+}
+
+/// The `p`th percentile (0..=100) of `samples` using the nearest-rank method
+///
+/// Returns `None` for an empty slice; a single sample is every percentile.
+pub fn percentile(samples: &[Duration], p: f64) -> Option<Duration> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    percentile_sorted(&sorted, p)
+}
+
+fn percentile_sorted(sorted: &[Duration], p: f64) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.saturating_sub(1)).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_percentile_empty_and_single() {
+        assert_eq!(percentile(&[], 50.0), None);
+        assert_eq!(LatencyStats::from_samples(&[]), None);
+
+        let one = ms(&[7]);
+        assert_eq!(percentile(&one, 0.0), Some(Duration::from_millis(7)));
+        assert_eq!(percentile(&one, 99.0), Some(Duration::from_millis(7)));
+    }
+
+    #[test]
+    fn test_percentile_small_sets() {
+        // Unsorted on purpose
+        let samples = ms(&[40, 10, 30, 20]);
+        assert_eq!(percentile(&samples, 50.0), Some(Duration::from_millis(20)));
+        assert_eq!(percentile(&samples, 95.0), Some(Duration::from_millis(40)));
+
+        let samples = ms(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 100]);
+        let stats = LatencyStats::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.mean, Duration::from_micros(14_500));
+        assert_eq!(stats.p50, Duration::from_millis(5));
+        assert_eq!(stats.p95, Duration::from_millis(100));
+        assert_eq!(stats.p99, Duration::from_millis(100));
+        assert_eq!(stats.max, Duration::from_millis(100));
+    }
+}
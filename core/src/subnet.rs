@@ -0,0 +1,192 @@
+//! Subnet arithmetic shared by the scanner and the UI
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// The subnet of a given prefix length that an address belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv4Subnet {
+    pub network: Ipv4Addr,
+    pub broadcast: Ipv4Addr,
+    pub prefix: u8,
+}
+
+impl Ipv4Subnet {
+    /// Subnet containing `ip`; prefixes above 32 are treated as /32
+    pub fn containing(ip: Ipv4Addr, prefix: u8) -> Self {
+        let prefix = prefix.min(32);
+        let mask = mask(prefix);
+        let network = u32::from(ip) & mask;
+        Self {
+            network: Ipv4Addr::from(network),
+            broadcast: Ipv4Addr::from(network | !mask),
+            prefix,
+        }
+    }
+
+    /// Netmask in dotted form, e.g. 255.255.255.0 for /24
+    pub fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from(mask(self.prefix))
+    }
+
+    /// Whether /31 or /32, where every address is a usable host
+    fn is_point_to_point(&self) -> bool {
+        self.prefix >= 31
+    }
+
+    /// First and last usable host addresses
+    pub fn host_range(&self) -> (Ipv4Addr, Ipv4Addr) {
+        let (network, broadcast) = (u32::from(self.network), u32::from(self.broadcast));
+        match self.is_point_to_point() {
+            true => (self.network, self.broadcast),
+            false => (Ipv4Addr::from(network + 1), Ipv4Addr::from(broadcast - 1)),
+        }
+    }
+
+    /// Number of usable host addresses
+    pub fn host_count(&self) -> u64 {
+        let (first, last) = self.host_range();
+        u32::from(last) as u64 - u32::from(first) as u64 + 1
+    }
+
+    /// Usable host addresses, network and broadcast excluded
+    pub fn hosts(&self) -> impl Iterator<Item = Ipv4Addr> + use<> {
+        let (first, last) = self.host_range();
+        (u32::from(first)..=u32::from(last)).map(Ipv4Addr::from)
+    }
+
+    pub fn contains(&self, ip: Ipv4Addr) -> bool {
+        (self.network..=self.broadcast).contains(&ip)
+    }
+
+    /// Offset of `ip` from the network address, if it is in this subnet
+    pub fn offset_of(&self, ip: Ipv4Addr) -> Option<u32> {
+        self.contains(ip)
+            .then(|| u32::from(ip) - u32::from(self.network))
+    }
+}
+
+fn mask(prefix: u8) -> u32 {
+    u32::MAX
+        .checked_shl(32 - prefix.min(32) as u32)
+        .unwrap_or(0)
+}
+
+/// The IPv6 subnet of a given prefix length that an address belongs to
+///
+/// Anything wider than a /112 holds far more addresses than can be probed, so
+/// host enumeration only walks the low-order bits up to a caller's limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv6Subnet {
+    pub network: Ipv6Addr,
+    pub prefix: u8,
+}
+
+impl Ipv6Subnet {
+    /// Subnet containing `ip`; prefixes above 128 are treated as /128
+    pub fn containing(ip: Ipv6Addr, prefix: u8) -> Self {
+        let prefix = prefix.min(128);
+        let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+        Self {
+            network: Ipv6Addr::from(u128::from(ip) & mask),
+            prefix,
+        }
+    }
+
+    /// Number of host addresses, saturating for a /0
+    pub fn host_count(&self) -> u128 {
+        let size = 1u128
+            .checked_shl(128 - self.prefix as u32)
+            .unwrap_or(u128::MAX);
+        match self.prefix {
+            127 | 128 => size,
+            // The all-zeros address is the subnet-router anycast address
+            _ => size - 1,
+        }
+    }
+
+    /// First and last host addresses
+    pub fn host_range(&self) -> (Ipv6Addr, Ipv6Addr) {
+        let network = u128::from(self.network);
+        let last = network | u128::MAX.checked_shr(self.prefix as u32).unwrap_or(0);
+        match self.prefix {
+            127 | 128 => (self.network, Ipv6Addr::from(last)),
+            _ => (Ipv6Addr::from(network + 1), Ipv6Addr::from(last)),
+        }
+    }
+
+    /// Up to `limit` host addresses counting up from the network address
+    pub fn hosts(&self, limit: usize) -> impl Iterator<Item = Ipv6Addr> + use<> {
+        let first = match self.prefix {
+            127 | 128 => u128::from(self.network),
+            _ => u128::from(self.network) + 1,
+        };
+        let count = self.host_count().min(limit as u128);
+        (0..count).map(move |n| Ipv6Addr::from(first + n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subnet_boundaries() {
+        let subnet = Ipv4Subnet::containing(Ipv4Addr::new(192, 168, 1, 137), 24);
+        assert_eq!(subnet.network, Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(subnet.broadcast, Ipv4Addr::new(192, 168, 1, 255));
+        assert_eq!(subnet.netmask(), Ipv4Addr::new(255, 255, 255, 0));
+        assert_eq!(subnet.host_count(), 254);
+        assert_eq!(subnet.offset_of(Ipv4Addr::new(192, 168, 1, 137)), Some(137));
+        assert_eq!(subnet.offset_of(Ipv4Addr::new(192, 168, 2, 1)), None);
+
+        let subnet = Ipv4Subnet::containing(Ipv4Addr::new(10, 20, 30, 40), 12);
+        assert_eq!(subnet.network, Ipv4Addr::new(10, 16, 0, 0));
+        assert_eq!(subnet.broadcast, Ipv4Addr::new(10, 31, 255, 255));
+    }
+
+    #[test]
+    fn test_point_to_point_and_whole_range() {
+        let subnet = Ipv4Subnet::containing(Ipv4Addr::new(10, 0, 0, 1), 31);
+        assert_eq!(subnet.host_count(), 2);
+        let subnet = Ipv4Subnet::containing(Ipv4Addr::new(10, 0, 0, 1), 32);
+        assert_eq!(
+            subnet.hosts().collect::<Vec<_>>(),
+            vec![Ipv4Addr::new(10, 0, 0, 1)]
+        );
+        let subnet = Ipv4Subnet::containing(Ipv4Addr::new(10, 0, 0, 1), 0);
+        assert_eq!(subnet.network, Ipv4Addr::UNSPECIFIED);
+        assert_eq!(subnet.broadcast, Ipv4Addr::BROADCAST);
+    }
+
+    #[test]
+    fn test_ipv6_hosts_walk_low_bits() {
+        let ip: Ipv6Addr = "fd00:1:2:3::abcd".parse().unwrap();
+        let subnet = Ipv6Subnet::containing(ip, 64);
+        assert_eq!(subnet.network, "fd00:1:2:3::".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(subnet.host_count(), u64::MAX as u128);
+
+        let hosts: Vec<_> = subnet.hosts(3).collect();
+        assert_eq!(
+            hosts,
+            ["fd00:1:2:3::1", "fd00:1:2:3::2", "fd00:1:2:3::3"]
+                .map(|h| h.parse::<Ipv6Addr>().unwrap())
+        );
+
+        let small = Ipv6Subnet::containing(ip, 120);
+        assert_eq!(small.hosts(usize::MAX).count(), 255);
+        assert_eq!(
+            Ipv6Subnet::containing(ip, 128)
+                .hosts(10)
+                .collect::<Vec<_>>(),
+            vec![ip]
+        );
+        assert_eq!(Ipv6Subnet::containing(ip, 0).host_count(), u128::MAX - 1);
+        assert_eq!(
+            small.host_range(),
+            (
+                "fd00:1:2:3::ab01".parse().unwrap(),
+                "fd00:1:2:3::abff".parse().unwrap()
+            )
+        );
+    }
+}
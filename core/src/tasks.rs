@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,19 +10,104 @@ pub struct Task {
     pub completed: bool,
 
     #[serde(skip)]
-    #[allow(unused)]
     pub state: TaskState,
 }
 
+impl Task {
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            description: description.into(),
+            completed: false,
+            state: TaskState::default(),
+        }
+    }
+
+    /// Channel for the work behind this task to report its state
+    ///
+    /// Hand the reporter to the work and pass each state that arrives on the
+    /// receiver to [`Task::set_state`].
+    pub fn progress_channel(&self) -> (ProgressReporter, UnboundedReceiver<TaskState>) {
+        let (states, rx) = mpsc::unbounded_channel();
+        (ProgressReporter { states }, rx)
+    }
+
+    /// Record reported state, completing the task once it's done
+    pub fn set_state(&mut self, state: TaskState) {
+        self.completed |= state.status == TaskStatus::Done;
+        self.state = state;
+    }
+}
+
+/// Where a task is in its lifecycle
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum TaskStatus {
+    /// Queued but not started
+    #[default]
+    Pending,
+    Running,
+    Done,
+    Failed(String),
+}
+
+/// Status of a task along with how much of it is finished
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskState {
+    pub status: TaskStatus,
+    /// Fraction complete, 0.0..=1.0
+    pub progress: f32,
+}
+
+impl TaskState {
+    pub fn running(progress: f32) -> Self {
+        Self {
+            status: TaskStatus::Running,
+            progress: progress.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn done() -> Self {
+        Self {
+            status: TaskStatus::Done,
+            progress: 1.0,
+        }
+    }
+
+    /// Failed with `reason`, keeping the progress made before the failure
+    pub fn failed(self, reason: impl Into<String>) -> Self {
+        Self {
+            status: TaskStatus::Failed(reason.into()),
+            ..self
+        }
+    }
+
+    /// Pending or running, so more progress is still expected
+    pub fn is_active(&self) -> bool {
+        matches!(self.status, TaskStatus::Pending | TaskStatus::Running)
+    }
+}
+
+/// Reports a task's state from the work doing it, see [`Task::progress_channel`]
+///
+/// Each method returns `false` once nobody is listening anymore.
 #[derive(Debug, Clone)]
-pub enum TaskState {
-    Idle,
-    Editing,
+pub struct ProgressReporter {
+    states: UnboundedSender<TaskState>,
 }
 
-impl Default for TaskState {
-    fn default() -> Self {
-        Self::Idle
+impl ProgressReporter {
+    pub fn progress(&self, progress: f32) -> bool {
+        self.states.send(TaskState::running(progress)).is_ok()
+    }
+
+    pub fn done(&self) -> bool {
+        self.states.send(TaskState::done()).is_ok()
+    }
+
+    pub fn fail(&self, reason: impl Into<String>) -> bool {
+        self.states
+            .send(TaskState::default().failed(reason))
+            .is_ok()
     }
 }
 
@@ -32,4 +118,45 @@ pub enum TaskMessage {
     DescriptionEdited(String),
     FinishEdition,
     Delete,
+    /// New state reported by the task's work
+    State(TaskState),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_over_channel() {
+        let mut task = Task::new("Scan 192.168.1.0/24");
+        assert!(task.state.is_active());
+
+        let (reporter, mut states) = task.progress_channel();
+        reporter.progress(0.25);
+        reporter.progress(2.0);
+        reporter.done();
+
+        task.set_state(states.try_recv().unwrap());
+        assert_eq!(task.state, TaskState::running(0.25));
+        // Out of range progress is clamped
+        task.set_state(states.try_recv().unwrap());
+        assert_eq!(task.state.progress, 1.0);
+        assert!(!task.completed);
+        task.set_state(states.try_recv().unwrap());
+        assert!(task.completed && !task.state.is_active());
+
+        drop(states);
+        assert!(!reporter.fail("gone"));
+    }
+
+    #[test]
+    fn test_failed_keeps_progress() {
+        let state = TaskState::running(0.5).failed("Network unreachable");
+        assert_eq!(
+            state.status,
+            TaskStatus::Failed(String::from("Network unreachable"))
+        );
+        assert_eq!(state.progress, 0.5);
+        assert!(!state.is_active());
+    }
 }
@@ -0,0 +1,354 @@
+//! COSMIC Theme Integration Demo
+//!
+//! This example demonstrates how to use Net Monkey's adaptive theme system
+//! with COSMIC desktop integration.
+//!
+//! To run this example:
+//! ```bash
+//! # Standard mode (cross-platform)
+//! cargo run -p net_monkey_examples --bin cosmic_theme_demo
+//!
+//! # With COSMIC integration (Linux/COSMIC)
+//! cargo run -p net_monkey_examples --bin cosmic_theme_demo --features cosmic
+//! ```
+
+use iced::widget::{button, column, container, horizontal_space, row, text, vertical_space};
+use iced::{Color, Element, Length, Task, Theme};
+
+// Import theme system with conditional COSMIC support
+use net_monkey_theme::{AdaptiveThemeManager, NetworkSpecificColors, ThemeManager};
+
+#[cfg(feature = "cosmic")]
+use net_monkey_theme::is_cosmic_environment;
+
+fn main() -> iced::Result {
+    println!("🐒 Net Monkey COSMIC Theme Demo");
+
+    #[cfg(feature = "cosmic")]
+    {
+        if is_cosmic_environment() {
+            println!("🚀 COSMIC desktop environment detected!");
+        } else {
+            println!("📱 Non-COSMIC environment - will use fallback themes");
+        }
+    }
+
+    #[cfg(not(feature = "cosmic"))]
+    {
+        println!("📦 Built without COSMIC integration");
+    }
+
+    iced::application(ThemeDemo::title, ThemeDemo::update, ThemeDemo::view)
+        .theme(ThemeDemo::theme)
+        .run_with(ThemeDemo::new)
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ThemeChanged(String),
+    RefreshTheme,
+    ShowNetworkColors,
+}
+
+pub struct ThemeDemo {
+    theme_manager: AdaptiveThemeManager,
+    available_themes: Vec<String>,
+    show_network_colors: bool,
+}
+
+impl ThemeDemo {
+    fn new() -> (Self, Task<Message>) {
+        // Saved themes would normally live in the app's data directory
+        let themes = ThemeManager::new(std::env::temp_dir().join("net_monkey_theme_demo"));
+        let theme_manager = AdaptiveThemeManager::new(themes);
+        let available_themes = theme_manager.available_themes();
+
+        let app = ThemeDemo {
+            theme_manager,
+            available_themes,
+            show_network_colors: false,
+        };
+
+        (app, Task::none())
+    }
+
+    fn title(&self) -> String {
+        format!("COSMIC Theme Demo - {}", self.theme_manager.name())
+    }
+
+    fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::ThemeChanged(theme_name) => {
+                if let Err(e) = self.theme_manager.switch_theme(&theme_name) {
+                    eprintln!("Failed to switch theme: {}", e);
+                }
+            }
+            Message::RefreshTheme => {
+                if let Err(e) = self.theme_manager.refresh() {
+                    eprintln!("Failed to refresh theme: {}", e);
+                }
+                self.available_themes = self.theme_manager.available_themes();
+            }
+            Message::ShowNetworkColors => {
+                self.show_network_colors = !self.show_network_colors;
+            }
+        }
+        Task::none()
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let theme_info = self.build_theme_info();
+        let theme_controls = self.build_theme_controls();
+        let color_demos = self.build_color_demos();
+        let network_colors = if self.show_network_colors {
+            self.build_network_colors()
+        } else {
+            Element::from(text(
+                "Click 'Show Network Colors' to see network-specific color palette",
+            ))
+        };
+
+        let content = column![
+            text("🐒 Net Monkey Theme Demo")
+                .size(24)
+                .style(|theme: &Theme| text::Style {
+                    color: Some(theme.palette().primary),
+                }),
+            vertical_space().height(20),
+            theme_info,
+            vertical_space().height(20),
+            theme_controls,
+            vertical_space().height(20),
+            text("Color Demonstrations:").size(18),
+            color_demos,
+            vertical_space().height(20),
+            text("Network-Specific Colors:").size(18),
+            network_colors,
+        ]
+        .spacing(10)
+        .padding(20);
+
+        container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x(Length::Fill)
+            .center_y(Length::Fill)
+            .into()
+    }
+
+    fn theme(&self) -> Theme {
+        self.theme_manager.iced_theme()
+    }
+
+    fn build_theme_info(&self) -> Element<'_, Message> {
+        let cosmic_status = self.get_cosmic_status();
+        let theme_type = if self.theme_manager.is_dark() {
+            "Dark"
+        } else {
+            "Light"
+        };
+
+        column![
+            text(format!("Current Theme: {}", self.theme_manager.name())),
+            text(format!("Theme Type: {}", theme_type)),
+            text(format!("COSMIC Status: {}", cosmic_status)),
+            text(format!(
+                "COSMIC Active: {}",
+                self.theme_manager.is_cosmic_active()
+            )),
+        ]
+        .spacing(5)
+        .into()
+    }
+
+    fn build_theme_controls(&self) -> Element<'_, Message> {
+        let mut theme_buttons = row![];
+
+        for theme_name in &self.available_themes {
+            let is_current = match self.theme_manager.is_following_system() {
+                true => theme_name == net_monkey_theme::SYSTEM_THEME,
+                false => theme_name == &self.theme_manager.name(),
+            };
+            let button = button(text(theme_name))
+                .on_press(Message::ThemeChanged(theme_name.clone()))
+                .style(if is_current {
+                    button::primary
+                } else {
+                    button::secondary
+                });
+            theme_buttons = theme_buttons
+                .push(button)
+                .push(horizontal_space().width(10));
+        }
+
+        column![
+            text("Available Themes:"),
+            theme_buttons,
+            vertical_space().height(10),
+            row![
+                button("Refresh Theme").on_press(Message::RefreshTheme),
+                horizontal_space().width(10),
+                button(if self.show_network_colors {
+                    "Hide Network Colors"
+                } else {
+                    "Show Network Colors"
+                })
+                .on_press(Message::ShowNetworkColors),
+            ]
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn build_color_demos(&self) -> Element<'_, Message> {
+        let colors = self.theme_manager.colors();
+
+        let color_boxes = row![
+            self.color_box("Primary", colors.primary_color()),
+            self.color_box("Success", colors.success_color()),
+            self.color_box("Warning", colors.warning_color()),
+            self.color_box("Danger", colors.danger_color()),
+        ]
+        .spacing(15);
+
+        let background_boxes = row![
+            self.color_box("Background", colors.background_color()),
+            self.color_box("Container", colors.container_color()),
+            self.color_box("Text", colors.text_color()),
+        ]
+        .spacing(15);
+
+        column![
+            text("Semantic Colors:"),
+            color_boxes,
+            vertical_space().height(10),
+            text("Background Colors:"),
+            background_boxes,
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn build_network_colors(&self) -> Element<'_, Message> {
+        let NetworkSpecificColors {
+            online,
+            offline,
+            timeout,
+            scanning,
+            low_latency,
+            medium_latency,
+            high_latency,
+        } = self.theme_manager.network_colors();
+
+        let status_colors = row![
+            self.color_box("Online", online),
+            self.color_box("Offline", offline),
+            self.color_box("Timeout", timeout),
+            self.color_box("Scanning", scanning),
+        ]
+        .spacing(15);
+
+        let performance_colors = row![
+            self.color_box("Low Latency", low_latency),
+            self.color_box("Medium Latency", medium_latency),
+            self.color_box("High Latency", high_latency),
+        ]
+        .spacing(15);
+
+        column![
+            text("Connection Status:"),
+            status_colors,
+            vertical_space().height(10),
+            text("Performance Indicators:"),
+            performance_colors,
+            vertical_space().height(10),
+            self.build_cosmic_info(),
+        ]
+        .spacing(10)
+        .into()
+    }
+
+    fn color_box(&self, label: &str, color: Color) -> Element<'_, Message> {
+        column![
+            container(text(""))
+                .width(80)
+                .height(50)
+                .style(move |_theme: &Theme| {
+                    container::Style {
+                        background: Some(color.into()),
+                        border: iced::Border {
+                            color: Color::BLACK,
+                            width: 1.0,
+                            radius: 4.0.into(),
+                        },
+                        ..Default::default()
+                    }
+                }),
+            text(label.to_string()).size(12),
+            text(format!(
+                "#{:02X}{:02X}{:02X}",
+                (color.r * 255.0) as u8,
+                (color.g * 255.0) as u8,
+                (color.b * 255.0) as u8
+            ))
+            .size(10),
+        ]
+        .spacing(5)
+        .align_x(iced::alignment::Horizontal::Center)
+        .into()
+    }
+
+    fn get_cosmic_status(&self) -> &'static str {
+        #[cfg(feature = "cosmic")]
+        {
+            if is_cosmic_environment() {
+                "Available"
+            } else {
+                "Not Available (Non-COSMIC Environment)"
+            }
+        }
+        #[cfg(not(feature = "cosmic"))]
+        {
+            "Not Compiled (Missing 'cosmic' feature)"
+        }
+    }
+
+    fn build_cosmic_info(&self) -> Element<'_, Message> {
+        #[cfg(feature = "cosmic")]
+        {
+            if self.theme_manager.is_cosmic_active() {
+                column![
+                    text("🚀 Using COSMIC Theme Integration").style(|theme: &Theme| text::Style {
+                        color: Some(theme.palette().success),
+                    }),
+                    text("Colors are derived from the active COSMIC theme").size(12),
+                    text("Refresh to pick up a system theme change").size(12),
+                ]
+                .spacing(5)
+                .into()
+            } else {
+                column![
+                    text("📱 Using Standard Theme System").style(|theme: &Theme| text::Style {
+                        color: Some(theme.palette().primary),
+                    }),
+                    text("Colors are manually defined in theme files").size(12),
+                    text("Enable COSMIC integration by building with --features cosmic").size(12),
+                ]
+                .spacing(5)
+                .into()
+            }
+        }
+        #[cfg(not(feature = "cosmic"))]
+        {
+            column![
+                text("📦 COSMIC Integration Not Available").style(|theme: &Theme| text::Style {
+                    color: Some(theme.palette().danger),
+                }),
+                text("Build with --features cosmic to enable COSMIC integration").size(12),
+                text("Currently using standard JSON-based themes").size(12),
+            ]
+            .spacing(5)
+            .into()
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Conversions between the stored RGBA colors and HSL for the theme editor
+
+/// A color as hue, saturation and lightness
+///
+/// Hue is in degrees (0.0..360.0), saturation and lightness are 0.0..=1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+impl Hsl {
+    /// HSL of an RGBA color, ignoring alpha; grays get a hue of 0
+    pub fn from_rgba([r, g, b, _]: [f32; 4]) -> Self {
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+        if delta <= f32::EPSILON {
+            return Self { h: 0.0, s: 0.0, l };
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = match max {
+            _ if max == r => 60.0 * ((g - b) / delta).rem_euclid(6.0),
+            _ if max == g => 60.0 * ((b - r) / delta + 2.0),
+            _ => 60.0 * ((r - g) / delta + 4.0),
+        };
+        Self {
+            h,
+            s: s.clamp(0.0, 1.0),
+            l,
+        }
+    }
+
+    /// RGBA color with the given alpha
+    pub fn to_rgba(self, alpha: f32) -> [f32; 4] {
+        let (s, l) = (self.s.clamp(0.0, 1.0), self.l.clamp(0.0, 1.0));
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        [r + m, g + m, b + m, alpha]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: [f32; 4], b: [f32; 4]) {
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).abs() < 1e-5, "{a} != {b}");
+        }
+    }
+
+    #[test]
+    fn test_hsl_round_trips() {
+        let red = [1.0, 0.0, 0.0, 1.0];
+        assert_eq!(
+            Hsl::from_rgba(red),
+            Hsl {
+                h: 0.0,
+                s: 1.0,
+                l: 0.5
+            }
+        );
+        assert_close(Hsl::from_rgba(red).to_rgba(1.0), red);
+
+        let gray = [0.5, 0.5, 0.5, 0.8];
+        assert_eq!(
+            Hsl::from_rgba(gray),
+            Hsl {
+                h: 0.0,
+                s: 0.0,
+                l: 0.5
+            }
+        );
+        assert_close(Hsl::from_rgba(gray).to_rgba(0.8), gray);
+
+        // #336699
+        let mid = [0.2, 0.4, 0.6, 1.0];
+        let hsl = Hsl::from_rgba(mid);
+        assert!((hsl.h - 210.0).abs() < 1e-3);
+        assert!((hsl.s - 0.5).abs() < 1e-5);
+        assert!((hsl.l - 0.4).abs() < 1e-5);
+        assert_close(hsl.to_rgba(1.0), mid);
+    }
+}
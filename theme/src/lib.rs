@@ -12,6 +12,18 @@ use palette;
 use iced::Theme;
 use serde::{Deserialize, Serialize};
 
+pub mod colors;
+pub mod manager;
+pub mod network;
+pub mod system;
+
+pub use colors::Hsl;
+pub use manager::{
+    AdaptiveThemeManager, HIGH_CONTRAST_THEME, SYSTEM_THEME, ThemeChanged, ThemeDefinition,
+    ThemeDraft, ThemeManager,
+};
+pub use network::NetworkSpecificColors;
+
 /// Simple color structure for basic theming needs
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct SimpleColors {
@@ -73,6 +85,15 @@ impl SimpleColors {
             self.danger[3],
         )
     }
+
+    /// Color for a ping, success up to `good` ms fading through warning to
+    /// danger at `bad` ms
+    pub fn latency_color(&self, ping_ms: u128, good: u128, bad: u128) -> iced::Color {
+        let span = bad.saturating_sub(good).max(1) as f32;
+        let fraction = (ping_ms as f32 - good as f32) / span;
+        NetworkSpecificColors::from_colors(self).latency(fraction)
+    }
+
     /// Get a lighter version of primary color for hover/secondary elements
     pub fn primary_light(&self) -> iced::Color {
         let primary = self.primary_color();
@@ -112,11 +133,78 @@ impl SimpleColors {
         iced::Color::from_rgba(text.r, text.g, text.b, 0.3)
     }
 
+    /// WCAG 2.1 contrast ratio between two colors, from 1.0 up to 21.0
+    ///
+    /// Alpha is ignored. Body text needs at least 4.5:1 to meet level AA.
+    pub fn contrast_ratio(fg: [f32; 4], bg: [f32; 4]) -> f32 {
+        let (fg, bg) = (relative_luminance(fg), relative_luminance(bg));
+        let (lighter, darker) = (fg.max(bg), fg.min(bg));
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// Contrast of the text color on the background
+    pub fn text_on_background_ratio(&self) -> f32 {
+        Self::contrast_ratio(self.text, self.background)
+    }
+
     /// Check if this is a dark theme
     pub fn is_dark(&self) -> bool {
         self.background[0] + self.background[1] + self.background[2] < 1.5
     }
 
+    /// Derive a whole palette from one accent color
+    ///
+    /// The background and text are near-black and near-white (or the reverse
+    /// for light themes) tinted with the accent's hue, and the accent itself
+    /// becomes the primary color, lightened or darkened until it stands out
+    /// from the background. Status colors come from the matching fallback theme.
+    pub fn from_accent(accent: [f32; 4], dark: bool) -> Self {
+        let base = match dark {
+            true => Self::DARK,
+            false => Self::LIGHT,
+        };
+        let hsl = Hsl::from_rgba(accent);
+        let tint = |s: f32, l: f32| {
+            Hsl {
+                h: hsl.h,
+                s: hsl.s.min(s),
+                l,
+            }
+            .to_rgba(1.0)
+        };
+        let (background, text) = match dark {
+            true => (tint(0.25, 0.1), tint(0.1, 0.92)),
+            false => (tint(0.25, 0.96), tint(0.1, 0.1)),
+        };
+        // A strongly saturated tint can still fall short, plain text never does
+        let text = match Self::contrast_ratio(text, background) >= MIN_TEXT_CONTRAST {
+            true => text,
+            false => base.text,
+        };
+
+        let mut primary = hsl;
+        while Self::contrast_ratio(primary.to_rgba(1.0), background) < MIN_ACCENT_CONTRAST
+            && (0.05..=0.95).contains(&primary.l)
+        {
+            primary.l += if dark { 0.05 } else { -0.05 };
+        }
+
+        Self {
+            background,
+            text,
+            primary: primary.to_rgba(accent[3]),
+            ..base
+        }
+    }
+
+    /// [`Self::DARK`] or [`Self::LIGHT`] to match the OS appearance
+    pub fn system() -> Self {
+        match system::prefers_dark() {
+            true => Self::DARK,
+            false => Self::LIGHT,
+        }
+    }
+
     /// Dark theme colors (fallback)
     pub const DARK: Self = Self {
         background: [0.1, 0.1, 0.1, 1.0],
@@ -136,6 +224,32 @@ impl SimpleColors {
         warning: [0.8, 0.4, 0.0, 1.0],
         danger: [0.8, 0.0, 0.0, 1.0],
     };
+
+    /// White on black with no hues, for `NO_COLOR` and low vision. States
+    /// differ only in brightness
+    pub const HIGH_CONTRAST: Self = Self {
+        background: [0.0, 0.0, 0.0, 1.0],
+        text: [1.0, 1.0, 1.0, 1.0],
+        primary: [1.0, 1.0, 1.0, 1.0],
+        success: [1.0, 1.0, 1.0, 1.0],
+        warning: [0.8, 0.8, 0.8, 1.0],
+        danger: [0.6, 0.6, 0.6, 1.0],
+    };
+}
+
+/// Minimum WCAG AA contrast ratio for normal sized text
+pub const MIN_TEXT_CONTRAST: f32 = 4.5;
+
+/// Minimum WCAG AA contrast ratio for buttons and other non-text elements
+pub const MIN_ACCENT_CONTRAST: f32 = 3.0;
+
+/// WCAG relative luminance of an sRGB color
+fn relative_luminance([r, g, b, _]: [f32; 4]) -> f32 {
+    let linear = |c: f32| match c.clamp(0.0, 1.0) {
+        c if c <= 0.04045 => c / 12.92,
+        c => ((c + 0.055) / 1.055).powf(2.4),
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
 }
 
 /// Theme provider that uses COSMIC when available, falls back to simple themes
@@ -158,8 +272,8 @@ impl Default for ThemeProvider {
             }
         }
 
-        // Fallback to dark theme
-        Self::Fallback(SimpleColors::DARK)
+        // Fallback to the OS light/dark preference
+        Self::Fallback(SimpleColors::system())
     }
 }
 
@@ -296,8 +410,8 @@ fn cosmic_color_to_array(color: palette::Srgba) -> [f32; 4] {
 /// Helper functions for common theming operations
 pub mod helpers {
     use super::*;
-    use iced::Element;
-    use iced::widget::{container, text};
+    use iced::widget::{button, container, text};
+    use iced::{Background, Border, Color, Element};
 
     /// Create a themed container
     pub fn themed_container<'a, Message>(
@@ -353,6 +467,123 @@ pub mod helpers {
         Info,
     }
 
+    /// Button variants for [`themed_button`]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ButtonVariant {
+        /// Filled with the primary color, for the main action
+        Primary,
+        /// Subtle outlined button, highlighted with the primary color on hover
+        Secondary,
+        /// Filled with the success color
+        Success,
+        /// Filled with the danger color, for destructive actions
+        Danger,
+        /// No background or border, just primary colored text
+        Text,
+    }
+
+    /// Style function for buttons using the current theme colors
+    ///
+    /// ```rust,ignore
+    /// button("Scan").style(helpers::themed_button(ButtonVariant::Primary, &theme_provider))
+    /// ```
+    pub fn themed_button(
+        variant: ButtonVariant,
+        theme_provider: &ThemeProvider,
+    ) -> impl Fn(&Theme, button::Status) -> button::Style + 'static {
+        let colors = theme_provider.colors();
+        move |_theme, status| button_style(colors, variant, status)
+    }
+
+    fn button_style(
+        colors: SimpleColors,
+        variant: ButtonVariant,
+        status: button::Status,
+    ) -> button::Style {
+        let text_color = colors.text_color();
+        let primary = colors.primary_color();
+
+        let style = match variant {
+            ButtonVariant::Primary | ButtonVariant::Success | ButtonVariant::Danger => {
+                let base = match variant {
+                    ButtonVariant::Success => colors.success_color(),
+                    ButtonVariant::Danger => colors.danger_color(),
+                    _ => primary,
+                };
+                let fill = match status {
+                    button::Status::Hovered => scale_rgb(base, 1.1),
+                    button::Status::Pressed => scale_rgb(base, 0.85),
+                    _ => base,
+                };
+                button::Style {
+                    background: Some(Background::Color(fill)),
+                    text_color: colors.background_color(),
+                    border: Border {
+                        color: fill,
+                        width: 2.0,
+                        radius: 4.0.into(),
+                    },
+                    ..Default::default()
+                }
+            }
+            ButtonVariant::Secondary => {
+                let (background, border) = match status {
+                    button::Status::Hovered => (with_alpha(primary, 0.2), primary),
+                    button::Status::Pressed => (with_alpha(primary, 0.3), primary),
+                    _ => (with_alpha(text_color, 0.1), colors.border_color()),
+                };
+                button::Style {
+                    background: Some(Background::Color(background)),
+                    text_color,
+                    border: Border {
+                        color: border,
+                        width: 1.0,
+                        radius: 4.0.into(),
+                    },
+                    ..Default::default()
+                }
+            }
+            ButtonVariant::Text => button::Style {
+                background: match status {
+                    button::Status::Hovered | button::Status::Pressed => {
+                        Some(Background::Color(colors.primary_light()))
+                    }
+                    _ => None,
+                },
+                text_color: primary,
+                ..Default::default()
+            },
+        };
+
+        match status {
+            button::Status::Disabled => button::Style {
+                background: style
+                    .background
+                    .map(|background| background.scale_alpha(0.5)),
+                text_color: with_alpha(style.text_color, style.text_color.a * 0.5),
+                border: Border {
+                    color: with_alpha(style.border.color, style.border.color.a * 0.5),
+                    ..style.border
+                },
+                ..style
+            },
+            _ => style,
+        }
+    }
+
+    fn with_alpha(color: Color, a: f32) -> Color {
+        Color { a, ..color }
+    }
+
+    fn scale_rgb(color: Color, factor: f32) -> Color {
+        Color::from_rgba(
+            (color.r * factor).min(1.0),
+            (color.g * factor).min(1.0),
+            (color.b * factor).min(1.0),
+            color.a,
+        )
+    }
+
     /// Legacy helper for menu containers (maps to themed_container)
     pub fn menu_container<'a, Message>(
         content: impl Into<Element<'a, Message>>,
@@ -409,6 +640,81 @@ mod tests {
         assert!(!light_provider.is_dark());
     }
 
+    #[test]
+    fn test_latency_color() {
+        let colors = SimpleColors::DARK;
+        assert_eq!(colors.latency_color(10, 50, 150), colors.success_color());
+        assert_eq!(colors.latency_color(100, 50, 150), colors.warning_color());
+        assert_eq!(colors.latency_color(400, 50, 150), colors.danger_color());
+    }
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = [0.0, 0.0, 0.0, 1.0];
+        let white = [1.0, 1.0, 1.0, 1.0];
+        assert!((SimpleColors::contrast_ratio(black, white) - 21.0).abs() < 0.01);
+        // Order doesn't matter
+        assert_eq!(
+            SimpleColors::contrast_ratio(white, black),
+            SimpleColors::contrast_ratio(black, white)
+        );
+        assert_eq!(SimpleColors::contrast_ratio(white, white), 1.0);
+
+        // #777777 on white sits just under the AA threshold
+        let grey = [119.0 / 255.0, 119.0 / 255.0, 119.0 / 255.0, 1.0];
+        let ratio = SimpleColors::contrast_ratio(grey, white);
+        assert!((ratio - 4.48).abs() < 0.01);
+
+        assert!(SimpleColors::DARK.text_on_background_ratio() >= MIN_TEXT_CONTRAST);
+        assert!(SimpleColors::LIGHT.text_on_background_ratio() >= MIN_TEXT_CONTRAST);
+    }
+
+    #[test]
+    fn test_from_accent() {
+        let purple = [0.5, 0.2, 0.9, 1.0];
+        for dark in [true, false] {
+            let colors = SimpleColors::from_accent(purple, dark);
+            assert_eq!(colors.is_dark(), dark);
+            assert!(colors.text_on_background_ratio() >= MIN_TEXT_CONTRAST);
+            assert!(
+                SimpleColors::contrast_ratio(colors.primary, colors.background)
+                    >= MIN_ACCENT_CONTRAST
+            );
+            // The background is a shade of the accent
+            let hue = Hsl::from_rgba(colors.background).h;
+            assert!((hue - Hsl::from_rgba(purple).h).abs() < 1.0);
+        }
+
+        // A dark accent is lifted enough to show up on a dark background
+        let navy = [0.0, 0.0, 0.3, 1.0];
+        let colors = SimpleColors::from_accent(navy, true);
+        assert!(SimpleColors::contrast_ratio(colors.primary, colors.background) >= 3.0);
+    }
+
+    #[test]
+    fn test_themed_button_states() {
+        use helpers::{ButtonVariant, themed_button};
+        use iced::widget::button::Status;
+
+        let provider = ThemeProvider::fallback(SimpleColors::DARK);
+        let theme = provider.to_iced_theme();
+
+        let primary = themed_button(ButtonVariant::Primary, &provider);
+        let active = primary(&theme, Status::Active);
+        assert_eq!(
+            active.background,
+            Some(iced::Background::Color(SimpleColors::DARK.primary_color()))
+        );
+        assert_ne!(primary(&theme, Status::Hovered), active);
+        assert_ne!(primary(&theme, Status::Pressed), active);
+
+        let disabled = primary(&theme, Status::Disabled);
+        assert!(disabled.text_color.a < active.text_color.a);
+
+        let text_button = themed_button(ButtonVariant::Text, &provider);
+        assert_eq!(text_button(&theme, Status::Active).background, None);
+    }
+
     #[cfg(feature = "cosmic")]
     #[test]
     fn test_cosmic_environment_detection() {
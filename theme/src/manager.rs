@@ -0,0 +1,563 @@
+//! Saving and loading custom themes, plus the theme editor's unsaved draft
+//! and switching between them and the system theme
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use iced::Subscription;
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, Stream, StreamExt};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+
+use crate::{NetworkSpecificColors, SimpleColors, ThemeProvider};
+
+/// Built-in black and white theme, used when `NO_COLOR` is set
+pub const HIGH_CONTRAST_THEME: &str = "High Contrast";
+
+/// Files starting with this prefix hold work in progress and aren't listed as themes
+pub const EDITING_PREFIX: &str = "editing_";
+
+/// A named set of colors stored as JSON in the themes directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThemeDefinition {
+    pub name: String,
+    pub colors: SimpleColors,
+}
+
+impl ThemeDefinition {
+    /// Themes that are always available without touching disk
+    pub fn builtin() -> Vec<Self> {
+        vec![
+            Self {
+                name: String::from("Dark"),
+                colors: SimpleColors::DARK,
+            },
+            Self {
+                name: String::from("Light"),
+                colors: SimpleColors::LIGHT,
+            },
+            Self {
+                name: String::from(HIGH_CONTRAST_THEME),
+                colors: SimpleColors::HIGH_CONTRAST,
+            },
+        ]
+    }
+
+    /// Check a theme read from an untrusted file is usable
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err(String::from("theme has no name"));
+        }
+        let colors = &self.colors;
+        let channels = [
+            ("background", colors.background),
+            ("text", colors.text),
+            ("primary", colors.primary),
+            ("success", colors.success),
+            ("warning", colors.warning),
+            ("danger", colors.danger),
+        ];
+        match channels
+            .iter()
+            .find(|(_, color)| !color.iter().all(|c| (0.0..=1.0).contains(c)))
+        {
+            Some((field, _)) => Err(format!("{field} color channels must be between 0 and 1")),
+            None => Ok(()),
+        }
+    }
+
+    /// Write the theme as a standalone JSON file that can be shared and imported
+    pub fn export(&self, path: &Path) -> io::Result<()> {
+        write_json(path, self)
+    }
+}
+
+/// The theme editor's working buffer, kept on disk so edits survive a restart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThemeDraft {
+    /// Theme the edits started from
+    pub base_theme: String,
+    /// Colors as currently edited
+    pub colors: SimpleColors,
+}
+
+/// A theme file was created, edited or removed outside the editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeChanged {
+    pub path: PathBuf,
+}
+
+/// Reads and writes themes in a single directory
+#[derive(Debug, Clone)]
+pub struct ThemeManager {
+    dir: PathBuf,
+}
+
+impl ThemeManager {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Directory the themes are stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Names of built-in and saved themes, skipping editing files
+    pub fn available_themes(&self) -> Vec<String> {
+        let mut names: Vec<String> = ThemeDefinition::builtin()
+            .into_iter()
+            .map(|theme| theme.name)
+            .collect();
+        let saved = std::fs::read_dir(&self.dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let stem = path.file_stem()?.to_str()?;
+                let is_json = path.extension().is_some_and(|ext| ext == "json");
+                (is_json && !stem.starts_with(EDITING_PREFIX)).then_some(path)
+            })
+            .filter_map(|path| read_json::<ThemeDefinition>(&path).map(|theme| theme.name));
+        for name in saved {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Load a built-in or saved theme by name
+    pub fn load_theme(&self, name: &str) -> Option<ThemeDefinition> {
+        ThemeDefinition::builtin()
+            .into_iter()
+            .find(|theme| theme.name == name)
+            .or_else(|| {
+                read_json(&self.theme_path(name))
+                    .filter(|theme: &ThemeDefinition| theme.name == name)
+            })
+    }
+
+    /// Write a theme to `<dir>/<name>.json`, replacing any existing one
+    ///
+    /// Names differing only in characters that can't go in a file name, like
+    /// "Night Shift" and "Night_Shift", share a file, so saving one over the
+    /// other fails with [`io::ErrorKind::AlreadyExists`].
+    pub fn save_theme(&self, theme: &ThemeDefinition) -> io::Result<()> {
+        let path = self.theme_path(&theme.name);
+        match read_json::<ThemeDefinition>(&path) {
+            Some(saved) if saved.name != theme.name => Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("the name is too close to the saved theme {}", saved.name),
+            )),
+            _ => write_json(&path, theme),
+        }
+    }
+
+    /// Add a theme shared as a standalone JSON file to the directory
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] if the file isn't a valid
+    /// theme definition or would shadow a built-in theme.
+    pub fn import_theme(&self, path: &Path) -> io::Result<ThemeDefinition> {
+        let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+        let theme: ThemeDefinition = serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| invalid(e.to_string()))?;
+        theme.validate().map_err(invalid)?;
+        if ThemeDefinition::builtin()
+            .iter()
+            .any(|builtin| builtin.name == theme.name)
+        {
+            return Err(invalid(format!("{} is a built-in theme", theme.name)));
+        }
+        self.save_theme(&theme)?;
+        Ok(theme)
+    }
+
+    /// Persist the editor's working buffer
+    pub fn save_draft(&self, draft: &ThemeDraft) -> io::Result<()> {
+        write_json(&self.draft_path(), draft)
+    }
+
+    /// The draft left behind by a previous session, if any
+    pub fn load_draft(&self) -> Option<ThemeDraft> {
+        read_json(&self.draft_path())
+    }
+
+    /// Remove the draft once it has been saved or discarded
+    pub fn discard_draft(&self) -> io::Result<()> {
+        match std::fs::remove_file(self.draft_path()) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Emits whenever a theme file in the directory changes on disk
+    ///
+    /// Editing files are ignored, so the editor's own draft saves don't
+    /// trigger a reload.
+    pub fn watch(&self) -> Subscription<ThemeChanged> {
+        Subscription::run_with_id(self.dir.clone(), watch_dir(self.dir.clone()))
+    }
+
+    fn theme_path(&self, name: &str) -> PathBuf {
+        // Keep names usable as file names on every platform
+        let stem: String = name
+            .chars()
+            .map(|c| match c.is_ascii_alphanumeric() || c == '-' {
+                true => c,
+                false => '_',
+            })
+            .collect();
+        self.dir.join(format!("{stem}.json"))
+    }
+
+    fn draft_path(&self) -> PathBuf {
+        self.dir.join(format!("{EDITING_PREFIX}draft.json"))
+    }
+}
+
+/// Name [`AdaptiveThemeManager`] lists for the COSMIC or OS-matched theme
+pub const SYSTEM_THEME: &str = "System";
+
+/// The theme in use, switchable between the system theme and any theme the
+/// [`ThemeManager`] knows about
+///
+/// Starts out following the system: COSMIC's theme where available, and
+/// otherwise the built-in theme matching the OS light/dark preference.
+#[derive(Debug, Clone)]
+pub struct AdaptiveThemeManager {
+    themes: ThemeManager,
+    provider: ThemeProvider,
+    /// Theme switched to by name, `None` while following the system
+    selected: Option<String>,
+}
+
+impl AdaptiveThemeManager {
+    pub fn new(themes: ThemeManager) -> Self {
+        Self {
+            themes,
+            provider: ThemeProvider::default(),
+            selected: None,
+        }
+    }
+
+    /// [`SYSTEM_THEME`] followed by the built-in and saved themes
+    pub fn available_themes(&self) -> Vec<String> {
+        let mut names = vec![String::from(SYSTEM_THEME)];
+        names.extend(self.themes.available_themes());
+        names
+    }
+
+    /// Use the named theme, or the system theme for [`SYSTEM_THEME`]
+    pub fn switch_theme(&mut self, name: &str) -> Result<(), String> {
+        if name == SYSTEM_THEME {
+            self.provider = ThemeProvider::default();
+            self.selected = None;
+            return Ok(());
+        }
+        let theme = self
+            .themes
+            .load_theme(name)
+            .ok_or_else(|| format!("No theme named {name}"))?;
+        self.provider = ThemeProvider::fallback(theme.colors);
+        self.selected = Some(theme.name);
+        Ok(())
+    }
+
+    /// Re-read the system theme, leaving a theme chosen by name alone
+    pub fn refresh(&mut self) -> Result<(), &'static str> {
+        match (&self.selected, self.provider.is_cosmic_active()) {
+            (Some(_), _) => Ok(()),
+            (None, true) => self.provider.refresh(),
+            (None, false) => {
+                self.provider = ThemeProvider::default();
+                Ok(())
+            }
+        }
+    }
+
+    /// Name of the chosen theme, or the system theme's own name
+    pub fn name(&self) -> String {
+        self.selected
+            .clone()
+            .unwrap_or_else(|| self.provider.name().to_string())
+    }
+
+    /// Whether the system theme is in use rather than one chosen by name
+    pub fn is_following_system(&self) -> bool {
+        self.selected.is_none()
+    }
+
+    pub fn is_dark(&self) -> bool {
+        self.provider.is_dark()
+    }
+
+    pub fn is_cosmic_active(&self) -> bool {
+        self.provider.is_cosmic_active()
+    }
+
+    pub fn colors(&self) -> SimpleColors {
+        self.provider.colors()
+    }
+
+    pub fn network_colors(&self) -> NetworkSpecificColors {
+        NetworkSpecificColors::from_colors(&self.colors())
+    }
+
+    pub fn iced_theme(&self) -> iced::Theme {
+        self.provider.to_iced_theme()
+    }
+
+    pub fn provider(&self) -> &ThemeProvider {
+        &self.provider
+    }
+
+    /// The saved themes it switches between
+    pub fn themes(&self) -> &ThemeManager {
+        &self.themes
+    }
+}
+
+fn watch_dir(dir: PathBuf) -> impl Stream<Item = ThemeChanged> {
+    iced::stream::channel(16, move |mut output| async move {
+        // notify calls back on its own thread, so hand events over a channel
+        let (tx, mut events) = mpsc::unbounded();
+        let watcher = notify::recommended_watcher(move |event| {
+            let _ = tx.unbounded_send(event);
+        })
+        .and_then(|mut watcher| {
+            std::fs::create_dir_all(&dir).map_err(notify::Error::io)?;
+            watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+        // Dropping the watcher stops the events, so keep it for the stream's lifetime
+        let _watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                eprintln!("Failed to watch themes in {}: {e}", dir.display());
+                return;
+            }
+        };
+        while let Some(event) = events.next().await {
+            let event: notify::Event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    eprintln!("Theme watcher error: {e}");
+                    continue;
+                }
+            };
+            for path in changed_themes(&event) {
+                let _ = output.send(ThemeChanged { path }).await;
+            }
+        }
+    })
+}
+
+/// Theme files touched by a file system event
+fn changed_themes(event: &notify::Event) -> impl Iterator<Item = PathBuf> + '_ {
+    let relevant = matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    );
+    event
+        .paths
+        .iter()
+        .filter(move |path| {
+            let is_json = path.extension().is_some_and(|ext| ext == "json");
+            let is_editing = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| stem.starts_with(EDITING_PREFIX));
+            relevant && is_json && !is_editing
+        })
+        .cloned()
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Option<T> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_manager(name: &str) -> ThemeManager {
+        let dir = std::env::temp_dir().join(format!("net_monkey_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        ThemeManager::new(dir)
+    }
+
+    #[test]
+    fn test_adaptive_manager_switches_themes() {
+        let manager = temp_manager("adaptive");
+        let night = ThemeDefinition {
+            name: String::from("Night Shift"),
+            colors: SimpleColors::from_accent([0.5, 0.2, 0.9, 1.0], true),
+        };
+        manager.save_theme(&night).unwrap();
+
+        let mut adaptive = AdaptiveThemeManager::new(manager.clone());
+        assert!(adaptive.is_following_system());
+        assert_eq!(
+            adaptive.available_themes(),
+            vec![
+                SYSTEM_THEME,
+                "Dark",
+                "Light",
+                HIGH_CONTRAST_THEME,
+                "Night Shift"
+            ]
+        );
+
+        adaptive.switch_theme("Night Shift").unwrap();
+        assert_eq!(adaptive.name(), "Night Shift");
+        assert_eq!(adaptive.colors(), night.colors);
+        // A named theme isn't replaced by the system one on refresh
+        adaptive.refresh().unwrap();
+        assert_eq!(adaptive.colors(), night.colors);
+
+        assert!(adaptive.switch_theme("Missing").is_err());
+        assert_eq!(adaptive.name(), "Night Shift");
+
+        adaptive.switch_theme(SYSTEM_THEME).unwrap();
+        assert!(adaptive.is_following_system());
+        assert_eq!(adaptive.colors(), ThemeProvider::default().colors());
+
+        let _ = std::fs::remove_dir_all(manager.dir());
+    }
+
+    #[test]
+    fn test_draft_round_trip_and_discard() {
+        let manager = temp_manager("draft");
+        assert_eq!(manager.load_draft(), None);
+
+        let draft = ThemeDraft {
+            base_theme: String::from("Dark"),
+            colors: SimpleColors {
+                primary: [0.5, 0.1, 0.9, 1.0],
+                ..SimpleColors::DARK
+            },
+        };
+        manager.save_draft(&draft).unwrap();
+        assert_eq!(manager.load_draft(), Some(draft));
+
+        // The draft isn't offered as a theme
+        assert_eq!(
+            manager.available_themes(),
+            vec!["Dark", "Light", HIGH_CONTRAST_THEME]
+        );
+
+        manager.discard_draft().unwrap();
+        assert_eq!(manager.load_draft(), None);
+        // Discarding twice is fine
+        manager.discard_draft().unwrap();
+
+        let _ = std::fs::remove_dir_all(manager.dir());
+    }
+
+    #[test]
+    fn test_import_validates_and_exports_round_trip() {
+        let manager = temp_manager("import");
+        let shared = manager.dir().with_extension("shared.json");
+        let theme = ThemeDefinition {
+            name: String::from("Shared"),
+            colors: SimpleColors::LIGHT,
+        };
+        theme.export(&shared).unwrap();
+        assert_eq!(manager.import_theme(&shared).unwrap(), theme);
+        assert!(manager.available_themes().contains(&theme.name));
+
+        std::fs::write(&shared, "{\"name\": \"Broken\"}").unwrap();
+        let err = manager.import_theme(&shared).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("colors"));
+
+        let out_of_range = ThemeDefinition {
+            name: String::from("Too Bright"),
+            colors: SimpleColors {
+                primary: [2.0, 0.0, 0.0, 1.0],
+                ..SimpleColors::DARK
+            },
+        };
+        out_of_range.export(&shared).unwrap();
+        assert!(manager.import_theme(&shared).is_err());
+        assert!(
+            manager
+                .import_theme(Path::new("/nonexistent/theme.json"))
+                .is_err()
+        );
+
+        let _ = std::fs::remove_file(shared);
+        let _ = std::fs::remove_dir_all(manager.dir());
+    }
+
+    #[test]
+    fn test_changed_themes_skips_drafts() {
+        use notify::event::{AccessKind, ModifyKind};
+
+        let event = notify::Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(PathBuf::from("themes/Night_Shift.json"))
+            .add_path(PathBuf::from("themes/editing_draft.json"))
+            .add_path(PathBuf::from("themes/notes.txt"));
+        assert_eq!(
+            changed_themes(&event).collect::<Vec<_>>(),
+            vec![PathBuf::from("themes/Night_Shift.json")]
+        );
+
+        let read = notify::Event::new(EventKind::Access(AccessKind::Any))
+            .add_path(PathBuf::from("themes/Night_Shift.json"));
+        assert_eq!(changed_themes(&read).count(), 0);
+    }
+
+    #[test]
+    fn test_saved_theme_is_listed() {
+        let manager = temp_manager("saved");
+        let theme = ThemeDefinition {
+            name: String::from("Night Shift"),
+            colors: SimpleColors::DARK,
+        };
+        manager.save_theme(&theme).unwrap();
+
+        assert!(manager.available_themes().contains(&theme.name));
+        assert_eq!(manager.load_theme("Night Shift"), Some(theme));
+        assert_eq!(
+            manager.load_theme("Light").map(|t| t.colors),
+            Some(SimpleColors::LIGHT)
+        );
+
+        let _ = std::fs::remove_dir_all(manager.dir());
+    }
+
+    #[test]
+    fn test_names_sharing_a_file_dont_overwrite() {
+        let manager = temp_manager("collide");
+        let theme = ThemeDefinition {
+            name: String::from("Night Shift"),
+            colors: SimpleColors::DARK,
+        };
+        manager.save_theme(&theme).unwrap();
+        let clash = ThemeDefinition {
+            name: String::from("Night_Shift"),
+            colors: SimpleColors::LIGHT,
+        };
+
+        let err = manager.save_theme(&clash).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(manager.load_theme("Night Shift"), Some(theme.clone()));
+        assert_eq!(manager.load_theme("Night_Shift"), None);
+        // Saving under the same name still replaces it
+        manager.save_theme(&theme).unwrap();
+
+        let _ = std::fs::remove_dir_all(manager.dir());
+    }
+}
@@ -0,0 +1,71 @@
+//! Colors for host status and latency, derived from a theme's palette
+
+use iced::Color;
+
+use crate::SimpleColors;
+
+/// What scan results are drawn in, so every view agrees on what a color means
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetworkSpecificColors {
+    pub online: Color,
+    pub offline: Color,
+    pub timeout: Color,
+    pub scanning: Color,
+    /// The fast end of [`Self::latency`]
+    pub low_latency: Color,
+    pub medium_latency: Color,
+    /// The slow end of [`Self::latency`]
+    pub high_latency: Color,
+}
+
+impl NetworkSpecificColors {
+    pub fn from_colors(colors: &SimpleColors) -> Self {
+        Self {
+            online: colors.success_color(),
+            offline: colors.danger_color(),
+            timeout: colors.warning_color(),
+            scanning: colors.primary_color(),
+            low_latency: colors.success_color(),
+            medium_latency: colors.warning_color(),
+            high_latency: colors.danger_color(),
+        }
+    }
+
+    /// Color along the latency gradient, from low at 0.0 through medium to high at 1.0
+    pub fn latency(&self, fraction: f32) -> Color {
+        let fraction = fraction.clamp(0.0, 1.0);
+        match fraction < 0.5 {
+            true => mix(self.low_latency, self.medium_latency, fraction * 2.0),
+            false => mix(self.medium_latency, self.high_latency, fraction * 2.0 - 1.0),
+        }
+    }
+}
+
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    // Weighted so both ends come out exact
+    let lerp = |a: f32, b: f32| a * (1.0 - t) + b * t;
+    Color::from_rgba(
+        lerp(from.r, to.r),
+        lerp(from.g, to.g),
+        lerp(from.b, to.b),
+        lerp(from.a, to.a),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_gradient() {
+        let colors = NetworkSpecificColors::from_colors(&SimpleColors::DARK);
+        assert_eq!(colors.latency(-1.0), colors.low_latency);
+        assert_eq!(colors.latency(0.5), colors.medium_latency);
+        assert_eq!(colors.latency(1.0), colors.high_latency);
+
+        // Between warning orange and danger red
+        let slow = colors.latency(0.75);
+        assert_eq!(slow.r, 1.0);
+        assert!((slow.g - 0.4).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,54 @@
+//! The OS light/dark preference, used by the fallback theme
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+use iced::Subscription;
+use iced::futures::{SinkExt, Stream};
+
+const UNKNOWN: u8 = 0;
+const DARK: u8 = 1;
+const LIGHT: u8 = 2;
+
+/// Last detected appearance, as detecting can mean asking the desktop over D-Bus
+static APPEARANCE: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+/// Whether the OS prefers dark mode
+///
+/// Detected on first use and cached until [`refresh`]. Platforms without a
+/// preference count as dark, matching the theme used before detection existed.
+pub fn prefers_dark() -> bool {
+    if APPEARANCE.load(Ordering::Relaxed) == UNKNOWN {
+        refresh();
+    }
+    APPEARANCE.load(Ordering::Relaxed) != LIGHT
+}
+
+/// Detect the OS appearance again, returning whether it changed
+pub fn refresh() -> bool {
+    let detected = match dark_light::detect() {
+        dark_light::Mode::Light => LIGHT,
+        dark_light::Mode::Dark | dark_light::Mode::Default => DARK,
+    };
+    let previous = APPEARANCE.swap(detected, Ordering::Relaxed);
+    previous != UNKNOWN && previous != detected
+}
+
+/// Emits whether the OS prefers dark mode each time the preference flips,
+/// checking every `interval`
+pub fn changes(interval: Duration) -> Subscription<bool> {
+    Subscription::run_with_id(("system-appearance", interval), poll(interval))
+}
+
+fn poll(interval: Duration) -> impl Stream<Item = bool> {
+    iced::stream::channel(1, move |mut output| async move {
+        prefers_dark();
+        loop {
+            tokio::time::sleep(interval).await;
+            let changed = tokio::task::spawn_blocking(refresh).await.unwrap_or(false);
+            if changed && output.send(prefers_dark()).await.is_err() {
+                return;
+            }
+        }
+    })
+}